@@ -2,7 +2,7 @@ use aoc_common::prelude::*;
 use aoc_data::prelude::*;
 use std::ops::ControlFlow::{Break, Continue};
 
-struct Solver {}
+pub(crate) struct Solver {}
 
 impl ResourceReader for Solver {}
 
@@ -15,6 +15,10 @@ impl Task for Solver {
         Day::Day1
     }
 
+    fn status(&self) -> Status {
+        Status::Complete
+    }
+
     fn solve_part1(&self, input: &str) -> Result<String> {
         input
             .trim()
@@ -56,7 +56,7 @@ fn parse_input(i: &str) -> Result<Vec<Present>> {
     many1(parse_present).parse(i).map_and_finish()
 }
 
-struct Solver {}
+pub(crate) struct Solver {}
 
 impl ResourceReader for Solver {}
 
@@ -69,6 +69,10 @@ impl Task for Solver {
         Day::Day2
     }
 
+    fn status(&self) -> Status {
+        Status::Complete
+    }
+
     fn solve_part1(&self, input: &str) -> Result<String> {
         let presents = parse_input(input.trim())?;
         let total_area: u32 = presents.iter().map(|p| p.surface_area()).sum();
@@ -81,7 +81,7 @@ impl Santa {
     }
 }
 
-struct Solver {}
+pub(crate) struct Solver {}
 
 impl ResourceReader for Solver {}
 
@@ -94,6 +94,10 @@ impl Task for Solver {
         Day::Day3
     }
 
+    fn status(&self) -> Status {
+        Status::Complete
+    }
+
     fn solve_part1(&self, input: &str) -> Result<String> {
         let moves = parse_input(input)?;
         let mut santa = Santa::new();
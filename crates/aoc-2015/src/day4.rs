@@ -1,7 +1,8 @@
+use aoc_common::hashing::{self, NonceSearch};
 use aoc_common::prelude::*;
 use aoc_data::prelude::*;
 
-struct Solver {}
+pub(crate) struct Solver {}
 
 impl ResourceReader for Solver {}
 
@@ -14,35 +15,28 @@ impl Task for Solver {
         Day::Day4
     }
 
+    fn status(&self) -> Status {
+        Status::Complete
+    }
+
     fn solve_part1(&self, input: &str) -> Result<String> {
-        let key = input.trim();
-        for i in 0..1_000_000_000 {
-            let test_string = format!("{}{}", key, i);
-            let digest = md5::compute(test_string.as_bytes());
-            if digest.0[0] == 0 && digest.0[1] == 0 && (digest.0[2] & 0xF0) == 0 {
-                return Ok(i.to_string());
-            }
-        }
-        Err(AdventError::Other(
-            "No valid number found in range".to_string(),
-        ))
+        mine(input.trim(), 5)
     }
 
     fn solve_part2(&self, input: &str) -> Result<String> {
-        let key = input.trim();
-        for i in 0..1_000_000_000 {
-            let test_string = format!("{}{}", key, i);
-            let digest = md5::compute(test_string.as_bytes());
-            if digest.0[0] == 0 && digest.0[1] == 0 && digest.0[2] == 0 {
-                return Ok(i.to_string());
-            }
-        }
-        Err(AdventError::Other(
-            "No valid number found in range".to_string(),
-        ))
+        mine(input.trim(), 6)
     }
 }
 
+/// The lowest number that, after the key, hashes to at least `zeros` leading zeros in hex.
+fn mine(key: &str, zeros: usize) -> Result<String> {
+    NonceSearch::new(key)
+        .with_range(0..1_000_000_000)
+        .first(hashing::zeros(zeros))
+        .map(|(nonce, _)| nonce.to_string())
+        .ok_or_else(|| AdventError::Other("No valid number found in range".to_string()))
+}
+
 #[cfg(test)]
 mod test {
 
@@ -75,7 +75,7 @@ impl Part2 for String {
     }
 }
 
-struct Solver {}
+pub(crate) struct Solver {}
 
 impl ResourceReader for Solver {}
 
@@ -88,6 +88,10 @@ impl Task for Solver {
         Day::Day5
     }
 
+    fn status(&self) -> Status {
+        Status::Complete
+    }
+
     fn solve_part1(&self, input: &str) -> Result<String> {
         let count = input
             .lines()
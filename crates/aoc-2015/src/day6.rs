@@ -1,3 +1,4 @@
+use aoc_common::grid::Grid;
 use aoc_common::prelude::*;
 use aoc_data::prelude::*;
 use nom::branch::alt;
@@ -7,6 +8,7 @@ use nom::character::complete::{newline, space1};
 use nom::combinator::opt;
 use nom::multi::many1;
 use nom::sequence::{separated_pair, terminated};
+use std::ops::Range;
 
 struct Coordinate {
     x: usize,
@@ -29,40 +31,109 @@ struct Instruction {
     bounds: Bounds,
 }
 
-struct Grid<T> {
-    lights: Vec<Vec<T>>,
+/// One axis of the grid cut at every rectangle edge, so that each slice between consecutive
+/// cuts is covered by exactly the same instructions and can stand in for all of its lights.
+struct Axis {
+    cuts: Vec<usize>,
 }
 
-type LitGrid = Grid<bool>;
-type BrightnessGrid = Grid<u32>;
+impl Axis {
+    fn new(spans: impl Iterator<Item = (usize, usize)>) -> Axis {
+        let mut cuts = spans
+            .flat_map(|(start, end)| [start, end + 1])
+            .collect_vec();
+        cuts.sort_unstable();
+        cuts.dedup();
+        Axis { cuts }
+    }
+    fn slices(&self) -> usize {
+        self.cuts.len().saturating_sub(1)
+    }
+    /// The slices making up `start..=end`, which must both be edges of some instruction.
+    fn span(&self, start: usize, end: usize) -> Range<usize> {
+        let slice = |at| self.cuts.binary_search(&at).unwrap_or_else(|i| i);
+        slice(start)..slice(end + 1)
+    }
+    fn width(&self, slice: usize) -> u64 {
+        (self.cuts[slice + 1] - self.cuts[slice]) as u64
+    }
+}
+
+enum Layout {
+    /// One cell per light on the 1000x1000 grid.
+    Kernel,
+    /// One cell per block of lights that every instruction treats alike.
+    Compressed { xs: Axis, ys: Axis },
+}
+
+struct Lights<T> {
+    lights: Grid<T>,
+    layout: Layout,
+}
+
+type LitGrid = Lights<bool>;
+type BrightnessGrid = Lights<u32>;
 
 type Kernel<T> = fn(&mut T);
 
+impl<T: Clone> Lights<T> {
+    fn new(kind: &SolverKind, instructions: &[Instruction], off: T) -> Lights<T> {
+        match kind {
+            SolverKind::Kernel => Lights {
+                lights: Grid::filled(1000, 1000, off),
+                layout: Layout::Kernel,
+            },
+            SolverKind::Compressed => {
+                let bounds = instructions.iter().map(|i| &i.bounds);
+                let xs = Axis::new(bounds.clone().map(|b| (b.start.x, b.end.x)));
+                let ys = Axis::new(bounds.map(|b| (b.start.y, b.end.y)));
+                Lights {
+                    lights: Grid::filled(xs.slices(), ys.slices(), off),
+                    layout: Layout::Compressed { xs, ys },
+                }
+            }
+        }
+    }
+}
+
 trait KernelApply<T> {
-    fn lights(&mut self) -> &mut Vec<Vec<T>>;
+    fn lights(&mut self) -> &mut Grid<T>;
+    fn layout(&self) -> &Layout;
+    /// The `(x, y)` ranges of cells holding the lights within `bounds`.
+    fn cells(&self, bounds: &Bounds) -> (Range<usize>, Range<usize>) {
+        let (start, end) = (&bounds.start, &bounds.end);
+        match self.layout() {
+            Layout::Kernel => (start.x..end.x + 1, start.y..end.y + 1),
+            Layout::Compressed { xs, ys } => (xs.span(start.x, end.x), ys.span(start.y, end.y)),
+        }
+    }
+    /// How many lights the cell at `(row, col)` stands for.
+    fn weight(&self, (row, col): (usize, usize)) -> u64 {
+        match self.layout() {
+            Layout::Kernel => 1,
+            Layout::Compressed { xs, ys } => xs.width(col) * ys.width(row),
+        }
+    }
     fn apply_kernel(&mut self, bounds: &Bounds, op: Kernel<T>) {
-        let start = &bounds.start;
-        let end = &bounds.end;
-        for x in start.x..=end.x {
-            for y in start.y..=end.y {
-                op(&mut self.lights()[x][y]);
+        let (xs, ys) = self.cells(bounds);
+        for x in xs {
+            for y in ys.clone() {
+                op(&mut self.lights()[(y, x)]);
             }
         }
     }
 }
 
-impl<T> KernelApply<T> for Grid<T> {
-    fn lights(&mut self) -> &mut Vec<Vec<T>> {
+impl<T> KernelApply<T> for Lights<T> {
+    fn lights(&mut self) -> &mut Grid<T> {
         &mut self.lights
     }
+    fn layout(&self) -> &Layout {
+        &self.layout
+    }
 }
 
 impl LitGrid {
-    fn new() -> LitGrid {
-        LitGrid {
-            lights: vec![vec![false; 1000]; 1000],
-        }
-    }
     fn apply(&mut self, instructions: &[Instruction]) {
         let op_turn_on = |light: &mut bool| {
             *light = true;
@@ -81,21 +152,60 @@ impl LitGrid {
             }
         }
     }
-    fn total_lit(&self) -> usize {
+    fn total_lit(&self) -> u64 {
         self.lights
             .iter()
-            .map(|row| row.iter().filter(|&&light| light).count())
+            .filter(|&(_, &light)| light)
+            .map(|(pos, _)| self.weight(pos))
             .sum()
     }
 }
 
-impl BrightnessGrid {
-    fn new() -> BrightnessGrid {
-        BrightnessGrid {
-            lights: vec![vec![0; 1000]; 1000],
+/// Pending brightness increases, added over rectangles in constant time and only summed into
+/// the lights when something needs their actual values.
+struct DifferenceArray {
+    deltas: Grid<i64>,
+    pending: bool,
+}
+
+impl DifferenceArray {
+    fn new(width: usize, height: usize) -> DifferenceArray {
+        DifferenceArray {
+            deltas: Grid::filled(width + 1, height + 1, 0),
+            pending: false,
         }
     }
+    fn add(&mut self, (xs, ys): (Range<usize>, Range<usize>), amount: i64) {
+        self.deltas[(ys.start, xs.start)] += amount;
+        self.deltas[(ys.start, xs.end)] -= amount;
+        self.deltas[(ys.end, xs.start)] -= amount;
+        self.deltas[(ys.end, xs.end)] += amount;
+        self.pending = true;
+    }
+    /// Adds the pending increases to `lights` by summing the deltas, then clears them.
+    fn flush(&mut self, lights: &mut Grid<u32>) {
+        if !self.pending {
+            return;
+        }
+        let mut above = vec![0; lights.width()];
+        for row in 0..lights.height() {
+            let mut running = 0;
+            for col in 0..lights.width() {
+                running += self.deltas[(row, col)];
+                above[col] += running;
+                lights[(row, col)] += above[col] as u32;
+            }
+        }
+        self.deltas = Grid::filled(self.deltas.width(), self.deltas.height(), 0);
+        self.pending = false;
+    }
+}
+
+impl BrightnessGrid {
     fn apply(&mut self, instructions: &[Instruction]) {
+        if let Layout::Compressed { .. } = self.layout {
+            return self.apply_batched(instructions);
+        }
         let op_turn_on = |light: &mut u32| {
             *light += 1;
         };
@@ -113,13 +223,33 @@ impl BrightnessGrid {
             }
         }
     }
-    fn total_brightness(&self) -> u32 {
-        self.lights.iter().map(|row| row.iter().sum::<u32>()).sum()
+    /// Increases commute, so runs of them go through a difference array; only turning lights
+    /// down, which stops at zero, has to see the real brightness and visit each cell.
+    fn apply_batched(&mut self, instructions: &[Instruction]) {
+        let mut increases = DifferenceArray::new(self.lights.width(), self.lights.height());
+        for instruction in instructions {
+            let cells = self.cells(&instruction.bounds);
+            match instruction.operation {
+                Operation::TurnOn => increases.add(cells, 1),
+                Operation::Toggle => increases.add(cells, 2),
+                Operation::TurnOff => {
+                    increases.flush(&mut self.lights);
+                    self.apply_kernel(&instruction.bounds, |light| *light -= 1.min(*light));
+                }
+            }
+        }
+        increases.flush(&mut self.lights);
+    }
+    fn total_brightness(&self) -> u64 {
+        self.lights
+            .iter()
+            .map(|(pos, &light)| u64::from(light) * self.weight(pos))
+            .sum()
     }
 }
 
 fn complete_coordinate(i: &str) -> IResult<&str, Coordinate> {
-    separated_pair(complete::u32, tag(","), complete::u32)
+    separated_pair(complete::u64, tag(","), complete::u64)
         .map(|(x, y)| Coordinate {
             x: x as usize,
             y: y as usize,
@@ -159,7 +289,16 @@ fn parse_input(i: &str) -> Result<Vec<Instruction>> {
     many1(parse_instruction).parse(i).map_and_finish()
 }
 
-struct Solver {}
+pub(crate) enum SolverKind {
+    /// Update every light inside each rectangle.
+    Kernel,
+    /// Update blocks of lights cut along the rectangles' edges, at any coordinates.
+    Compressed,
+}
+
+pub(crate) struct Solver {
+    pub(crate) kind: SolverKind,
+}
 
 impl ResourceReader for Solver {}
 
@@ -172,9 +311,20 @@ impl Task for Solver {
         Day::Day6
     }
 
+    fn status(&self) -> Status {
+        Status::Complete
+    }
+
+    fn strategy(&self) -> &str {
+        match self.kind {
+            SolverKind::Kernel => "kernel",
+            SolverKind::Compressed => "compressed",
+        }
+    }
+
     fn solve_part1(&self, input: &str) -> Result<String> {
         let instructions = parse_input(input)?;
-        let mut grid = LitGrid::new();
+        let mut grid = LitGrid::new(&self.kind, &instructions, false);
         grid.apply(&instructions);
         let total = grid.total_lit();
         Ok(total.to_string())
@@ -182,7 +332,7 @@ impl Task for Solver {
 
     fn solve_part2(&self, input: &str) -> Result<String> {
         let instruction = parse_input(input)?;
-        let mut grid = BrightnessGrid::new();
+        let mut grid = BrightnessGrid::new(&self.kind, &instruction, 0);
         grid.apply(&instruction);
         let total = grid.total_brightness();
         Ok(total.to_string())
@@ -195,16 +345,16 @@ mod test {
     use super::*;
     use rstest::*;
 
-    #[fixture]
-    fn solver() -> Solver {
-        Solver {}
-    }
-
     #[rstest]
     #[case("turn on 0,0 through 999,999", "1000000")]
     #[case("toggle 0,0 through 999,0", "1000")]
     #[case("turn off 499,499 through 500,500", "0")]
-    fn example1(solver: Solver, #[case] input: String, #[case] expected: String) -> Result<()> {
+    fn example1(
+        #[values(SolverKind::Kernel, SolverKind::Compressed)] kind: SolverKind,
+        #[case] input: String,
+        #[case] expected: String,
+    ) -> Result<()> {
+        let solver = Solver { kind };
         assert_eq!(solver.solve_part1(&input)?, expected);
         Ok(())
     }
@@ -212,20 +362,51 @@ mod test {
     #[rstest]
     #[case("turn on 0,0 through 0,0", "1")]
     #[case("toggle 0,0 through 999,999", "2000000")]
-    fn example2(solver: Solver, #[case] input: String, #[case] expected: String) -> Result<()> {
+    fn example2(
+        #[values(SolverKind::Kernel, SolverKind::Compressed)] kind: SolverKind,
+        #[case] input: String,
+        #[case] expected: String,
+    ) -> Result<()> {
+        let solver = Solver { kind };
         assert_eq!(solver.solve_part2(&input)?, expected);
         Ok(())
     }
 
     #[rstest]
-    fn part1(solver: Solver) -> Result<()> {
+    #[case(
+        "turn on 0,0 through 999999999,999999999\ntoggle 1,1 through 999999999,999999999",
+        "1999999999",
+        "2999999996000000002"
+    )]
+    #[case(
+        "toggle 5,5 through 2000000,9\nturn off 0,0 through 1000000,1000000\nturn on 7,8 through 7,8",
+        "5000001",
+        "14999981"
+    )]
+    fn huge_extents(
+        #[case] input: String,
+        #[case] lit: String,
+        #[case] brightness: String,
+    ) -> Result<()> {
+        let solver = Solver {
+            kind: SolverKind::Compressed,
+        };
+        assert_eq!(solver.solve_part1(&input)?, lit);
+        assert_eq!(solver.solve_part2(&input)?, brightness);
+        Ok(())
+    }
+
+    #[rstest]
+    fn part1(#[values(SolverKind::Kernel, SolverKind::Compressed)] kind: SolverKind) -> Result<()> {
+        let solver = Solver { kind };
         let input = solver.read_resource(Input::Part1)?;
         assert_eq!(solver.solve_part1(&input)?, "569999");
         Ok(())
     }
 
     #[rstest]
-    fn part2(solver: Solver) -> Result<()> {
+    fn part2(#[values(SolverKind::Kernel, SolverKind::Compressed)] kind: SolverKind) -> Result<()> {
+        let solver = Solver { kind };
         let input = solver.read_resource(Input::Part2)?;
         assert_eq!(solver.solve_part2(&input)?, "17836115");
         Ok(())
@@ -1,3 +1,4 @@
+use aoc_common::memo::Memo;
 use aoc_common::prelude::*;
 use aoc_data::prelude::*;
 use nom::branch::alt;
@@ -6,12 +7,11 @@ use nom::character::complete;
 use nom::combinator::{map, opt};
 use nom::multi::many1;
 use nom::sequence::{preceded, separated_pair, terminated};
-use std::cell::RefCell;
 use std::collections::HashMap;
 
 struct Circuit {
     wires: HashMap<Wire, Connection>,
-    cache: RefCell<HashMap<Wire, u16>>,
+    signals: Memo<Wire, Option<u16>>,
 }
 
 impl Circuit {
@@ -20,8 +20,8 @@ impl Circuit {
             .iter()
             .map(|conn| (conn.output.clone(), conn.clone()))
             .collect();
-        let cache = RefCell::new(HashMap::new());
-        Self { wires, cache }
+        let signals = Memo::new();
+        Self { wires, signals }
     }
     fn override_wire(&mut self, wire: &str, input: ConnInput) {
         let connection = Connection {
@@ -29,54 +29,50 @@ impl Circuit {
             output: wire.to_string(),
         };
         self.wires.insert(wire.to_string(), connection);
-        self.cache.borrow_mut().clear();
+        self.signals.clear();
     }
-    fn input_signal(&self, input: &ConnInput) -> Option<u16> {
-        match input {
-            ConnInput::Raw(value) => Some(*value),
-            ConnInput::Wire(wire) => self.wire_signal(wire),
-        }
+    fn wire_signal(&mut self, wire: &str) -> Option<u16> {
+        let wires = &self.wires;
+        self.signals
+            .get_or_compute(wire.to_string(), &|signal, wire| {
+                evaluate(wires.get(wire)?, signal)
+            })
     }
-    fn wire_signal(&self, wire: &str) -> Option<u16> {
-        if let Some(cached) = self.cache.borrow().get(wire) {
-            return Some(*cached);
-        }
-
-        let connection = self.wires.get(wire);
-        let signal = match connection {
-            Some(connection) => match &connection.operation {
-                Operation::Signal(value) => self.input_signal(value),
-                Operation::And(left, right) => {
-                    let left_signal = self.input_signal(left)?;
-                    let right_signal = self.input_signal(right)?;
-                    Some(left_signal & right_signal)
-                }
-                Operation::Or(left, right) => {
-                    let left_signal = self.input_signal(left)?;
-                    let right_signal = self.input_signal(right)?;
-                    Some(left_signal | right_signal)
-                }
-                Operation::LShift(input, shift) => {
-                    let input_signal = self.input_signal(input)?;
-                    Some(input_signal << shift)
-                }
-                Operation::RShift(input, shift) => {
-                    let input_signal = self.input_signal(input)?;
-                    Some(input_signal >> shift)
-                }
-                Operation::Not(input) => {
-                    let input_signal = self.input_signal(input)?;
-                    Some(!input_signal)
-                }
-            },
-            None => None,
-        };
+}
 
-        if let Some(sig) = signal {
-            self.cache.borrow_mut().insert(wire.to_string(), sig);
+/// The signal a connection puts out, given a way to find the signal on any other wire.
+fn evaluate(
+    connection: &Connection,
+    wire_signal: &mut dyn FnMut(Wire) -> Option<u16>,
+) -> Option<u16> {
+    let mut input_signal = |input: &ConnInput| match input {
+        ConnInput::Raw(value) => Some(*value),
+        ConnInput::Wire(wire) => wire_signal(wire.clone()),
+    };
+    match &connection.operation {
+        Operation::Signal(value) => input_signal(value),
+        Operation::And(left, right) => {
+            let left_signal = input_signal(left)?;
+            let right_signal = input_signal(right)?;
+            Some(left_signal & right_signal)
+        }
+        Operation::Or(left, right) => {
+            let left_signal = input_signal(left)?;
+            let right_signal = input_signal(right)?;
+            Some(left_signal | right_signal)
+        }
+        Operation::LShift(input, shift) => {
+            let input_signal = input_signal(input)?;
+            Some(input_signal << shift)
+        }
+        Operation::RShift(input, shift) => {
+            let input_signal = input_signal(input)?;
+            Some(input_signal >> shift)
+        }
+        Operation::Not(input) => {
+            let input_signal = input_signal(input)?;
+            Some(!input_signal)
         }
-
-        signal
     }
 }
 
@@ -227,7 +223,7 @@ fn parse_input(i: &str) -> Result<Circuit> {
     parse_circuit(i).map_and_finish()
 }
 
-struct Solver {}
+pub(crate) struct Solver {}
 
 impl ResourceReader for Solver {}
 
@@ -240,8 +236,12 @@ impl Task for Solver {
         Day::Day7
     }
 
+    fn status(&self) -> Status {
+        Status::Complete
+    }
+
     fn solve_part1(&self, input: &str) -> Result<String> {
-        let circuit = parse_input(input)?;
+        let mut circuit = parse_input(input)?;
         let signal = circuit.wire_signal("a").ok_or_else(|| {
             AdventError::Other("Could not determine signal for wire 'a'".to_string())
         })?;
@@ -275,7 +275,7 @@ mod test {
     #[rstest]
     fn example1(solver: Solver) -> Result<()> {
         let input = solver.read_resource(Input::Example1)?;
-        let circuit = parse_input(&input)?;
+        let mut circuit = parse_input(&input)?;
 
         assert_eq!(circuit.wire_signal("a"), None);
 
@@ -133,7 +133,7 @@ fn parse_input(i: &str) -> Result<Vec<Action>> {
     many1(parse_action).parse(i).map_and_finish()
 }
 
-struct Solver {}
+pub(crate) struct Solver {}
 
 impl ResourceReader for Solver {}
 
@@ -146,6 +146,10 @@ impl Task for Solver {
         Day::Day1
     }
 
+    fn status(&self) -> Status {
+        Status::Complete
+    }
+
     fn solve_part1(&self, input: &str) -> Result<String> {
         let actions = parse_input(input.trim())?;
         let state = actions
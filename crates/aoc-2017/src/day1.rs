@@ -13,7 +13,7 @@ fn kernel(acc: u32, pair: (char, char)) -> Result<u32> {
     }
 }
 
-struct Solver {}
+pub(crate) struct Solver {}
 
 impl ResourceReader for Solver {}
 
@@ -26,6 +26,10 @@ impl Task for Solver {
         Day::Day1
     }
 
+    fn status(&self) -> Status {
+        Status::Complete
+    }
+
     fn solve_part1(&self, input: &str) -> Result<String> {
         let trimmed = input.trim();
         trimmed
@@ -1,7 +1,20 @@
+use aoc_common::hex::{Axial, FlatDirection, flat_path};
 use aoc_common::prelude::*;
 use aoc_data::prelude::*;
 
-struct Solver {}
+/// Where the child process is after each step, starting from the origin.
+fn positions(path: &[FlatDirection]) -> impl Iterator<Item = Axial> {
+    path.iter().scan(Axial::default(), |hex, step| {
+        *hex += step.delta();
+        Some(*hex)
+    })
+}
+
+fn parse_input(input: &str) -> Result<Vec<FlatDirection>> {
+    flat_path(input.trim()).map_and_finish()
+}
+
+pub(crate) struct Solver {}
 
 impl ResourceReader for Solver {}
 
@@ -14,12 +27,23 @@ impl Task for Solver {
         Day::Day11
     }
 
+    fn status(&self) -> Status {
+        Status::Complete
+    }
+
     fn solve_part1(&self, input: &str) -> Result<String> {
-        Ok("0".to_string())
+        let path = parse_input(input)?;
+        let end = positions(&path).last().unwrap_or_default();
+        Ok(end.distance(Axial::default()).to_string())
     }
 
     fn solve_part2(&self, input: &str) -> Result<String> {
-        Ok("0".to_string())
+        let path = parse_input(input)?;
+        let furthest = positions(&path)
+            .map(|hex| hex.distance(Axial::default()))
+            .max()
+            .unwrap_or_default();
+        Ok(furthest.to_string())
     }
 }
 
@@ -34,6 +58,22 @@ mod test {
         Solver {}
     }
 
+    #[rstest]
+    #[case("ne,ne,ne", "3")]
+    #[case("ne,ne,sw,sw", "0")]
+    #[case("ne,ne,s,s", "2")]
+    #[case("se,sw,se,sw,sw", "3")]
+    fn examples_part1(solver: Solver, #[case] input: &str, #[case] expected: &str) -> Result<()> {
+        assert_eq!(solver.solve_part1(input)?, expected);
+        Ok(())
+    }
+
+    #[rstest]
+    fn example_part2(solver: Solver) -> Result<()> {
+        assert_eq!(solver.solve_part2("ne,ne,sw,sw")?, "2");
+        Ok(())
+    }
+
     #[rstest]
     fn example1(solver: Solver) -> Result<()> {
         let input = solver.read_resource(Input::Example1)?;
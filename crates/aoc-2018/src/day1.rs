@@ -32,7 +32,7 @@ fn parse_input(i: &str) -> Result<Vec<i32>> {
     many1(parse_delta).parse(i).map_and_finish()
 }
 
-struct Solver {}
+pub(crate) struct Solver {}
 
 impl ResourceReader for Solver {}
 
@@ -45,6 +45,10 @@ impl Task for Solver {
         Day::Day1
     }
 
+    fn status(&self) -> Status {
+        Status::Complete
+    }
+
     fn solve_part1(&self, input: &str) -> Result<String> {
         let deltas = parse_input(input.trim())?;
         let frequency: i32 = deltas.iter().sum();
@@ -28,7 +28,7 @@ fn parse_input(i: &str) -> Result<Vec<i32>> {
     many1(parse_mass).parse(i).map_and_finish()
 }
 
-struct Solver {}
+pub(crate) struct Solver {}
 
 impl ResourceReader for Solver {}
 
@@ -41,6 +41,10 @@ impl Task for Solver {
         Day::Day1
     }
 
+    fn status(&self) -> Status {
+        Status::Complete
+    }
+
     fn solve_part1(&self, input: &str) -> Result<String> {
         let masses = parse_input(input.trim())?;
         let fuel = masses.iter().map(calculate_fuel).sum::<i32>();
@@ -1,7 +1,7 @@
 use aoc_common::prelude::*;
 use aoc_data::prelude::*;
 
-struct Solver {}
+pub(crate) struct Solver {}
 
 impl ResourceReader for Solver {}
 
@@ -14,6 +14,10 @@ impl Task for Solver {
         Day::Day17
     }
 
+    fn status(&self) -> Status {
+        Status::Unimplemented
+    }
+
     fn solve_part1(&self, input: &str) -> Result<String> {
         Ok("0".to_string())
     }
@@ -14,7 +14,7 @@ fn parse_input(i: &str) -> Result<Vec<i32>> {
     many1(parse_expense).parse(i).map_and_finish()
 }
 
-struct Solver {}
+pub(crate) struct Solver {}
 
 impl ResourceReader for Solver {}
 
@@ -27,6 +27,10 @@ impl Task for Solver {
         Day::Day1
     }
 
+    fn status(&self) -> Status {
+        Status::Complete
+    }
+
     fn solve_part1(&self, input: &str) -> Result<String> {
         let expenses = parse_input(input.trim())?;
         expenses
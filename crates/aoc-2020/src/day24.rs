@@ -1,7 +1,42 @@
+use aoc_common::hex::{Axial, PointyDirection, life_step, pointy_path};
 use aoc_common::prelude::*;
 use aoc_data::prelude::*;
+use nom::combinator::all_consuming;
+use std::collections::HashSet;
 
-struct Solver {}
+type Path = Vec<PointyDirection>;
+
+/// The tiles flipped an odd number of times, which end up black side up.
+fn black_tiles(paths: &[Path]) -> HashSet<Axial> {
+    let mut black = HashSet::new();
+    for path in paths {
+        let tile = path
+            .iter()
+            .fold(Axial::default(), |hex, step| hex + step.delta());
+        if !black.remove(&tile) {
+            black.insert(tile);
+        }
+    }
+    black
+}
+
+/// Black tiles with no or more than two black neighbours turn white; white tiles with exactly
+/// two turn black.
+fn living_art(black: &HashSet<Axial>) -> HashSet<Axial> {
+    life_step(black, |is_black, n| match is_black {
+        true => n == 1 || n == 2,
+        false => n == 2,
+    })
+}
+
+fn parse_input(input: &str) -> Result<Vec<Path>> {
+    input
+        .lines()
+        .map(|line| all_consuming(pointy_path).parse(line).map_and_finish())
+        .collect()
+}
+
+pub(crate) struct Solver {}
 
 impl ResourceReader for Solver {}
 
@@ -14,12 +49,22 @@ impl Task for Solver {
         Day::Day24
     }
 
+    fn status(&self) -> Status {
+        Status::Complete
+    }
+
     fn solve_part1(&self, input: &str) -> Result<String> {
-        Ok("0".to_string())
+        let paths = parse_input(input)?;
+        Ok(black_tiles(&paths).len().to_string())
     }
 
     fn solve_part2(&self, input: &str) -> Result<String> {
-        Ok("0".to_string())
+        let paths = parse_input(input)?;
+        let mut black = black_tiles(&paths);
+        for _ in 0..100 {
+            black = living_art(&black);
+        }
+        Ok(black.len().to_string())
     }
 }
 
@@ -37,14 +82,14 @@ mod test {
     #[rstest]
     fn example1(solver: Solver) -> Result<()> {
         let input = solver.read_resource(Input::Example1)?;
-        assert_eq!(solver.solve_part1(&input)?, "0");
+        assert_eq!(solver.solve_part1(&input)?, "10");
         Ok(())
     }
 
     #[rstest]
     fn example2(solver: Solver) -> Result<()> {
         let input = solver.read_resource(Input::Example2)?;
-        assert_eq!(solver.solve_part2(&input)?, "0");
+        assert_eq!(solver.solve_part2(&input)?, "2208");
         Ok(())
     }
 
@@ -13,7 +13,7 @@ fn parse_input(i: &str) -> Result<Vec<i32>> {
     many1(parse_depth).parse(i).map_and_finish()
 }
 
-struct Solver {}
+pub(crate) struct Solver {}
 
 impl ResourceReader for Solver {}
 
@@ -26,6 +26,10 @@ impl Task for Solver {
         Day::Day1
     }
 
+    fn status(&self) -> Status {
+        Status::Complete
+    }
+
     fn solve_part1(&self, input: &str) -> Result<String> {
         let depths = parse_input(input.trim())?;
         let count = depths.iter().tuple_windows().filter(|(a, b)| b > a).count();
@@ -1,9 +1,7 @@
+use aoc_common::geom::{Direction4, Point2};
+use aoc_common::grid::Grid;
 use aoc_common::prelude::*;
 use aoc_data::prelude::*;
-use nom::AsChar;
-use nom::character::complete::{newline, satisfy};
-use nom::combinator::map;
-use nom::multi::{many1, separated_list1};
 use std::collections::HashSet;
 use std::ops::Index;
 
@@ -11,33 +9,7 @@ type Height = usize;
 type Score = usize;
 type Rating = usize;
 
-#[derive(EnumIter)]
-enum Direction {
-    Up,
-    Down,
-    Left,
-    Right,
-}
-
-#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
-struct Coordinate {
-    row: i32,
-    col: i32,
-}
-
-impl Coordinate {
-    fn new(row: i32, col: i32) -> Self {
-        Self { row, col }
-    }
-    fn moved(&self, direction: Direction) -> Self {
-        match direction {
-            Direction::Up => Self::new(self.row - 1, self.col),
-            Direction::Down => Self::new(self.row + 1, self.col),
-            Direction::Left => Self::new(self.row, self.col - 1),
-            Direction::Right => Self::new(self.row, self.col + 1),
-        }
-    }
-}
+type Coordinate = Point2<i32>;
 
 type Trail = Vec<Coordinate>;
 type Trails = Vec<Trail>;
@@ -66,46 +38,21 @@ impl TrailsExt for Trails {
 }
 
 struct Map {
-    heights: Vec<Vec<Height>>,
-    rows: usize,
-    cols: usize,
+    heights: Grid<Height>,
 }
 
 impl Map {
-    fn new(heights: Vec<Vec<Height>>) -> Self {
-        let rows = heights.len();
-        let cols = heights.first().map_or(0, |row| row.len());
-        assert!(heights.iter().all(|row| row.len() == cols));
-        Self {
-            heights,
-            rows,
-            cols,
-        }
-    }
-    fn show(&self) {
-        for row in &self.heights {
-            for height in row {
-                if height == &100 {
-                    print!(". ");
-                } else {
-                    print!("{} ", height);
-                }
-            }
-            println!();
-        }
-    }
-    fn coordinates(&self) -> CoordinateIterator {
-        CoordinateIterator {
-            current_index: 0,
-            rows: self.rows,
-            cols: self.cols,
-        }
+    fn new(heights: Grid<Height>) -> Self {
+        Self { heights }
     }
     fn coordinates_with_height(&self, height: Height) -> Vec<Coordinate> {
-        self.coordinates().filter(|c| self[c] == height).collect()
+        self.heights
+            .positions(|&h| h == height)
+            .map(|(row, col)| Coordinate::new(col as i32, row as i32))
+            .collect()
     }
     fn coordinate_is_valid(&self, c: &Coordinate) -> bool {
-        (0..self.rows as i32).contains(&c.row) && (0..self.cols as i32).contains(&c.col)
+        self.heights.contains(c)
     }
     fn trails_from(&self, start: &Coordinate, allow_retrace: bool) -> Trails {
         let mut trails = vec![];
@@ -132,8 +79,8 @@ impl Map {
 
             let current_height = map[start];
 
-            let next_coordinates = Direction::iter()
-                .map(|direction| start.moved(direction))
+            let next_coordinates = Direction4::iter()
+                .map(|direction| *start + direction.delta())
                 .filter(|c| map.coordinate_is_valid(c))
                 .filter(|c| current_height + 1 == map[c])
                 .collect_vec();
@@ -158,48 +105,16 @@ impl Index<&Coordinate> for Map {
     type Output = Height;
 
     fn index(&self, c: &Coordinate) -> &Self::Output {
-        assert!(self.coordinate_is_valid(c));
-        &self.heights[c.row as usize][c.col as usize]
-    }
-}
-
-struct CoordinateIterator {
-    current_index: usize,
-    rows: usize,
-    cols: usize,
-}
-
-impl Iterator for CoordinateIterator {
-    type Item = Coordinate;
-    fn next(&mut self) -> Option<Self::Item> {
-        if self.current_index < self.rows * self.cols {
-            let row = (self.current_index / self.cols) as i32;
-            let col = (self.current_index % self.cols) as i32;
-            self.current_index += 1;
-            Some(Coordinate::new(row, col))
-        } else {
-            None
-        }
+        &self.heights[c]
     }
 }
 
-fn parse_map(i: &str) -> IResult<&str, Map> {
-    let (i, heights) = separated_list1(
-        newline,
-        many1(map(satisfy(|c| AsChar::is_dec_digit(c as u8)), |c| {
-            c.to_string().parse::<Height>().unwrap()
-        })),
-    )
-    .parse(i)?;
-    let map = Map::new(heights);
-    Ok((i, map))
-}
-
 fn parse_input(input: &str) -> Result<Map> {
-    parse_map(input).map_and_finish()
+    let heights = Grid::parse(input.trim(), |c| c.to_digit(10).map(|d| d as Height))?;
+    Ok(Map::new(heights))
 }
 
-struct Solver {}
+pub(crate) struct Solver {}
 
 impl ResourceReader for Solver {}
 
@@ -212,9 +127,12 @@ impl Task for Solver {
         Day::Day10
     }
 
+    fn status(&self) -> Status {
+        Status::Complete
+    }
+
     fn solve_part1(&self, input: &str) -> Result<String> {
         let map = parse_input(input)?;
-        // map.show();
         let total_score: Score = map
             .coordinates_with_height(0)
             .into_iter()
@@ -226,7 +144,6 @@ impl Task for Solver {
 
     fn solve_part2(&self, input: &str) -> Result<String> {
         let map = parse_input(input)?;
-        // map.show();
         let total_rating: Score = map
             .coordinates_with_height(0)
             .into_iter()
@@ -47,7 +47,7 @@ fn parse_input(input: &str) -> Result<Stones> {
     parse_stones(input).map_and_finish()
 }
 
-struct Solver {}
+pub(crate) struct Solver {}
 
 impl ResourceReader for Solver {}
 
@@ -60,6 +60,10 @@ impl Task for Solver {
         Day::Day11
     }
 
+    fn status(&self) -> Status {
+        Status::Complete
+    }
+
     fn solve_part1(&self, input: &str) -> Result<String> {
         let mut stones = parse_input(input)?;
         for _ in 0..25 {
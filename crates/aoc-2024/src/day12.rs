@@ -1,47 +1,20 @@
+use aoc_common::dsu::KeyedUnionFind;
+use aoc_common::geom::{Direction4, Point2};
+use aoc_common::grid::Grid;
 use aoc_common::prelude::*;
 use aoc_data::prelude::*;
 use itertools::MinMaxResult;
-use nom::AsChar;
-use nom::character::complete::{newline, satisfy};
-use nom::multi::{many1, separated_list1};
 use std::collections::{HashMap, HashSet};
 use std::ops::Index;
 
-#[derive(Debug, EnumIter, Clone, Copy)]
-enum Direction {
-    Up,
-    Down,
-    Left,
-    Right,
-}
-
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
-struct Coordinate {
-    row: i32,
-    col: i32,
-}
-
+type Coordinate = Point2<i32>;
 type Coordinates = Vec<Coordinate>;
 
-impl Coordinate {
-    fn new(row: i32, col: i32) -> Self {
-        Self { row, col }
-    }
-    fn neighbor(&self, direction: Direction) -> Self {
-        match direction {
-            Direction::Up => Self::new(self.row - 1, self.col),
-            Direction::Down => Self::new(self.row + 1, self.col),
-            Direction::Left => Self::new(self.row, self.col - 1),
-            Direction::Right => Self::new(self.row, self.col + 1),
-        }
-    }
-}
-
 type Region = HashSet<Coordinate>;
 
 struct Side {
     region: Region,
-    direction: Direction,
+    direction: Direction4,
 }
 
 trait RegionExt {
@@ -56,9 +29,9 @@ impl RegionExt for Region {
     fn perimeter(&self) -> usize {
         self.iter()
             .map(|c| {
-                Direction::iter()
+                Direction4::iter()
                     .filter(|d| {
-                        let neighbor = c.neighbor(*d);
+                        let neighbor = *c + d.delta();
                         !self.contains(&neighbor)
                     })
                     .count()
@@ -68,35 +41,27 @@ impl RegionExt for Region {
 }
 
 type Crop = char;
-type Plots = Vec<Vec<Crop>>;
+type Plots = Grid<Crop>;
 
 struct Garden {
     plots: Plots,
-    rows: usize,
-    cols: usize,
 }
 
 fn decompose_into_regions(coords: Coordinates) -> Vec<Region> {
-    let mut regions = vec![];
-    let mut remaining = coords.into_iter().collect::<HashSet<_>>();
-
-    while let Some(&c) = remaining.iter().next() {
-        let mut region = HashSet::new();
-        let mut stack = vec![c];
-        while let Some(c) = stack.pop() {
-            if region.insert(c) {
-                remaining.remove(&c);
-                for d in Direction::iter() {
-                    let neighbor = c.neighbor(d);
-                    if remaining.contains(&neighbor) {
-                        stack.push(neighbor);
-                    }
-                }
+    let mut regions: KeyedUnionFind<Coordinate> = coords.iter().copied().collect();
+    for &c in &coords {
+        for d in [Direction4::Right, Direction4::Down] {
+            let neighbor = c + d.delta();
+            if regions.contains(&neighbor) {
+                regions.union(c, neighbor);
             }
         }
-        regions.push(region);
     }
     regions
+        .components()
+        .into_iter()
+        .map(|region| region.into_iter().collect())
+        .collect()
 }
 
 enum Decompose {
@@ -104,14 +69,14 @@ enum Decompose {
     Col(i32),
 }
 
-fn decompose_into_sides(region: &Region, direction: Direction, decompose: Decompose) -> Vec<Side> {
+fn decompose_into_sides(region: &Region, direction: Direction4, decompose: Decompose) -> Vec<Side> {
     let filtered_region = region
         .iter()
         .filter(|c| match decompose {
-            Decompose::Row(row) => c.row == row,
-            Decompose::Col(col) => c.col == col,
+            Decompose::Row(row) => c.y == row,
+            Decompose::Col(col) => c.x == col,
         })
-        .filter(|c| !region.contains(&c.neighbor(direction)))
+        .filter(|&&c| !region.contains(&(c + direction.delta())))
         .cloned()
         .collect_vec();
 
@@ -123,8 +88,8 @@ fn decompose_into_sides(region: &Region, direction: Direction, decompose: Decomp
 }
 
 fn count_sides(region: &Region) -> usize {
-    let minmax_row = region.iter().map(|c| c.row).minmax();
-    let minmax_col = region.iter().map(|c| c.col).minmax();
+    let minmax_row = region.iter().map(|c| c.y).minmax();
+    let minmax_col = region.iter().map(|c| c.x).minmax();
     let rows = match minmax_row {
         MinMaxResult::NoElements => return 0,
         MinMaxResult::OneElement(row) => row..row + 1,
@@ -138,22 +103,22 @@ fn count_sides(region: &Region) -> usize {
 
     let up_sides = rows
         .clone()
-        .flat_map(|row| decompose_into_sides(region, Direction::Up, Decompose::Row(row)))
+        .flat_map(|row| decompose_into_sides(region, Direction4::Up, Decompose::Row(row)))
         .count();
 
     let down_sides = rows
         .clone()
-        .flat_map(|row| decompose_into_sides(region, Direction::Down, Decompose::Row(row)))
+        .flat_map(|row| decompose_into_sides(region, Direction4::Down, Decompose::Row(row)))
         .count();
 
     let left_sides = cols
         .clone()
-        .flat_map(|col| decompose_into_sides(region, Direction::Left, Decompose::Col(col)))
+        .flat_map(|col| decompose_into_sides(region, Direction4::Left, Decompose::Col(col)))
         .count();
 
     let right_sides = cols
         .clone()
-        .flat_map(|col| decompose_into_sides(region, Direction::Right, Decompose::Col(col)))
+        .flat_map(|col| decompose_into_sides(region, Direction4::Right, Decompose::Col(col)))
         .count();
 
     up_sides + down_sides + left_sides + right_sides
@@ -161,39 +126,15 @@ fn count_sides(region: &Region) -> usize {
 
 impl Garden {
     fn new(plots: Plots) -> Self {
-        let rows = plots.len();
-        let cols = plots.first().map_or(0, |row| row.len());
-        assert!(plots.iter().all(|row| row.len() == cols));
-        Garden { plots, rows, cols }
-    }
-    fn show(&self) {
-        for row in &self.plots {
-            for plot in row {
-                print!("{} ", plot);
-            }
-            println!();
-        }
-    }
-    fn show_regions(&self) {
-        let regions = self.regions();
-        for region in regions {
-            println!(
-                "Region: area {}, perimeter {}",
-                region.area(),
-                region.perimeter()
-            );
-            for c in region {
-                println!("  {}: {:?}", self[&c], c);
-            }
-            println!("-----------");
-        }
+        Garden { plots }
     }
     fn regions(&self) -> Vec<Region> {
-        self.coordinates()
+        self.plots
+            .iter()
             .fold(
                 HashMap::new(),
-                |mut acc: HashMap<char, Vec<Coordinate>>, c| {
-                    let crop = self[&c];
+                |mut acc: HashMap<char, Vec<Coordinate>>, ((row, col), &crop)| {
+                    let c = Coordinate::new(col as i32, row as i32);
                     acc.entry(crop).or_default().push(c);
                     acc
                 },
@@ -205,68 +146,39 @@ impl Garden {
     fn price(&self) -> usize {
         self.regions()
             .iter()
-            .map(|r| r.area() * r.perimeter())
+            .map(|r| {
+                let (area, perimeter) = (r.area(), r.perimeter());
+                trace!(area, perimeter, "Region");
+                area * perimeter
+            })
             .sum()
     }
     fn bulk_price(&self) -> usize {
         self.regions()
             .iter()
-            .map(|r| r.area() * count_sides(r))
+            .map(|r| {
+                let (area, sides) = (r.area(), count_sides(r));
+                trace!(area, sides, "Region");
+                area * sides
+            })
             .sum()
     }
-    fn coordinates(&self) -> CoordinateIterator {
-        CoordinateIterator {
-            current_index: 0,
-            rows: self.rows,
-            cols: self.cols,
-        }
-    }
-    fn coordinate_is_valid(&self, c: &Coordinate) -> bool {
-        (0..self.rows as i32).contains(&c.row) && (0..self.cols as i32).contains(&c.col)
-    }
 }
 
 impl Index<&Coordinate> for Garden {
     type Output = Crop;
 
     fn index(&self, c: &Coordinate) -> &Self::Output {
-        assert!(self.coordinate_is_valid(c));
-        &self.plots[c.row as usize][c.col as usize]
+        &self.plots[c]
     }
 }
 
-struct CoordinateIterator {
-    current_index: usize,
-    rows: usize,
-    cols: usize,
-}
-
-impl Iterator for CoordinateIterator {
-    type Item = Coordinate;
-    fn next(&mut self) -> Option<Self::Item> {
-        if self.current_index < self.rows * self.cols {
-            let row = (self.current_index / self.cols) as i32;
-            let col = (self.current_index % self.cols) as i32;
-            self.current_index += 1;
-            Some(Coordinate::new(row, col))
-        } else {
-            None
-        }
-    }
-}
-
-fn parse_garden(i: &str) -> IResult<&str, Garden> {
-    let (input, plots) =
-        separated_list1(newline, many1(satisfy(|c| !AsChar::is_newline(c as u8)))).parse(i)?;
-    let garden = Garden::new(plots);
-    Ok((i, garden))
-}
-
 fn parse_input(input: &str) -> Result<Garden> {
-    parse_garden(input).map_and_finish()
+    let plots = Grid::parse(input.trim(), Some)?;
+    Ok(Garden::new(plots))
 }
 
-struct Solver {}
+pub(crate) struct Solver {}
 
 impl ResourceReader for Solver {}
 
@@ -279,6 +191,10 @@ impl Task for Solver {
         Day::Day12
     }
 
+    fn status(&self) -> Status {
+        Status::Complete
+    }
+
     fn solve_part1(&self, input: &str) -> Result<String> {
         let garden = parse_input(input)?;
         Ok(garden.price().to_string())
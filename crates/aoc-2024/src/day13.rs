@@ -1,22 +1,15 @@
+use aoc_common::geom::{Point2, Vec2};
+use aoc_common::linear::{self, Diophantine, Solution};
 use aoc_common::prelude::*;
 use aoc_data::prelude::*;
-use nalgebra::{Matrix2, Vector2};
 use nom::bytes::complete::tag;
 use nom::character::complete;
 use nom::character::complete::{anychar, newline};
 use nom::combinator::opt;
 use nom::multi::separated_list1;
+use std::fmt::{Display, Formatter};
 
-struct Coordinate {
-    x: i64,
-    y: i64,
-}
-
-impl Coordinate {
-    fn new(x: i64, y: i64) -> Self {
-        Self { x, y }
-    }
-}
+type Position = Point2<i64>;
 
 struct Button {
     dx: i64,
@@ -30,11 +23,11 @@ impl Button {
 }
 
 struct Prize {
-    loc: Coordinate,
+    loc: Position,
 }
 
 impl Prize {
-    fn new(loc: Coordinate) -> Self {
+    fn new(loc: Position) -> Self {
         Self { loc }
     }
 }
@@ -45,6 +38,14 @@ struct Machine {
     prize: Prize,
 }
 
+impl Display for Machine {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "Button A: X+{}, Y+{}", self.a.dx, self.a.dy)?;
+        writeln!(f, "Button B: X+{}, Y+{}", self.b.dx, self.b.dy)?;
+        write!(f, "Prize: X={}, Y={}", self.prize.loc.x, self.prize.loc.y)
+    }
+}
+
 #[derive(Debug)]
 struct Moves {
     a: i64,
@@ -58,11 +59,6 @@ impl Moves {
 }
 
 impl Machine {
-    fn show(&self) {
-        println!("Button A: X+{}, Y+{}", self.a.dx, self.a.dy);
-        println!("Button B: X+{}, Y+{}", self.b.dx, self.b.dy);
-        println!("Prize: X={}, Y={}", self.prize.loc.x, self.prize.loc.y);
-    }
     fn all_possible_moves(&self) -> Vec<Moves> {
         let mut moves = vec![];
         let (ax, ay) = (self.a.dx, self.a.dy);
@@ -82,63 +78,46 @@ impl Machine {
         moves
     }
     fn move_prize(&mut self, dx: i64, dy: i64) {
-        self.prize.loc.x += dx;
-        self.prize.loc.y += dy;
+        self.prize.loc += Vec2::new(dx, dy);
     }
-    fn cheapest_move(&self) -> Option<Moves> {
+    fn cheapest_move_brute_force(&self) -> Option<Moves> {
         //
         // Suffers in brute-force searching.
         //
-        self.show();
+        debug!("Searching all moves for\n{self}");
         self.all_possible_moves()
             .into_iter()
             .min_by_key(|m| m.cost())
     }
-    fn cheapest_move_numerical(&self) -> Option<Moves> {
-        //
-        // Suffers in numerical instability.
-        //
-        let a = Matrix2::new(
-            self.a.dx as f64,
-            self.b.dx as f64,
-            self.a.dy as f64,
-            self.b.dy as f64,
-        );
-        let mut b = Vector2::new(self.prize.loc.x as f64, self.prize.loc.y as f64);
-        let lu = a.lu();
-        let tol = 1e-12;
-        match lu.solve(&b) {
-            Some(x)
-                if x.iter()
-                    .all(|v| v.fract().abs() < tol || (1.0 - v.fract()).abs() < tol) =>
-            {
-                Some(Moves {
-                    a: x[0].round() as i64,
-                    b: x[1].round() as i64,
-                })
-            }
-            Some(_) | None => None,
-        }
-    }
-    fn cheapest_move_linear_algebra(&self) -> Option<Moves> {
+    fn cheapest_move(&self) -> Option<Moves> {
         let (ax, ay) = (self.a.dx, self.a.dy);
         let (bx, by) = (self.b.dx, self.b.dy);
         let (px, py) = (self.prize.loc.x, self.prize.loc.y);
 
-        let det = ax * by - ay * bx;
-
-        let na = (by * px - bx * py) as f64 / (det as f64);
-        let nb = (ax * py - ay * px) as f64 / (det as f64);
-
-        fn is_integer(x: f64, tol: f64) -> Option<i64> {
-            if (x.fract().abs() < tol) || (1.0 - x.fract()).abs() < tol {
-                Some(x.round() as i64)
-            } else {
-                None
+        match linear::solve(&[vec![ax, bx], vec![ay, by]], &[px, py]).ok()? {
+            Solution::Unique(presses) => {
+                let a = presses[0].to_integer()?.try_into().ok()?;
+                let b = presses[1].to_integer()?.try_into().ok()?;
+                (a >= 0 && b >= 0).then_some(Moves { a, b })
+            }
+            Solution::Infinite => {
+                // The buttons move along the same line, so the equation for either axis they
+                // move along says everything. The cost, being linear in the free parameter, is
+                // cheapest at one end of the range.
+                let presses = if (ax, bx) != (0, 0) {
+                    Diophantine::solve(ax, bx, px)?
+                } else {
+                    Diophantine::solve(ay, by, py)?
+                };
+                let (low, high) = presses.non_negative()?;
+                [low, high]
+                    .into_iter()
+                    .map(|k| presses.at(k))
+                    .map(|(a, b)| Moves { a, b })
+                    .min_by_key(Moves::cost)
             }
+            Solution::None => None,
         }
-        let tol = 1e-12;
-        is_integer(na, tol).and_then(|a| is_integer(nb, tol).map(|b| Moves { a, b }))
     }
 }
 
@@ -162,7 +141,7 @@ fn parse_prize(i: &str) -> IResult<&str, Prize> {
     let (i, _) = tag(", Y=").parse(i)?;
     let (i, y) = complete::i64(i)?;
     let (i, _) = opt(newline).parse(i)?;
-    Ok((i, Prize::new(Coordinate::new(x, y))))
+    Ok((i, Prize::new(Position::new(x, y))))
 }
 fn parse_machine(i: &str) -> IResult<&str, Machine> {
     let (i, a) = parse_button(i)?;
@@ -176,10 +155,11 @@ fn parse_machines(i: &str) -> IResult<&str, Machines> {
 }
 
 fn parse_input(input: &str) -> Result<Machines> {
+    let _span = debug_span!("parse").entered();
     parse_machines(input).map_and_finish()
 }
 
-struct Solver {}
+pub(crate) struct Solver {}
 
 impl ResourceReader for Solver {}
 
@@ -192,11 +172,15 @@ impl Task for Solver {
         Day::Day13
     }
 
+    fn status(&self) -> Status {
+        Status::Complete
+    }
+
     fn solve_part1(&self, input: &str) -> Result<String> {
         let machines = parse_input(input)?;
         let lowest_cost: i64 = machines
             .iter()
-            .flat_map(|m| m.cheapest_move_linear_algebra())
+            .flat_map(|m| m.cheapest_move())
             .map(|m| m.cost())
             .sum();
         Ok(lowest_cost.to_string())
@@ -211,7 +195,7 @@ impl Task for Solver {
 
         let lowest_cost: i64 = machines
             .iter()
-            .flat_map(|m| m.cheapest_move_linear_algebra())
+            .flat_map(|m| m.cheapest_move())
             .map(|m| m.cost())
             .sum();
 
@@ -244,6 +228,43 @@ mod test {
         Ok(())
     }
 
+    #[rstest]
+    fn brute_force_agrees(solver: Solver) -> Result<()> {
+        let input = solver.read_resource(Input::Example1)?;
+        for machine in parse_input(&input)? {
+            let exact = machine.cheapest_move().map(|m| m.cost());
+            let brute_force = machine.cheapest_move_brute_force().map(|m| m.cost());
+            assert_eq!(exact, brute_force);
+        }
+        Ok(())
+    }
+
+    #[rstest]
+    fn collinear_buttons() {
+        let machine = Machine {
+            a: Button::new(2, 4),
+            b: Button::new(1, 2),
+            prize: Prize::new(Position::new(10, 20)),
+        };
+        let moves = machine.cheapest_move().unwrap();
+        assert_eq!((moves.a, moves.b), (0, 10));
+        assert_eq!(
+            machine.cheapest_move_brute_force().map(|m| m.cost()),
+            Some(moves.cost())
+        );
+    }
+
+    #[rstest]
+    fn vertical_buttons() {
+        let machine = Machine {
+            a: Button::new(0, 2),
+            b: Button::new(0, 3),
+            prize: Prize::new(Position::new(0, 7)),
+        };
+        let moves = machine.cheapest_move().unwrap();
+        assert_eq!((moves.a, moves.b), (2, 1));
+    }
+
     #[rstest]
     fn part1(solver: Solver) -> Result<()> {
         let input = solver.read_resource(Input::Part1)?;
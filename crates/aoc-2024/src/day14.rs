@@ -1,3 +1,5 @@
+use aoc_common::geom::{Point2, Vec2};
+use aoc_common::grid::{Grid, SummedArea};
 use aoc_common::prelude::*;
 use aoc_data::prelude::*;
 use nom::bytes::complete::tag;
@@ -7,20 +9,15 @@ use nom::combinator::opt;
 use nom::multi::many1;
 use nom::sequence::separated_pair;
 
-#[derive(Debug)]
-struct Vec2 {
-    x: i32,
-    y: i32,
-}
-
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 struct State {
-    pos: Vec2,
-    vel: Vec2,
+    pos: Point2<i32>,
+    vel: Vec2<i32>,
 }
 
 type States = Vec<State>;
 
+#[derive(Clone, PartialEq, Eq, Hash)]
 struct Arena {
     width: i32,
     height: i32,
@@ -37,22 +34,16 @@ impl Arena {
             states,
         }
     }
-    fn show(&self) {
-        let mut grid = vec![vec![' '; self.width as usize]; self.height as usize];
+    fn occupancy(&self) -> Occupancy {
+        let mut counts = Grid::filled(self.width as usize, self.height as usize, 0);
         for state in &self.states {
-            grid[state.pos.y as usize][state.pos.x as usize] = 'x';
-        }
-        for row in grid {
-            println!(
-                "{} ",
-                row.into_iter().flat_map(|c| [c, ' ']).collect::<String>()
-            );
+            counts[state.pos] += 1;
         }
+        Occupancy { counts }
     }
     fn tick(&mut self) {
         self.states.iter_mut().for_each(|state| {
-            state.pos.x += state.vel.x + self.width;
-            state.pos.y += state.vel.y + self.height;
+            state.pos += state.vel + Vec2::new(self.width, self.height);
             state.pos.x %= self.width;
             state.pos.y %= self.height;
         })
@@ -91,43 +82,103 @@ impl Arena {
         assert!(subgrid_size.1 <= self.height as usize);
         let (subgrid_width, subgrid_height) = subgrid_size;
         let subgrid_area = subgrid_width * subgrid_height;
-        let mut entropy = 0.0;
-        for y in 0..(self.height as usize - subgrid_height) {
-            for x in 0..(self.width as usize - subgrid_width) {
-                // TODO: This has terrible performance, and could be optimized.
-                let subgrid = self
-                    .states
-                    .iter()
-                    .filter(|state| {
-                        (y..(y + subgrid_height)).contains(&(state.pos.y as usize))
-                            && (x..(x + subgrid_width)).contains(&(state.pos.x as usize))
-                    })
-                    .count();
-                entropy += match subgrid {
-                    0 => 0.0,
-                    _ => {
-                        let p = (subgrid as f64) / (subgrid_area as f64);
-                        -p * p.log2()
-                    }
-                };
+        let robots = SummedArea::new(&self.occupancy().counts);
+        robots
+            .windows(subgrid_width, subgrid_height)
+            .map(|(_, subgrid)| match subgrid {
+                0 => 0.0,
+                _ => {
+                    let p = (subgrid as f64) / (subgrid_area as f64);
+                    -p * p.log2()
+                }
+            })
+            .sum()
+    }
+}
+
+/// Robots per tile, drawn like the puzzle text: a count, or `.` for none.
+struct Occupancy {
+    counts: Grid<usize>,
+}
+
+impl Render for Occupancy {
+    fn size(&self) -> (usize, usize) {
+        (self.counts.width(), self.counts.height())
+    }
+    fn cell(&self, x: usize, y: usize) -> Cell {
+        match self.counts[(y, x)] {
+            0 => Cell::new('.', Color::DARK_GREY),
+            n => {
+                let glyph = char::from_digit(n.min(9) as u32, 10).expect("Single digit");
+                Cell::new(glyph, Color::GREEN)
             }
         }
-        entropy
     }
 }
 
-fn parse_vec2(i: &str) -> IResult<&str, Vec2> {
-    let (i, (x, y)) = separated_pair(complete::i32, tag(","), complete::i32).parse(i)?;
-    Ok((i, Vec2 { x, y }))
+enum Until {
+    Seconds(usize),
+    Picture,
+}
+
+/// The robots moving second by second, for a set time or until they draw a picture.
+struct Restroom {
+    arena: Arena,
+    seconds: usize,
+    until: Until,
+    entropy: Option<f64>,
+}
+
+impl Restroom {
+    const PICTURE_LIMIT: usize = 9_999;
+
+    fn found_picture(&self) -> bool {
+        // TODO: this is hard-coded, and could be replaced by detecting a
+        //       statistically significant change in entropy.
+        self.entropy.is_some_and(|e| e < 500.0)
+    }
+}
+
+impl Simulation for Restroom {
+    fn step(&mut self) {
+        if self.is_done() {
+            return;
+        }
+        self.arena.tick();
+        self.seconds += 1;
+        if let Until::Picture = self.until {
+            self.entropy = Some(self.arena.entropy((2, 2)));
+        }
+    }
+    fn render(&self) -> Frame {
+        Frame::capture(&self.arena.occupancy())
+    }
+    fn is_done(&self) -> bool {
+        match self.until {
+            Until::Seconds(seconds) => self.seconds >= seconds,
+            Until::Picture => self.found_picture() || self.seconds >= Self::PICTURE_LIMIT,
+        }
+    }
+    fn status(&self) -> String {
+        match self.entropy {
+            Some(entropy) => format!("second {}, entropy {entropy:.1}", self.seconds),
+            None => format!("second {}", self.seconds),
+        }
+    }
+}
+
+fn parse_pair(i: &str) -> IResult<&str, (i32, i32)> {
+    separated_pair(complete::i32, tag(","), complete::i32).parse(i)
 }
 
 fn parse_state(i: &str) -> IResult<&str, State> {
     let (i, _) = tag("p=").parse(i)?;
-    let (i, pos) = parse_vec2(i)?;
+    let (i, pos) = parse_pair(i)?;
     let (i, _) = space1(i)?;
     let (i, _) = tag("v=").parse(i)?;
-    let (i, vel) = parse_vec2(i)?;
+    let (i, vel) = parse_pair(i)?;
     let (i, _) = opt(newline).parse(i)?;
+    let (pos, vel) = (pos.into(), vel.into());
     Ok((i, State { pos, vel }))
 }
 
@@ -135,14 +186,27 @@ fn parse_input(i: &str) -> Result<Vec<State>> {
     many1(parse_state).parse(i).map_and_finish()
 }
 
-struct Solver {
-    arena_size: (i32, i32),
+pub(crate) struct Solver {
+    pub(crate) arena_size: (i32, i32),
 }
 
 impl Solver {
     fn make_arena(&self, states: States) -> Arena {
         Arena::new(self.arena_size.0, self.arena_size.1, states)
     }
+    fn make_restroom(&self, part: Part, input: &str) -> Result<Restroom> {
+        let arena = self.make_arena(parse_input(input)?);
+        let until = match part {
+            Part::Part1 => Until::Seconds(100),
+            Part::Part2 => Until::Picture,
+        };
+        Ok(Restroom {
+            arena,
+            seconds: 0,
+            until,
+            entropy: None,
+        })
+    }
 }
 
 impl ResourceReader for Solver {}
@@ -155,29 +219,31 @@ impl Task for Solver {
     fn day(&self) -> Day {
         Day::Day14
     }
+
+    fn status(&self) -> Status {
+        Status::Complete
+    }
+
     fn solve_part1(&self, input: &str) -> Result<String> {
-        let states = parse_input(input)?;
-        let mut arena = self.make_arena(states);
-        for _ in 0..100 {
-            arena.tick();
-        }
-        Ok(arena.safety_factor().to_string())
+        self.solve_with_frames(Part::Part1, input, &mut Frames::disabled())
     }
 
     fn solve_part2(&self, input: &str) -> Result<String> {
-        let states = parse_input(input)?;
-        let mut arena = self.make_arena(states);
-        for t in 1..10_000 {
-            arena.tick();
-            let e = arena.entropy((2, 2));
-            // TODO: this is hard-coded, and could be replaced by detecting a
-            //       statistically significant change in entropy.
-            if e < 500.0 {
-                // arena.show();
-                return Ok(t.to_string());
-            }
+        self.solve_with_frames(Part::Part2, input, &mut Frames::disabled())
+    }
+
+    fn solve_with_frames(&self, part: Part, input: &str, frames: &mut Frames) -> Result<String> {
+        let mut restroom = self.make_restroom(part, input)?;
+        restroom.run(frames);
+        match part {
+            Part::Part1 => Ok(restroom.arena.safety_factor().to_string()),
+            Part::Part2 if restroom.found_picture() => Ok(restroom.seconds.to_string()),
+            Part::Part2 => Err(AdventError::Other("Limit reached".to_string())),
         }
-        Err(AdventError::Other("Limit reached".to_string()))
+    }
+
+    fn simulation(&self, part: Part, input: &str) -> Result<Option<Box<dyn Simulation>>> {
+        Ok(Some(Box::new(self.make_restroom(part, input)?)))
     }
 }
 
@@ -185,6 +251,8 @@ impl Task for Solver {
 mod test {
 
     use super::*;
+    use aoc_common::cycle::{self, Cycle};
+    use aoc_common::render::to_text;
     use rstest::*;
 
     #[rstest]
@@ -197,6 +265,67 @@ mod test {
         Ok(())
     }
 
+    #[rstest]
+    fn example1_frames() -> Result<()> {
+        let solver = Solver {
+            arena_size: (11, 7),
+        };
+        let input = solver.read_resource(Input::Example1)?;
+        let mut frames = Frames::new();
+        solver.solve_with_frames(Part::Part1, &input, &mut frames)?;
+        let last = frames.frames().last().expect("No frames");
+        assert_eq!(frames.frames().len(), 101);
+        assert_eq!(
+            to_text(last),
+            "\
+......2..1.
+...........
+1..........
+.11........
+.....1.....
+...12......
+.1....1....
+"
+        );
+        Ok(())
+    }
+
+    #[rstest]
+    fn example1_entropy() -> Result<()> {
+        let solver = Solver {
+            arena_size: (11, 7),
+        };
+        let input = solver.read_resource(Input::Example1)?;
+        let arena = solver.make_arena(parse_input(&input)?);
+        // Every 2x2 window counts, including those along the bottom row and right column.
+        assert!((arena.entropy((2, 2)) - 12.811278124459132).abs() < 1e-9);
+        Ok(())
+    }
+
+    #[rstest]
+    fn robots_cycle() -> Result<()> {
+        let solver = Solver {
+            arena_size: (11, 7),
+        };
+        let input = solver.read_resource(Input::Example1)?;
+        let arena = solver.make_arena(parse_input(&input)?);
+        // Every robot is back where it started after lcm(11, 7) seconds.
+        let expected = Cycle {
+            start: 0,
+            length: 77,
+        };
+        assert_eq!(cycle::brent(arena.clone(), Arena::tick), expected);
+        assert_eq!(cycle::floyd(arena.clone(), Arena::tick), expected);
+
+        let mut ticked = arena.clone();
+        for _ in 0..100 {
+            ticked.tick();
+        }
+        let forwarded = cycle::fast_forward(arena.clone(), Arena::tick, 1_000_000_024);
+        assert!(forwarded == ticked);
+        Ok(())
+    }
+
     #[rstest]
     fn part1() -> Result<()> {
         let solver = Solver {
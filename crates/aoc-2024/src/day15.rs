@@ -1,45 +1,21 @@
+use aoc_common::geom::{Direction4, Point2};
+use aoc_common::grid::Grid;
+use aoc_common::parse::{grid, sections};
 use aoc_common::prelude::*;
+use aoc_common::render::to_text;
 use aoc_data::prelude::*;
-use nom::branch::alt;
-use nom::character::complete::{char, newline, one_of};
-use nom::combinator::{map, opt};
-use nom::multi::{fold_many1, many1};
-use nom::sequence::terminated;
+use nom::character::complete::one_of;
+use nom::multi::fold_many1;
 use std::ops::{Index, IndexMut};
 
-#[derive(Debug, Clone)]
-enum Direction {
-    Up,
-    Down,
-    Left,
-    Right,
-}
-
-type Moves = Vec<Direction>;
-
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
-struct Coordinate {
-    row: i32,
-    col: i32,
-}
+type Moves = Vec<Direction4>;
 
+type Coordinate = Point2<i32>;
 type Coordinates = Vec<Coordinate>;
 
-impl Coordinate {
-    fn new(row: i32, col: i32) -> Self {
-        Self { row, col }
-    }
-    fn neighbor(&self, direction: &Direction) -> Self {
-        match *direction {
-            Direction::Up => Self::new(self.row - 1, self.col),
-            Direction::Down => Self::new(self.row + 1, self.col),
-            Direction::Left => Self::new(self.row, self.col - 1),
-            Direction::Right => Self::new(self.row, self.col + 1),
-        }
-    }
-    fn gps(&self) -> i32 {
-        100 * self.row + self.col
-    }
+/// The GPS coordinate: 100 times the row plus the column.
+fn gps(c: Coordinate) -> i32 {
+    100 * c.y + c.x
 }
 
 #[derive(Debug, Copy, Clone, PartialEq)]
@@ -68,28 +44,17 @@ impl Robot {
 }
 
 struct Arena {
-    width: usize,
-    height: usize,
     robot: Robot,
-    tiles: Vec<Vec<Tile>>,
+    tiles: Grid<Tile>,
 }
 
 impl Arena {
-    fn new(tiles: Vec<Vec<Tile>>) -> Arena {
-        let height = tiles.len();
-        let width = tiles.first().map_or(0, |row| row.len());
-        assert!(tiles.iter().all(|row| row.len() == width));
-
+    fn new(tiles: Grid<Tile>) -> Arena {
         // Find the robot location
-        let mut robot_coordinates = vec![];
-        for (row_index, row) in tiles.iter().enumerate() {
-            for (col_index, tile) in row.iter().enumerate() {
-                if tile == &Tile::Robot {
-                    let coord = Coordinate::new(row_index as i32, col_index as i32);
-                    robot_coordinates.push(coord);
-                }
-            }
-        }
+        let robot_coordinates = tiles
+            .positions(|tile| tile == &Tile::Robot)
+            .map(|(row, col)| Coordinate::new(col as i32, row as i32))
+            .collect_vec();
 
         assert_eq!(robot_coordinates.len(), 1);
         let robot_coord = robot_coordinates
@@ -97,60 +62,47 @@ impl Arena {
             .expect("A single robot must exist");
 
         let robot = Robot::new(*robot_coord);
-        Arena {
-            width,
-            height,
-            robot,
-            tiles,
-        }
-    }
-    fn show(&self) {
-        for row in &self.tiles {
-            for tile in row {
-                match tile {
-                    Tile::Wall => print!("#"),
-                    Tile::Empty => print!("."),
-                    Tile::Box(side) => match side {
-                        Side::Both => print!("O"),
-                        Side::Left => print!("["),
-                        Side::Right => print!("]"),
-                    },
-                    Tile::Robot => print!("@"),
-                }
-                print!(" ")
-            }
-            println!();
-        }
+        Arena { robot, tiles }
     }
     fn make_double_wide(&mut self) {
-        self.width *= 2;
-        self.robot.location.col *= 2;
-        for row in &mut self.tiles {
-            *row = row
-                .iter()
-                .flat_map(|tile| match tile {
-                    Tile::Wall => [Tile::Wall, Tile::Wall],
-                    Tile::Empty => [Tile::Empty, Tile::Empty],
-                    Tile::Box(Side::Both) => [Tile::Box(Side::Left), Tile::Box(Side::Right)],
-                    Tile::Box(Side::Left) => panic!("Double-wide boxes can't double again"),
-                    Tile::Box(Side::Right) => panic!("Double-wide boxes can't double again"),
-                    Tile::Robot => [Tile::Robot, Tile::Empty],
-                })
-                .collect();
-        }
+        self.robot.location.x *= 2;
+        let rows = self
+            .tiles
+            .rows()
+            .map(|row| {
+                row.iter()
+                    .flat_map(|tile| match tile {
+                        Tile::Wall => [Tile::Wall, Tile::Wall],
+                        Tile::Empty => [Tile::Empty, Tile::Empty],
+                        Tile::Box(Side::Both) => [Tile::Box(Side::Left), Tile::Box(Side::Right)],
+                        Tile::Box(Side::Left) => panic!("Double-wide boxes can't double again"),
+                        Tile::Box(Side::Right) => panic!("Double-wide boxes can't double again"),
+                        Tile::Robot => [Tile::Robot, Tile::Empty],
+                    })
+                    .collect()
+            })
+            .collect();
+        self.tiles = Grid::from_rows(rows).expect("Every row doubles");
     }
     fn apply_move(
         &mut self,
-        direction: &Direction,
+        direction: &Direction4,
         coord: Option<Coordinate>,
         apply: bool,
     ) -> bool {
         let curr_loc = coord.unwrap_or(self.robot.location);
 
-        let next_loc = curr_loc.neighbor(direction);
+        let next_loc = curr_loc + direction.delta();
         assert!(self.coordinate_is_valid(&next_loc));
 
-        // println!("Moving from {:?} ({:?}) to {:?} ({:?})", curr_loc, self[&curr_loc], next_loc, self[&next_loc]);
+        trace!(
+            from = ?curr_loc,
+            tile = ?self[&curr_loc],
+            to = ?next_loc,
+            next = ?self[&next_loc],
+            apply,
+            "Moving"
+        );
 
         match (self[&curr_loc], self[&next_loc]) {
             (_, Tile::Wall) => false,
@@ -178,7 +130,7 @@ impl Arena {
             }
             (tile, Tile::Box(side)) => match (direction, side) {
                 (_, Side::Both) => panic!("Arena should not be in this state"),
-                (Direction::Left | Direction::Right, _) => {
+                (Direction4::Left | Direction4::Right, _) => {
                     let move_ok = self.apply_move(direction, Some(next_loc), apply);
                     if apply {
                         if tile == Tile::Robot {
@@ -189,10 +141,10 @@ impl Arena {
                     }
                     move_ok
                 }
-                (Direction::Up, Side::Left) => {
-                    let other_loc = next_loc.neighbor(&Direction::Right);
-                    let next_ok = self.apply_move(&Direction::Up, Some(next_loc), apply);
-                    let other_ok = self.apply_move(&Direction::Up, Some(other_loc), apply);
+                (Direction4::Up, Side::Left) => {
+                    let other_loc = next_loc + Direction4::Right.delta();
+                    let next_ok = self.apply_move(&Direction4::Up, Some(next_loc), apply);
+                    let other_ok = self.apply_move(&Direction4::Up, Some(other_loc), apply);
                     if next_ok && other_ok && apply {
                         if tile == Tile::Robot {
                             self.robot.location = next_loc;
@@ -203,10 +155,10 @@ impl Arena {
                     }
                     next_ok && other_ok
                 }
-                (Direction::Up, Side::Right) => {
-                    let other_loc = next_loc.neighbor(&Direction::Left);
-                    let next_ok = self.apply_move(&Direction::Up, Some(next_loc), apply);
-                    let other_ok = self.apply_move(&Direction::Up, Some(other_loc), apply);
+                (Direction4::Up, Side::Right) => {
+                    let other_loc = next_loc + Direction4::Left.delta();
+                    let next_ok = self.apply_move(&Direction4::Up, Some(next_loc), apply);
+                    let other_ok = self.apply_move(&Direction4::Up, Some(other_loc), apply);
                     if next_ok && other_ok && apply {
                         if tile == Tile::Robot {
                             self.robot.location = next_loc;
@@ -217,10 +169,10 @@ impl Arena {
                     }
                     next_ok && other_ok
                 }
-                (Direction::Down, Side::Left) => {
-                    let other_loc = next_loc.neighbor(&Direction::Right);
-                    let next_ok = self.apply_move(&Direction::Down, Some(next_loc), apply);
-                    let other_ok = self.apply_move(&Direction::Down, Some(other_loc), apply);
+                (Direction4::Down, Side::Left) => {
+                    let other_loc = next_loc + Direction4::Right.delta();
+                    let next_ok = self.apply_move(&Direction4::Down, Some(next_loc), apply);
+                    let other_ok = self.apply_move(&Direction4::Down, Some(other_loc), apply);
                     if next_ok && other_ok && apply {
                         if tile == Tile::Robot {
                             self.robot.location = next_loc;
@@ -231,10 +183,10 @@ impl Arena {
                     }
                     next_ok && other_ok
                 }
-                (Direction::Down, Side::Right) => {
-                    let other_loc = next_loc.neighbor(&Direction::Left);
-                    let next_ok = self.apply_move(&Direction::Down, Some(next_loc), apply);
-                    let other_ok = self.apply_move(&Direction::Down, Some(other_loc), apply);
+                (Direction4::Down, Side::Right) => {
+                    let other_loc = next_loc + Direction4::Left.delta();
+                    let next_ok = self.apply_move(&Direction4::Down, Some(next_loc), apply);
+                    let other_ok = self.apply_move(&Direction4::Down, Some(other_loc), apply);
                     if next_ok && other_ok && apply {
                         if tile == Tile::Robot {
                             self.robot.location = next_loc;
@@ -249,22 +201,38 @@ impl Arena {
             (_, Tile::Robot) => panic!("Robot should not be in this tile"),
         }
     }
-    fn apply_moves(&mut self, moves: &Moves) {
-        for direction in moves {
-            if self.apply_move(direction, None, false) {
-                self.apply_move(direction, None, true);
-            }
-            // self.show();
+    /// Move the robot if everything in its way can move too.
+    fn apply_direction(&mut self, direction: &Direction4) -> bool {
+        let moved = self.apply_move(direction, None, false);
+        if moved {
+            self.apply_move(direction, None, true);
         }
+        moved
+    }
+    fn gps_sum(&self) -> i32 {
+        // Double-wide boxes are measured from their left side.
+        self.tiles
+            .positions(|tile| matches!(tile, Tile::Box(Side::Both | Side::Left)))
+            .map(|(row, col)| gps(Coordinate::new(col as i32, row as i32)))
+            .sum()
     }
     fn coordinate_is_valid(&self, c: &Coordinate) -> bool {
-        c.row >= 0 && c.row < self.height as i32 && c.col >= 0 && c.col < self.width as i32
+        self.tiles.contains(c)
+    }
+}
+
+impl Render for Arena {
+    fn size(&self) -> (usize, usize) {
+        (self.tiles.width(), self.tiles.height())
     }
-    fn coordinates(&self) -> CoordinateIterator {
-        CoordinateIterator {
-            current_index: 0,
-            rows: self.height,
-            cols: self.width,
+    fn cell(&self, x: usize, y: usize) -> Cell {
+        match self.tiles[(y, x)] {
+            Tile::Wall => Cell::new('#', Color::GREY),
+            Tile::Empty => Cell::new('.', Color::DARK_GREY),
+            Tile::Box(Side::Both) => Cell::new('O', Color::YELLOW),
+            Tile::Box(Side::Left) => Cell::new('[', Color::YELLOW),
+            Tile::Box(Side::Right) => Cell::new(']', Color::YELLOW),
+            Tile::Robot => Cell::new('@', Color::RED),
         }
     }
 }
@@ -273,59 +241,78 @@ impl Index<&Coordinate> for Arena {
     type Output = Tile;
 
     fn index(&self, c: &Coordinate) -> &Self::Output {
-        assert!(self.coordinate_is_valid(c));
-        &self.tiles[c.row as usize][c.col as usize]
+        &self.tiles[c]
     }
 }
 
 impl IndexMut<&Coordinate> for Arena {
     fn index_mut(&mut self, c: &Coordinate) -> &mut Self::Output {
-        assert!(self.coordinate_is_valid(c));
-        &mut self.tiles[c.row as usize][c.col as usize]
+        &mut self.tiles[c]
     }
 }
 
-struct CoordinateIterator {
-    current_index: usize,
-    rows: usize,
-    cols: usize,
+/// The robot working through its list of moves.
+struct Warehouse {
+    arena: Arena,
+    moves: Moves,
+    next: usize,
 }
 
-impl Iterator for CoordinateIterator {
-    type Item = Coordinate;
-    fn next(&mut self) -> Option<Self::Item> {
-        if self.current_index < self.rows * self.cols {
-            let row = (self.current_index / self.cols) as i32;
-            let col = (self.current_index % self.cols) as i32;
-            self.current_index += 1;
-            Some(Coordinate::new(row, col))
-        } else {
-            None
+impl Warehouse {
+    fn new(part: Part, input: &str) -> Result<Self> {
+        let (mut arena, moves) = parse_input(input)?;
+        if part == Part::Part2 {
+            arena.make_double_wide();
         }
+        trace!("\n{}", to_text(&arena));
+        Ok(Self {
+            arena,
+            moves,
+            next: 0,
+        })
+    }
+}
+
+impl Simulation for Warehouse {
+    fn step(&mut self) {
+        let Some(direction) = self.moves.get(self.next) else {
+            return;
+        };
+        let moved = self.arena.apply_direction(direction);
+        self.next += 1;
+        debug!(?direction, moved, robot = ?self.arena.robot.location, "Step");
+        trace!("\n{}", to_text(&self.arena));
+    }
+    fn render(&self) -> Frame {
+        Frame::capture(&self.arena)
+    }
+    fn is_done(&self) -> bool {
+        self.next == self.moves.len()
+    }
+    fn status(&self) -> String {
+        let Point2 { x: col, y: row } = self.arena.robot.location;
+        let (next, moves) = (self.next, self.moves.len());
+        format!("move {next}/{moves}, robot at row {row}, column {col}")
     }
 }
 
-fn parse_arena(i: &str) -> IResult<&str, Arena> {
-    let (i, tiles) = many1(terminated(
-        many1(alt((
-            map(char('#'), |_| Tile::Wall),
-            map(char('.'), |_| Tile::Empty),
-            map(char('O'), |_| Tile::Box(Side::Both)),
-            map(char('@'), |_| Tile::Robot),
-        ))),
-        opt(newline),
-    ))
-    .parse(i)?;
-    Ok((i, Arena::new(tiles)))
+fn parse_tile(c: char) -> Option<Tile> {
+    match c {
+        '#' => Some(Tile::Wall),
+        '.' => Some(Tile::Empty),
+        'O' => Some(Tile::Box(Side::Both)),
+        '@' => Some(Tile::Robot),
+        _ => None,
+    }
 }
 
 fn parse_moves(i: &str) -> IResult<&str, Moves> {
     fold_many1(one_of("<>^v\n"), Moves::new, |mut acc: Moves, c| {
         match c {
-            '<' => acc.push(Direction::Left),
-            '>' => acc.push(Direction::Right),
-            '^' => acc.push(Direction::Up),
-            'v' => acc.push(Direction::Down),
+            '<' => acc.push(Direction4::Left),
+            '>' => acc.push(Direction4::Right),
+            '^' => acc.push(Direction4::Up),
+            'v' => acc.push(Direction4::Down),
             _ => {}
         }
         acc
@@ -333,17 +320,15 @@ fn parse_moves(i: &str) -> IResult<&str, Moves> {
     .parse(i)
 }
 
-fn parse_map_and_moves(i: &str) -> IResult<&str, (Arena, Moves)> {
-    let (i, arena) = parse_arena(i)?;
-    let (i, moves) = parse_moves(i)?;
-    Ok((i, (arena, moves)))
-}
-
 fn parse_input(i: &str) -> Result<(Arena, Moves)> {
-    parse_map_and_moves(i).map_and_finish()
+    let _span = debug_span!("parse").entered();
+    let (tiles, moves) = sections(grid(parse_tile), parse_moves)
+        .parse(i)
+        .map_and_finish()?;
+    Ok((Arena::new(tiles), moves))
 }
 
-struct Solver {}
+pub(crate) struct Solver {}
 
 impl ResourceReader for Solver {}
 
@@ -356,29 +341,26 @@ impl Task for Solver {
         Day::Day15
     }
 
+    fn status(&self) -> Status {
+        Status::Complete
+    }
+
     fn solve_part1(&self, input: &str) -> Result<String> {
-        let (mut arena, moves) = parse_input(input)?;
-        // arena.show();
-        arena.apply_moves(&moves);
-        let sum: i32 = arena
-            .coordinates()
-            .filter(|c| arena[c] == Tile::Box(Side::Both))
-            .map(|c| c.gps())
-            .sum();
-        Ok(sum.to_string())
+        self.solve_with_frames(Part::Part1, input, &mut Frames::disabled())
     }
 
     fn solve_part2(&self, input: &str) -> Result<String> {
-        let (mut arena, moves) = parse_input(input)?;
-        // arena.show();
-        arena.make_double_wide();
-        arena.apply_moves(&moves);
-        let sum: i32 = arena
-            .coordinates()
-            .filter(|c| arena[c] == Tile::Box(Side::Left))
-            .map(|c| c.gps())
-            .sum();
-        Ok(sum.to_string())
+        self.solve_with_frames(Part::Part2, input, &mut Frames::disabled())
+    }
+
+    fn solve_with_frames(&self, part: Part, input: &str, frames: &mut Frames) -> Result<String> {
+        let mut warehouse = Warehouse::new(part, input)?;
+        warehouse.run(frames);
+        Ok(warehouse.arena.gps_sum().to_string())
+    }
+
+    fn simulation(&self, part: Part, input: &str) -> Result<Option<Box<dyn Simulation>>> {
+        Ok(Some(Box::new(Warehouse::new(part, input)?)))
     }
 }
 
@@ -425,6 +407,17 @@ mod test {
         Ok(())
     }
 
+    #[rstest]
+    fn frames(solver: Solver) -> Result<()> {
+        let input = solver.read_resource(Input::Example1)?;
+        let mut frames = Frames::new();
+        solver.solve_with_frames(Part::Part2, &input, &mut frames)?;
+        // The starting state, then one frame per move.
+        assert_eq!(frames.frames().len(), 701);
+        assert_eq!(frames.frames()[0].size(), (20, 10));
+        Ok(())
+    }
+
     #[rstest]
     fn example1(solver: Solver) -> Result<()> {
         let input = solver.read_resource(Input::Example1)?;
@@ -1,48 +1,13 @@
+use aoc_common::geom::{Direction4, Point2, Turn};
+use aoc_common::grid::Grid;
 use aoc_common::prelude::*;
+use aoc_common::search::{Search, dijkstra};
 use aoc_data::prelude::*;
-use nom::branch::alt;
-use nom::character::complete::{char, newline};
-use nom::combinator::{map, opt};
-use nom::multi::many1;
-use nom::sequence::terminated;
-use std::cmp::{Ordering, Reverse};
-use std::collections::{BinaryHeap, HashMap, HashSet};
+use std::collections::{HashMap, HashSet};
 use std::ops::Index;
 
-#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, EnumIter)]
-enum Direction {
-    North,
-    South,
-    West,
-    East,
-}
-
-impl Direction {
-    fn turned(&self, turn: &Turn) -> Self {
-        match turn {
-            Turn::Left => match self {
-                Direction::North => Direction::West,
-                Direction::West => Direction::South,
-                Direction::South => Direction::East,
-                Direction::East => Direction::North,
-            },
-            Turn::Right => match self {
-                Direction::North => Direction::East,
-                Direction::East => Direction::South,
-                Direction::South => Direction::West,
-                Direction::West => Direction::North,
-            },
-        }
-    }
-    fn opposite(&self) -> Self {
-        match self {
-            Direction::North => Direction::South,
-            Direction::South => Direction::North,
-            Direction::West => Direction::East,
-            Direction::East => Direction::West,
-        }
-    }
-}
+const STEP_SCORE: usize = 1;
+const TURN_SCORE: usize = 1000;
 
 #[derive(Debug, Copy, Clone, PartialEq)]
 enum Tile {
@@ -67,309 +32,75 @@ impl Tile {
     }
 }
 
-#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
-struct Coordinate {
-    row: i32,
-    col: i32,
-}
-
-impl Coordinate {
-    fn new(row: i32, col: i32) -> Self {
-        Self { row, col }
-    }
-    fn neighbor(&self, direction: &Direction) -> Self {
-        match *direction {
-            Direction::North => Self::new(self.row - 1, self.col),
-            Direction::South => Self::new(self.row + 1, self.col),
-            Direction::West => Self::new(self.row, self.col - 1),
-            Direction::East => Self::new(self.row, self.col + 1),
-        }
-    }
-}
+type Coordinate = Point2<i32>;
 
+/// Where the reindeer stands and which way it faces.
 #[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
 struct Step {
     coordinate: Coordinate,
-    direction: Direction,
+    direction: Direction4,
 }
 
 impl Step {
-    fn new(coordinate: Coordinate, direction: Direction) -> Self {
+    fn new(coordinate: Coordinate, direction: Direction4) -> Self {
         Self {
             coordinate,
             direction,
         }
     }
-    fn take(&self, direction: &Direction) -> Self {
-        Self::new(self.coordinate.neighbor(direction), *direction)
-    }
-}
-
-type Paths = Vec<Path>;
-
-#[derive(Debug, Clone)]
-struct Path {
-    vec: Vec<Step>,
-    set: HashSet<Step>,
-    score: usize,
-}
-
-pub struct ScoredPath(Path);
-
-impl PartialEq for ScoredPath {
-    fn eq(&self, other: &Self) -> bool {
-        self.0.score == other.0.score
-    }
-}
-
-impl Eq for ScoredPath {}
-
-#[allow(clippy::non_canonical_partial_ord_impl)]
-impl PartialOrd for ScoredPath {
-    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
-        Some(self.0.score.cmp(&other.0.score))
-    }
-}
-
-impl Ord for ScoredPath {
-    fn cmp(&self, other: &Self) -> Ordering {
-        self.0.score.cmp(&other.0.score)
-    }
-}
-
-impl Path {
-    fn new() -> Self {
-        Self {
-            vec: vec![],
-            set: HashSet::new(),
-            score: 0,
-        }
-    }
-    fn new_with_step(step: Step) -> Self {
-        let mut path = Self::new();
-        path.push(step);
-        path
-    }
-    fn last(&self) -> Option<&Step> {
-        self.vec.last()
-    }
-    fn push(&mut self, step: Step) {
-        self.vec.push(step);
-        self.set.insert(step);
-        self.score = self.calculate_score();
-    }
-    fn pop(&mut self) -> Option<Step> {
-        let step = self.vec.pop();
-        if let Some(step) = &step {
-            self.set.remove(step);
-            self.score = self.calculate_score();
-        }
-        step
-    }
-    fn contains_coord(&self, coord: &Coordinate) -> bool {
-        self.set.iter().any(|s| s.coordinate == *coord)
-    }
-    fn contains_step(&self, step: &Step) -> bool {
-        self.set.contains(step)
-    }
-    fn reaches_exit(&self, maze: &Maze) -> bool {
-        self.vec
-            .last()
-            .is_some_and(|s| maze[&s.coordinate].is_exit())
+    /// Forward a tile, or turn on the spot, with the score each adds.
+    fn moves(&self) -> [(Step, usize); 3] {
+        let forward = self.coordinate + self.direction.delta();
+        [
+            (Step::new(forward, self.direction), STEP_SCORE),
+            (self.turned(Turn::Left), TURN_SCORE),
+            (self.turned(Turn::Right), TURN_SCORE),
+        ]
     }
-    fn calculate_score(&self) -> usize {
-        let num_turns = self
-            .vec
-            .iter()
-            .tuple_windows()
-            .filter(|(a, b)| a.direction != b.direction)
-            .count();
-        num_turns * 1000 + self.vec.len() - 1
+    fn turned(&self, turn: Turn) -> Self {
+        Self::new(self.coordinate, self.direction.turn(turn))
     }
 }
 
-enum Turn {
-    Left,
-    Right,
-}
+type Path = Vec<Step>;
 
 struct Maze {
-    width: usize,
-    height: usize,
-    tiles: Vec<Vec<Tile>>,
+    tiles: Grid<Tile>,
 }
 
 impl Maze {
-    fn new(tiles: Vec<Vec<Tile>>) -> Self {
-        let height = tiles.len();
-        let width = tiles.first().map_or(0, |row| row.len());
-        assert!(tiles.iter().all(|row| row.len() == width));
-        Self {
-            width,
-            height,
-            tiles,
-        }
-    }
-    fn show(&self) {
-        for row in &self.tiles {
-            for tile in row {
-                print!(
-                    "{} ",
-                    match tile {
-                        Tile::Wall => '#',
-                        Tile::Empty => '.',
-                        Tile::Start => 'S',
-                        Tile::Exit => 'E',
-                    }
-                );
-            }
-            println!();
-        }
-    }
-    fn show_with_path(&self, path: &Path) {
-        for row in 0..self.height {
-            for col in 0..self.width {
-                let c = Coordinate::new(row as i32, col as i32);
-                if path.contains_step(&Step::new(c, Direction::North)) {
-                    print!("^ ");
-                } else if path.contains_step(&Step::new(c, Direction::South)) {
-                    print!("v ");
-                } else if path.contains_step(&Step::new(c, Direction::West)) {
-                    print!("< ");
-                } else if path.contains_step(&Step::new(c, Direction::East)) {
-                    print!("> ");
-                } else {
-                    print!(
-                        "{} ",
-                        match self[&c] {
-                            Tile::Wall => '#',
-                            Tile::Empty => '.',
-                            Tile::Start => 'S',
-                            Tile::Exit => 'E',
-                        }
-                    );
-                }
-            }
-            println!();
-        }
+    fn new(tiles: Grid<Tile>) -> Self {
+        Self { tiles }
     }
     fn start(&self) -> Option<Step> {
-        for c in self.coordinates() {
-            if self[&c].is_start() {
-                return Some(Step::new(c, Direction::East));
-            }
-        }
-        None
-    }
-    fn shortest_path(&self, start_step: Step) -> Option<Path> {
-        let path = Path::new_with_step(start_step);
-
-        // Do not re-visit the same coordinate.
-        let mut visited: HashSet<Coordinate> = HashSet::new();
-
-        // Keep a priority queue - reversed for a minimum heap.
-        let mut heap = BinaryHeap::new();
-        heap.push(Reverse(ScoredPath(path)));
-
-        // Keep fetching the path with the lowest score.
-        while let Some(Reverse(ScoredPath(path))) = heap.pop() {
-            if path.reaches_exit(self) {
-                return Some(path);
-            } else {
-                let current_step = path.last().expect("Empty path");
-                let next_steps = Direction::iter()
-                    .filter(|d| *d != current_step.direction.opposite())
-                    .map(|d| current_step.take(&d))
-                    .filter(|s| {
-                        let next_tile = self[&s.coordinate];
-                        next_tile.is_empty() || next_tile.is_exit()
-                    })
-                    .filter(|s| visited.insert(s.coordinate));
-
-                for next in next_steps {
-                    let mut new_path = path.clone();
-                    new_path.push(next);
-                    heap.push(Reverse(ScoredPath(new_path)));
-                }
-            }
-        }
-
-        None
-    }
-    fn all_shortest_paths(&self, start_step: Step) -> Paths {
-        let mut shortest_paths = Paths::new();
-        let mut lowest_score: Option<usize> = None;
-        let path = Path::new_with_step(start_step);
-
-        // Keep scores.
-        let mut visited: HashMap<Coordinate, usize> = HashMap::new();
-
-        // Keep a priority queue - reversed for a minimum heap.
-        let mut heap = BinaryHeap::new();
-        heap.push(Reverse(ScoredPath(path)));
-
-        // Keep fetching the path with the lowest score.
-        while let Some(Reverse(ScoredPath(path))) = heap.pop() {
-            if lowest_score.map(|low| path.score > low).unwrap_or(false) {
-                continue;
-            } else if path.reaches_exit(self) {
-                match lowest_score {
-                    Some(low) if path.score < low => {
-                        lowest_score = Some(path.score);
-                        shortest_paths.clear();
-                        shortest_paths.push(path);
-                    }
-                    Some(low) if path.score == low => {
-                        shortest_paths.push(path);
-                    }
-                    None => {
-                        lowest_score = Some(path.score);
-                        shortest_paths.push(path);
-                    }
-                    _ => {}
-                }
-            } else {
-                let current_step = path.last().expect("Empty path");
-                let next_steps = Direction::iter()
-                    .filter(|d| *d != current_step.direction.opposite())
-                    .map(|d| current_step.take(&d))
-                    .filter(|s| !self[&s.coordinate].is_wall())
-                    .filter(|s| !path.contains_coord(&s.coordinate));
-
-                for next in next_steps {
-                    let mut new_path = path.clone();
-                    new_path.push(next);
-
-                    let entry = visited.entry(next.coordinate).or_insert(usize::MAX);
-                    match new_path.score.cmp(entry) {
-                        Ordering::Less => {
-                            *entry = new_path.score;
-                            heap.push(Reverse(ScoredPath(new_path)));
-                        }
-                        Ordering::Equal => {
-                            heap.push(Reverse(ScoredPath(new_path)));
-                        }
-                        Ordering::Greater => {
-                            if new_path.score < *entry + 2000 {
-                                heap.push(Reverse(ScoredPath(new_path)));
-                            }
-                        }
-                    }
-                }
-            }
-        }
-
-        shortest_paths
-    }
-    fn coordinate_is_valid(&self, c: &Coordinate) -> bool {
-        c.row >= 0 && c.row < self.height as i32 && c.col >= 0 && c.col < self.width as i32
-    }
-    fn coordinates(&self) -> CoordinateIterator {
-        CoordinateIterator {
-            current_index: 0,
-            rows: self.height,
-            cols: self.width,
-        }
+        let (row, col) = self.tiles.find(Tile::is_start)?;
+        let c = Coordinate::new(col as i32, row as i32);
+        Some(Step::new(c, Direction4::Right))
+    }
+    /// The lowest scores from the start, until the exit is reached.
+    fn search(&self, start: Step) -> Search<Step, usize> {
+        dijkstra(
+            [start],
+            |step| {
+                step.moves()
+                    .into_iter()
+                    .filter(|(next, _)| !self[&next.coordinate].is_wall())
+            },
+            |step| self[&step.coordinate].is_exit(),
+        )
+    }
+    /// The best path to the exit, with its score.
+    fn shortest_path(&self, start: Step) -> Option<(Path, usize)> {
+        let search = self.search(start);
+        Some((search.path()?, search.cost()?))
+    }
+    /// The tiles on any of the best paths to the exit.
+    fn best_tiles(&self, start: Step) -> HashSet<Coordinate> {
+        self.search(start)
+            .on_best_paths()
+            .into_iter()
+            .map(|step| step.coordinate)
+            .collect()
     }
 }
 
@@ -377,50 +108,67 @@ impl Index<&Coordinate> for Maze {
     type Output = Tile;
 
     fn index(&self, c: &Coordinate) -> &Self::Output {
-        assert!(self.coordinate_is_valid(c));
-        &self.tiles[c.row as usize][c.col as usize]
+        &self.tiles[c]
     }
 }
 
-struct CoordinateIterator {
-    current_index: usize,
-    rows: usize,
-    cols: usize,
+/// The maze with some tiles overdrawn, e.g. by the steps of a path.
+struct MazeView<'a> {
+    maze: &'a Maze,
+    marks: HashMap<Coordinate, char>,
 }
 
-impl Iterator for CoordinateIterator {
-    type Item = Coordinate;
-    fn next(&mut self) -> Option<Self::Item> {
-        if self.current_index < self.rows * self.cols {
-            let row = (self.current_index / self.cols) as i32;
-            let col = (self.current_index % self.cols) as i32;
-            self.current_index += 1;
-            Some(Coordinate::new(row, col))
-        } else {
-            None
+impl<'a> MazeView<'a> {
+    fn new(maze: &'a Maze) -> Self {
+        let marks = HashMap::new();
+        Self { maze, marks }
+    }
+    fn with_path(mut self, path: &[Step]) -> Self {
+        for step in path {
+            let glyph = match step.direction {
+                Direction4::Up => '^',
+                Direction4::Down => 'v',
+                Direction4::Left => '<',
+                Direction4::Right => '>',
+            };
+            self.marks.insert(step.coordinate, glyph);
         }
+        self
+    }
+    fn with_tiles(mut self, tiles: &HashSet<Coordinate>) -> Self {
+        self.marks.extend(tiles.iter().map(|&c| (c, 'O')));
+        self
     }
 }
 
-fn parse_maze(i: &str) -> IResult<&str, Maze> {
-    let (i, tiles) = many1(terminated(
-        many1(alt((
-            map(char('#'), |_| Tile::Wall),
-            map(char('.'), |_| Tile::Empty),
-            map(char('S'), |_| Tile::Start),
-            map(char('E'), |_| Tile::Exit),
-        ))),
-        opt(newline),
-    ))
-    .parse(i)?;
-    Ok((i, Maze::new(tiles)))
+impl Render for MazeView<'_> {
+    fn size(&self) -> (usize, usize) {
+        (self.maze.tiles.width(), self.maze.tiles.height())
+    }
+    fn cell(&self, x: usize, y: usize) -> Cell {
+        let c = Coordinate::new(x as i32, y as i32);
+        match (self.marks.get(&c), self.maze[&c]) {
+            (Some(&glyph), _) => Cell::new(glyph, Color::YELLOW),
+            (None, Tile::Wall) => Cell::new('#', Color::GREY),
+            (None, Tile::Empty) => Cell::new('.', Color::DARK_GREY),
+            (None, Tile::Start) => Cell::new('S', Color::GREEN),
+            (None, Tile::Exit) => Cell::new('E', Color::RED),
+        }
+    }
 }
 
 fn parse_input(input: &str) -> Result<Maze> {
-    parse_maze(input).map_and_finish()
+    let tiles = Grid::parse(input.trim(), |c| match c {
+        '#' => Some(Tile::Wall),
+        '.' => Some(Tile::Empty),
+        'S' => Some(Tile::Start),
+        'E' => Some(Tile::Exit),
+        _ => None,
+    })?;
+    Ok(Maze::new(tiles))
 }
 
-struct Solver {}
+pub(crate) struct Solver {}
 
 impl ResourceReader for Solver {}
 
@@ -433,31 +181,41 @@ impl Task for Solver {
         Day::Day16
     }
 
+    fn status(&self) -> Status {
+        Status::Complete
+    }
+
     fn solve_part1(&self, input: &str) -> Result<String> {
-        let maze = parse_input(input)?;
-        // maze.show();
-        let start = maze.start().expect("No start found");
-        let path = maze.shortest_path(start).expect("No path found");
-        Ok(path.score.to_string())
+        self.solve_with_frames(Part::Part1, input, &mut Frames::disabled())
     }
 
     fn solve_part2(&self, input: &str) -> Result<String> {
+        self.solve_with_frames(Part::Part2, input, &mut Frames::disabled())
+    }
+
+    fn solve_with_frames(&self, part: Part, input: &str, frames: &mut Frames) -> Result<String> {
         let maze = parse_input(input)?;
+        frames.record_with(|| MazeView::new(&maze));
         let start = maze.start().expect("No start found");
-        let all_paths = maze.all_shortest_paths(start);
-        let tile_count = all_paths
-            .iter()
-            .flat_map(|p| p.vec.iter())
-            .map(|s| s.coordinate)
-            .collect::<HashSet<_>>()
-            .len();
-        Ok(tile_count.to_string())
+        match part {
+            Part::Part1 => {
+                let (path, score) = maze.shortest_path(start).expect("No path found");
+                frames.record_with(|| MazeView::new(&maze).with_path(&path));
+                Ok(score.to_string())
+            }
+            Part::Part2 => {
+                let tiles = maze.best_tiles(start);
+                frames.record_with(|| MazeView::new(&maze).with_tiles(&tiles));
+                Ok(tiles.len().to_string())
+            }
+        }
     }
 }
 
 #[cfg(test)]
 mod test {
     use super::*;
+    use aoc_common::render::to_text;
     use rstest::*;
 
     #[fixture]
@@ -499,6 +257,18 @@ mod test {
         Ok(())
     }
 
+    #[rstest]
+    fn example_second_frames(solver: Solver, second_example: &str) -> Result<()> {
+        let mut frames = Frames::new();
+        solver.solve_with_frames(Part::Part2, second_example, &mut frames)?;
+        let [maze, tiles] = frames.frames() else {
+            panic!("Expected two frames");
+        };
+        assert_eq!(to_text(maze).trim(), second_example);
+        assert_eq!(to_text(tiles).matches('O').count(), 64);
+        Ok(())
+    }
+
     #[rstest]
     fn example1(solver: Solver) -> Result<()> {
         let input = solver.read_resource(Input::Example1)?;
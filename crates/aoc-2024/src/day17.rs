@@ -1,9 +1,9 @@
+use aoc_common::parse::{csv_nums, key_value, number};
 use aoc_common::prelude::*;
 use aoc_data::prelude::*;
 use nom::bytes::complete::tag;
-use nom::character::complete;
 use nom::character::complete::newline;
-use nom::multi::separated_list1;
+use nom::sequence::preceded;
 use std::ops::{BitXorAssign, Deref};
 use z3::ast::{Ast, BV};
 
@@ -277,18 +277,13 @@ impl Computer {
 }
 
 fn parse_register_value<'a>(i: &'a str, register: &'a str) -> IResult<&'a str, u64> {
-    let (i, _) = tag("Register ").parse(i)?;
-    let (i, _) = tag(register).parse(i)?;
-    let (i, _) = tag(": ").parse(i)?;
-    let (i, value) = complete::u64(i)?;
+    let (i, (_, value)) = key_value((tag("Register "), tag(register)), ":", number).parse(i)?;
     let (i, _) = newline(i)?;
     Ok((i, value))
 }
 
 fn parse_program(i: &str) -> IResult<&str, Program> {
-    let (i, _) = tag("Program: ").parse(i)?;
-    let (i, program) = separated_list1(tag(","), complete::u64).parse(i)?;
-    Ok((i, program))
+    preceded(tag("Program: "), csv_nums).parse(i)
 }
 
 fn parse_computer(i: &str) -> IResult<&str, (Computer, Program)> {
@@ -306,15 +301,15 @@ fn parse_input(input: &str) -> Result<(Computer, Program)> {
     parse_computer(input).map_and_finish()
 }
 
-enum SolverKind {
+pub(crate) enum SolverKind {
     Flexi,
     Small1,
     Small2,
     Large,
 }
 
-struct Solver {
-    kind: SolverKind,
+pub(crate) struct Solver {
+    pub(crate) kind: SolverKind,
 }
 
 impl ResourceReader for Solver {}
@@ -328,6 +323,19 @@ impl Task for Solver {
         Day::Day17
     }
 
+    fn status(&self) -> Status {
+        Status::Complete
+    }
+
+    fn strategy(&self) -> &str {
+        match self.kind {
+            SolverKind::Flexi => "flexi",
+            SolverKind::Small1 => "small1",
+            SolverKind::Small2 => "small2",
+            SolverKind::Large => "large",
+        }
+    }
+
     fn solve_part1(&self, input: &str) -> Result<String> {
         let (mut computer, program) = parse_input(input)?;
         let executable: Box<dyn Executable> = match self.kind {
@@ -1,4 +1,8 @@
+use aoc_common::dsu::UnionFind;
+use aoc_common::geom::{Direction4, Direction8, Point2};
+use aoc_common::grid::Grid;
 use aoc_common::prelude::*;
+use aoc_common::search::bfs;
 use aoc_data::prelude::*;
 use nom::bytes::complete::tag;
 use nom::character::complete;
@@ -6,89 +10,10 @@ use nom::character::complete::newline;
 use nom::combinator::{map, opt};
 use nom::multi::many1;
 use nom::sequence::{separated_pair, terminated};
-use std::collections::HashSet;
 
-enum Direction {
-    Up,
-    Down,
-    Left,
-    Right,
-}
-
-#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
-struct Position {
-    x: i64,
-    y: i64,
-}
-
-impl Position {
-    fn move_to(&self, direction: Direction) -> Position {
-        match direction {
-            Direction::Up => Position {
-                x: self.x,
-                y: self.y - 1,
-            },
-            Direction::Down => Position {
-                x: self.x,
-                y: self.y + 1,
-            },
-            Direction::Left => Position {
-                x: self.x - 1,
-                y: self.y,
-            },
-            Direction::Right => Position {
-                x: self.x + 1,
-                y: self.y,
-            },
-        }
-    }
-}
-
-struct Path {
-    vec: Vec<Position>,
-    set: HashSet<Position>,
-}
-
-impl Path {
-    fn new() -> Self {
-        let vec = Vec::new();
-        let set = HashSet::new();
-        Path { vec, set }
-    }
-    fn new_with_start(start: Position) -> Self {
-        let vec = vec![start];
-        let set = HashSet::from([start]);
-        Path { vec, set }
-    }
-    fn last(&self) -> Option<&Position> {
-        self.vec.last()
-    }
-    fn push(&mut self, position: Position) {
-        self.vec.push(position);
-        self.set.insert(position);
-    }
-    fn pop(&mut self) -> Option<Position> {
-        let result = self.vec.pop();
-        if let Some(pos) = &result {
-            self.set.remove(pos);
-        }
-        result
-    }
-    fn add(&mut self, position: Position) {
-        self.vec.push(position);
-        self.set.insert(position);
-    }
-    fn contains(&self, position: Position) -> bool {
-        self.set.contains(&position)
-    }
-    fn len(&self) -> usize {
-        self.vec.len()
-    }
-    fn num_steps(&self) -> usize {
-        self.vec.len().max(1) - 1
-    }
-}
+type Position = Point2<i64>;
 
+type Path = Vec<Position>;
 type Positions = Vec<Position>;
 
 #[derive(Debug, Copy, Clone)]
@@ -97,62 +22,94 @@ enum Byte {
     Corrupted,
 }
 
-type Bytes = Vec<Byte>;
-type Grid = Vec<Bytes>;
-
 struct Memory {
-    grid: Grid,
-    width: usize,
-    height: usize,
+    grid: Grid<Byte>,
 }
 
 impl Memory {
     fn new(width: usize, height: usize) -> Self {
-        let grid = vec![vec![Byte::Healthy; width]; height];
-        Memory {
-            grid,
-            width,
-            height,
-        }
+        let grid = Grid::filled(width, height, Byte::Healthy);
+        Memory { grid }
     }
     fn start(&self) -> Position {
         Position { x: 0, y: 0 }
     }
     fn exit(&self) -> Position {
         Position {
-            x: self.width as i64 - 1,
-            y: self.height as i64 - 1,
+            x: self.grid.width() as i64 - 1,
+            y: self.grid.height() as i64 - 1,
         }
     }
-    fn is_start(&self, position: Position) -> bool {
-        self.start().eq(&position)
+    fn corrupt(&mut self, position: Position) {
+        if let Some(byte) = self.grid.get_mut(position) {
+            *byte = Byte::Corrupted;
+        }
     }
-    fn is_exit(&self, position: Position) -> bool {
-        self.exit().eq(&position)
+    fn is_safe(&self, position: Position) -> bool {
+        matches!(self.grid.get(position), Some(Byte::Healthy))
     }
-    fn is_valid(&self, position: Position) -> bool {
-        position.x >= 0
-            && position.x < self.width as i64
-            && position.y >= 0
-            && position.y < self.height as i64
+    fn shortest_path(&self, start: Position, finish: Position) -> Option<Path> {
+        bfs(
+            [start],
+            |&position| {
+                Direction4::iter()
+                    .map(move |d| position + d.delta())
+                    .filter(|&next| self.is_safe(next))
+            },
+            |&position| position == finish,
+        )
+        .path()
     }
 }
 
+/// Memory with bytes falling into it, one at a time.
 struct CorruptingMemory {
-    memory: Memory,
+    size: usize,
     corruptions: Positions,
 }
 
 impl CorruptingMemory {
-    fn new(memory: Memory, corruptions: Positions) -> Self {
-        CorruptingMemory {
-            memory,
-            corruptions,
+    fn new(size: usize, corruptions: Positions) -> Self {
+        CorruptingMemory { size, corruptions }
+    }
+    /// The memory once the first few bytes have fallen.
+    fn after(&self, fallen: usize) -> Memory {
+        let mut memory = Memory::new(self.size, self.size);
+        for &position in self.corruptions.iter().take(fallen) {
+            memory.corrupt(position);
         }
-    }
-    fn shortest_path(&self, start: Position, finish: Position) -> Option<Path> {
-        let path = Path::new_with_start(start);
-        Some(path)
+        memory
+    }
+    /// The first byte to cut the exit off from the start.
+    ///
+    /// The exit is cut off once a chain of corrupted bytes, touching diagonally or side by
+    /// side, runs from the top or right edge to the bottom or left edge.
+    fn first_blocking(&self) -> Option<Position> {
+        let size = self.size as i64;
+        let cells = self.size * self.size;
+        let (top_right, bottom_left) = (cells, cells + 1);
+        let index = |p: Position| (p.y * size + p.x) as usize;
+        let mut walls = UnionFind::new(cells + 2);
+        let mut memory = Memory::new(self.size, self.size);
+        for &byte in &self.corruptions {
+            memory.corrupt(byte);
+            if byte.y == 0 || byte.x == size - 1 {
+                walls.union(index(byte), top_right);
+            }
+            if byte.x == 0 || byte.y == size - 1 {
+                walls.union(index(byte), bottom_left);
+            }
+            for d in Direction8::iter() {
+                let neighbour = byte + d.delta();
+                if matches!(memory.grid.get(neighbour), Some(Byte::Corrupted)) {
+                    walls.union(index(byte), index(neighbour));
+                }
+            }
+            if walls.same(top_right, bottom_left) {
+                return Some(byte);
+            }
+        }
+        None
     }
 }
 
@@ -175,17 +132,34 @@ fn parse_input(input: &str) -> Result<Positions> {
     parse_positions(input).map_and_finish()
 }
 
-enum MemorySize {
+pub(crate) enum MemorySize {
     Small,
     Large,
 }
 
-struct Solver {
+impl MemorySize {
+    /// The width and height of the memory space.
+    fn size(&self) -> usize {
+        match self {
+            MemorySize::Small => 7,
+            MemorySize::Large => 71,
+        }
+    }
+    /// How many bytes have fallen before setting off.
+    fn fallen(&self) -> usize {
+        match self {
+            MemorySize::Small => 12,
+            MemorySize::Large => 1024,
+        }
+    }
+}
+
+pub(crate) struct Solver {
     memory_size: MemorySize,
 }
 
 impl Solver {
-    fn new(memory_size: MemorySize) -> Self {
+    pub(crate) fn new(memory_size: MemorySize) -> Self {
         Solver { memory_size }
     }
 }
@@ -201,31 +175,25 @@ impl Task for Solver {
         Day::Day18
     }
 
-    fn solve_part1(&self, input: &str) -> Result<String> {
-        // Take only the first kilobyte of input.
-        let corruptions = parse_input(input)?.into_iter().take(1024).collect();
-
-        // Set up the memory arena.
-        let memory = match self.memory_size {
-            MemorySize::Small => Memory::new(7, 7),
-            MemorySize::Large => Memory::new(70, 70),
-        };
-
-        // Set up the scenario.
-        let start = memory.start();
-        let finish = memory.exit();
-        let corrupting_memory = CorruptingMemory::new(memory, corruptions);
+    fn status(&self) -> Status {
+        Status::Complete
+    }
 
-        // Solve.
-        let path = corrupting_memory
-            .shortest_path(start, finish)
+    fn solve_part1(&self, input: &str) -> Result<String> {
+        let corruptions = parse_input(input)?;
+        let memory = CorruptingMemory::new(self.memory_size.size(), corruptions)
+            .after(self.memory_size.fallen());
+        let path = memory
+            .shortest_path(memory.start(), memory.exit())
             .expect("No path found");
-
-        Ok(path.num_steps().to_string())
+        Ok((path.len() - 1).to_string())
     }
 
     fn solve_part2(&self, input: &str) -> Result<String> {
-        Ok("0".to_string())
+        let corruptions = parse_input(input)?;
+        let memory = CorruptingMemory::new(self.memory_size.size(), corruptions);
+        let byte = memory.first_blocking().expect("The exit is never cut off");
+        Ok(format!("{},{}", byte.x, byte.y))
     }
 }
 
@@ -239,7 +207,7 @@ mod test {
     fn example1() -> Result<()> {
         let solver = Solver::new(MemorySize::Small);
         let input = solver.read_resource(Input::Example1)?;
-        assert_eq!(solver.solve_part1(&input)?, "0");
+        assert_eq!(solver.solve_part1(&input)?, "22");
         Ok(())
     }
 
@@ -247,7 +215,7 @@ mod test {
     fn example2() -> Result<()> {
         let solver = Solver::new(MemorySize::Small);
         let input = solver.read_resource(Input::Example2)?;
-        assert_eq!(solver.solve_part2(&input)?, "0");
+        assert_eq!(solver.solve_part2(&input)?, "6,1");
         Ok(())
     }
 
@@ -255,7 +223,7 @@ mod test {
     fn part1() -> Result<()> {
         let solver = Solver::new(MemorySize::Large);
         let input = solver.read_resource(Input::Part1)?;
-        assert_eq!(solver.solve_part1(&input)?, "0");
+        assert_eq!(solver.solve_part1(&input)?, "354");
         Ok(())
     }
 
@@ -263,7 +231,7 @@ mod test {
     fn part2() -> Result<()> {
         let solver = Solver::new(MemorySize::Large);
         let input = solver.read_resource(Input::Part2)?;
-        assert_eq!(solver.solve_part2(&input)?, "0");
+        assert_eq!(solver.solve_part2(&input)?, "36,17");
         Ok(())
     }
 }
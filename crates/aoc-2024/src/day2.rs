@@ -74,7 +74,7 @@ fn parse_input(i: &str) -> Result<Vec<Report>> {
     many1(parse_report).parse(i).map_and_finish()
 }
 
-struct Solver {}
+pub(crate) struct Solver {}
 
 impl ResourceReader for Solver {}
 
@@ -87,6 +87,10 @@ impl Task for Solver {
         Day::Day2
     }
 
+    fn status(&self) -> Status {
+        Status::Complete
+    }
+
     fn solve_part1(&self, input: &str) -> Result<String> {
         let reports = parse_input(input)?;
         let num_safe = reports.iter().filter(|r| r.is_safe()).count();
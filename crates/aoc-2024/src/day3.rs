@@ -57,7 +57,7 @@ fn parse_input(i: &str) -> Result<Vec<Op>> {
     many1(parse_ops).parse(i).map_and_finish()
 }
 
-struct Solver {}
+pub(crate) struct Solver {}
 
 impl ResourceReader for Solver {}
 
@@ -70,6 +70,10 @@ impl Task for Solver {
         Day::Day3
     }
 
+    fn status(&self) -> Status {
+        Status::Complete
+    }
+
     fn solve_part1(&self, input: &str) -> Result<String> {
         let ops = parse_input(input)?;
         let result: i32 = ops.iter().map(|op| op.eval()).sum();
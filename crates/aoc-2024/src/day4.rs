@@ -1,134 +1,48 @@
+use aoc_common::geom::{Direction8, Point2};
+use aoc_common::grid::Grid;
 use aoc_common::prelude::*;
 use aoc_data::prelude::*;
 use std::collections::HashMap;
-use std::ops::Index;
 
-#[derive(Debug, EnumIter)]
-enum Direction {
-    North,
-    NorthEast,
-    East,
-    SouthEast,
-    South,
-    SouthWest,
-    West,
-    NorthWest,
-}
+type Position = Point2<i32>;
+type Positions = Vec<Position>;
 
-impl Direction {
-    fn delta(&self) -> (i32, i32) {
-        match self {
-            Direction::North => (0, -1),
-            Direction::NorthEast => (1, -1),
-            Direction::East => (1, 0),
-            Direction::SouthEast => (1, 1),
-            Direction::South => (0, 1),
-            Direction::SouthWest => (-1, 1),
-            Direction::West => (-1, 0),
-            Direction::NorthWest => (-1, -1),
-        }
-    }
+/// `n` positions from `start`, stepping `direction` each time.
+fn mark_line(start: Position, direction: Direction8, n: usize) -> Positions {
+    let step = direction.delta();
+    (0..n as i32).map(|i| start + step * i).collect()
 }
 
-struct Grid<T> {
-    rows: usize,
-    cols: usize,
-    data: Vec<T>,
+/// Lines of `n` positions outwards from `start`, one per direction.
+fn mark_lines(start: Position, n: usize) -> Vec<Positions> {
+    Direction8::iter()
+        .map(|d| mark_line(start, d, n))
+        .collect_vec()
 }
 
-#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
-struct Coordinate {
-    row: usize,
-    col: usize,
+/// The diagonals of length `n` through `centre`, in both directions.
+fn mark_x(centre: Position, n: usize) -> Vec<Positions> {
+    assert_eq!(n % 2, 1);
+    let h = (n as i32) / 2;
+    [
+        Direction8::DownRight,
+        Direction8::DownLeft,
+        Direction8::UpLeft,
+        Direction8::UpRight,
+    ]
+    .into_iter()
+    .map(|d| mark_line(centre - d.delta() * h, d, n))
+    .collect_vec()
 }
 
-type Coordinates = Vec<Coordinate>;
-
-impl Coordinate {
-    fn new(row: usize, col: usize) -> Coordinate {
-        Coordinate { row, col }
-    }
-    fn neighbor(&self, d: &Direction) -> Option<Coordinate> {
-        let (dx, dy) = d.delta();
-        let new_row = self.row as i32 + dy;
-        let new_col = self.col as i32 + dx;
-        if new_row >= 0 && new_col >= 0 {
-            Some(Coordinate::new(new_row as usize, new_col as usize))
-        } else {
-            None
-        }
-    }
-
-    fn mark_line(&self, d: &Direction, n: usize) -> Vec<Coordinate> {
-        let mut current = *self;
-        let mut result = vec![current];
-        for _ in 1..n {
-            current = match current.neighbor(d) {
-                Some(c) => c,
-                None => break,
-            };
-            result.push(current);
-        }
-        result
-    }
-
-    fn mark_lines(&self, n: usize) -> Vec<Coordinates> {
-        Direction::iter()
-            .map(|d| self.mark_line(&d, n))
-            .collect_vec()
-    }
-
-    fn mark_x(&self, n: usize) -> Vec<Coordinates> {
-        assert_eq!(n % 2, 1);
-        let h = (n as i32) / 2;
-
-        let mut line_1 = vec![];
-        let mut line_2 = vec![];
-        for i in -h..=h {
-            let new_row = self.row as i32 + i;
-            let new_col_1 = self.col as i32 + i;
-            let new_col_2 = self.col as i32 - i;
-            if new_row >= 0 && new_col_1 >= 0 {
-                let c = Coordinate::new(new_row as usize, new_col_1 as usize);
-                line_1.push(c);
-            }
-            if new_row >= 0 && new_col_2 >= 0 {
-                let c = Coordinate::new(new_row as usize, new_col_2 as usize);
-                line_2.push(c);
-            }
-        }
-
-        let line_3 = line_1.iter().rev().copied().collect_vec();
-        let line_4 = line_2.iter().rev().copied().collect_vec();
-        vec![line_1, line_2, line_3, line_4]
-    }
+trait WordSearch<T> {
+    fn search(&self, s: &[T]) -> usize;
+    fn search_x(&self, s: &[T]) -> usize;
+    fn values(&self, positions: &[Position]) -> Vec<T>;
 }
 
-impl<T> Grid<T> {
-    fn new(data: Vec<Vec<T>>) -> Grid<T> {
-        let rows = data.len();
-        let cols = data.first().map(|r| r.len()).unwrap_or(0);
-        assert!(data.iter().all(|r| r.len() == cols));
-        let data = data.into_iter().flatten().collect();
-        Grid { rows, cols, data }
-    }
-
-    fn show(&self)
-    where
-        T: std::fmt::Display,
-    {
-        for row in 0..self.rows {
-            for col in 0..self.cols {
-                print!("{} ", self.data[row * self.cols + col]);
-            }
-            println!();
-        }
-    }
-
-    fn search(&self, s: &[T]) -> usize
-    where
-        T: PartialEq + Copy,
-    {
+impl<T: PartialEq + Copy> WordSearch<T> for Grid<T> {
+    fn search(&self, s: &[T]) -> usize {
         // for each coordinate:
         //    for each direction:
         //       get all coordinates that match the string length radiating out
@@ -136,40 +50,23 @@ impl<T> Grid<T> {
         //       collect the coordinates
         //    flatten the coordinates
         self.coordinates()
-            .flat_map(|c| {
-                c.mark_lines(s.len())
+            .flat_map(|(row, col)| {
+                mark_lines(Position::new(col as i32, row as i32), s.len())
                     .into_iter()
-                    .filter(|vc| {
-                        let values = vc
-                            .iter()
-                            .filter(|c| self.coordinate_is_valid(c))
-                            .map(|c| *self.coordinate_to_value(c))
-                            .collect_vec();
-                        values.as_slice() == s
-                    })
+                    .filter(|vc| self.values(vc).as_slice() == s)
                     .collect_vec()
             })
             .count()
     }
 
-    fn search_x(&self, s: &[T]) -> usize
-    where
-        T: PartialEq + Copy,
-    {
+    fn search_x(&self, s: &[T]) -> usize {
         assert_eq!(s.len() % 2, 1);
         assert!(!s.is_empty());
         self.coordinates()
-            .flat_map(|c| {
-                c.mark_x(s.len())
+            .flat_map(|(row, col)| {
+                mark_x(Position::new(col as i32, row as i32), s.len())
                     .into_iter()
-                    .filter(|vc| {
-                        let values = vc
-                            .iter()
-                            .filter(|c| self.coordinate_is_valid(c))
-                            .map(|c| *self.coordinate_to_value(c))
-                            .collect_vec();
-                        values.as_slice() == s
-                    })
+                    .filter(|vc| self.values(vc).as_slice() == s)
                     .collect_vec()
             })
             .map(|v| v[s.len() / 2])
@@ -182,63 +79,23 @@ impl<T> Grid<T> {
             .count()
     }
 
-    fn coordinates(&self) -> CoordinateIterator {
-        CoordinateIterator {
-            current_index: 0,
-            rows: self.rows,
-            cols: self.cols,
-        }
-    }
-
-    fn coordinate_is_valid(&self, c: &Coordinate) -> bool {
-        c.row < self.rows && c.col < self.cols
-    }
-    fn coordinate_to_index(&self, c: &Coordinate) -> usize {
-        self.cols * c.row + c.col
-    }
-    fn coordinate_to_value(&self, c: &Coordinate) -> &T {
-        let i = self.coordinate_to_index(c);
-        &self.data[i]
-    }
-}
-
-struct CoordinateIterator {
-    current_index: usize,
-    rows: usize,
-    cols: usize,
-}
-
-impl Iterator for CoordinateIterator {
-    type Item = Coordinate;
-    fn next(&mut self) -> Option<Self::Item> {
-        if self.current_index < self.rows * self.cols {
-            let row = self.current_index / self.cols;
-            let col = self.current_index % self.cols;
-            self.current_index += 1;
-            Some(Coordinate { row, col })
-        } else {
-            None
-        }
-    }
-}
-
-impl<T> Index<Coordinate> for Grid<T> {
-    type Output = T;
-
-    fn index(&self, c: Coordinate) -> &Self::Output {
-        let index = self.coordinate_to_index(&c);
-        &self.data[index]
+    /// The values along some positions, skipping any off the grid.
+    fn values(&self, positions: &[Position]) -> Vec<T> {
+        positions
+            .iter()
+            .filter_map(|&p| self.get(p))
+            .copied()
+            .collect()
     }
 }
 
 type CharGrid = Grid<char>;
 
 fn parse_input(i: &str) -> Result<CharGrid> {
-    let data = i.lines().map(|l| l.trim().chars().collect()).collect_vec();
-    Ok(CharGrid::new(data))
+    Grid::parse(i.trim(), Some)
 }
 
-struct Solver {}
+pub(crate) struct Solver {}
 
 impl ResourceReader for Solver {}
 
@@ -251,9 +108,12 @@ impl Task for Solver {
         Day::Day4
     }
 
+    fn status(&self) -> Status {
+        Status::Complete
+    }
+
     fn solve_part1(&self, input: &str) -> Result<String> {
         let grid = parse_input(input)?;
-        // grid.show();
         let term = "XMAS".chars().collect_vec();
         let num = grid.search(&term);
         Ok(num.to_string())
@@ -1,12 +1,11 @@
+use aoc_common::graph::Graph;
+use aoc_common::parse::{csv_nums, lines, number, sections};
 use aoc_common::prelude::*;
 use aoc_data::prelude::*;
 use nom::IResult;
 use nom::bytes::complete::tag;
-use nom::character::complete;
-use nom::character::complete::newline;
-use nom::combinator::{map, opt};
-use nom::multi::{many0, many1, separated_list1};
-use nom::sequence::{separated_pair, terminated};
+use nom::combinator::map;
+use nom::sequence::separated_pair;
 
 type Page = u32;
 
@@ -28,10 +27,7 @@ trait RuleChecking {
 }
 
 trait RuleFixing {
-    fn fix_with_rule(&mut self, rule: &Rule) -> bool;
-    fn fix_with_rules(&mut self, rules: &Rules) {
-        while rules.iter().any(|rule| self.fix_with_rule(rule)) {}
-    }
+    fn fix_with_rules(&mut self, rules: &Rules) -> Result<()>;
 }
 
 trait MiddlePage {
@@ -57,23 +53,22 @@ impl RuleChecking for Update {
 }
 
 impl RuleFixing for Update {
-    fn fix_with_rule(&mut self, rule: &Rule) -> bool {
-        let ante = self
-            .iter()
-            .find_position(|&&page| page == rule.ante)
-            .map(|r| r.0);
-        let post = self
+    fn fix_with_rules(&mut self, rules: &Rules) -> Result<()> {
+        let mut graph = Graph::directed();
+        for &page in self.iter() {
+            graph.node(page);
+        }
+        for rule in rules
             .iter()
-            .find_position(|&&page| page == rule.post)
-            .map(|r| r.0);
-        if let (Some(ante_index), Some(post_index)) = (ante, post)
-            && ante_index >= post_index
+            .filter(|rule| self.contains(&rule.ante) && self.contains(&rule.post))
         {
-            self.remove(ante_index);
-            self.insert(post_index, rule.ante);
-            return true;
+            graph.add_edge(rule.ante, rule.post);
         }
-        false
+        let order = graph.topological_sort().ok_or_else(|| {
+            AdventError::InvalidInput(format!("Rules for update {self:?} are cyclic"))
+        })?;
+        *self = order.into_iter().map(|id| *graph.name(id)).collect();
+        Ok(())
     }
 }
 
@@ -83,42 +78,22 @@ impl MiddlePage for Update {
     }
 }
 
-fn parse_page(i: &str) -> IResult<&str, Page> {
-    complete::u32(i)
-}
-
 fn parse_rule(i: &str) -> IResult<&str, Rule> {
-    map(
-        separated_pair(parse_page, tag("|"), parse_page),
-        |(ante, post)| Rule { ante, post },
-    )
+    map(separated_pair(number, tag("|"), number), |(ante, post)| {
+        Rule { ante, post }
+    })
     .parse(i)
 }
 
-fn parse_rules(i: &str) -> IResult<&str, Rules> {
-    many1(terminated(parse_rule, newline)).parse(i)
-}
-
-fn parse_update(i: &str) -> IResult<&str, Update> {
-    terminated(separated_list1(tag(","), parse_page), opt(newline)).parse(i)
-}
-
-fn parse_updates(i: &str) -> IResult<&str, Updates> {
-    many1(parse_update).parse(i)
-}
-
 fn parse_raw(i: &str) -> IResult<&str, (Rules, Updates)> {
-    let (i, rules) = parse_rules(i)?;
-    let (i, _) = many0(newline).parse(i)?;
-    let (_, updates) = parse_updates(i)?;
-    Ok((i, (rules, updates)))
+    sections(lines(parse_rule), lines(csv_nums)).parse(i)
 }
 
 fn parse_input(i: &str) -> Result<(Rules, Updates)> {
     parse_raw(i).map_and_finish()
 }
 
-struct Solver {}
+pub(crate) struct Solver {}
 
 impl ResourceReader for Solver {}
 
@@ -131,6 +106,10 @@ impl Task for Solver {
         Day::Day5
     }
 
+    fn status(&self) -> Status {
+        Status::Complete
+    }
+
     fn solve_part1(&self, input: &str) -> Result<String> {
         let (rules, updates) = parse_input(input)?;
         let result: Page = updates
@@ -143,14 +122,14 @@ impl Task for Solver {
 
     fn solve_part2(&self, input: &str) -> Result<String> {
         let (rules, updates) = parse_input(input)?;
-        let result: Page = updates
+        let result = updates
             .into_iter()
             .filter(|update| !update.check_rules(&rules))
             .map(|mut update| {
-                update.fix_with_rules(&rules);
-                update.middle()
+                update.fix_with_rules(&rules)?;
+                Ok(update.middle())
             })
-            .sum();
+            .sum::<Result<Page>>()?;
         Ok(result.to_string())
     }
 }
@@ -179,6 +158,15 @@ mod test {
         Ok(())
     }
 
+    #[rstest]
+    fn cyclic_rules(solver: Solver) {
+        let input = "1|2\n2|3\n3|1\n\n3,2,1\n";
+        assert!(matches!(
+            solver.solve_part2(input),
+            Err(AdventError::InvalidInput(_))
+        ));
+    }
+
     #[rstest]
     fn part1(solver: Solver) -> Result<()> {
         let input = solver.read_resource(Input::Part1)?;
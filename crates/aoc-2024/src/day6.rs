@@ -1,51 +1,19 @@
+use aoc_common::geom::{Direction4, Point2};
 use aoc_common::prelude::*;
 use aoc_data::prelude::*;
 use nom::branch::alt;
 use nom::bytes::complete::tag;
 use nom::character::complete::{newline, one_of};
-use nom::combinator::map;
+use nom::combinator::{map, map_res};
 use nom::multi::{many1, separated_list1};
 use std::collections::HashSet;
 
-#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
-enum Direction {
-    Up,
-    Down,
-    Left,
-    Right,
-}
-
-impl Direction {
-    fn turn(&mut self, turn: Turn) {
-        *self = self.turned(turn);
-    }
-    fn turned(&self, turn: Turn) -> Direction {
-        match turn {
-            Turn::Left => match self {
-                Direction::Up => Direction::Left,
-                Direction::Left => Direction::Down,
-                Direction::Down => Direction::Right,
-                Direction::Right => Direction::Up,
-            },
-            Turn::Right => match self {
-                Direction::Up => Direction::Right,
-                Direction::Right => Direction::Down,
-                Direction::Down => Direction::Left,
-                Direction::Left => Direction::Up,
-            },
-        }
-    }
-}
-
-enum Turn {
-    Left,
-    Right,
-}
+type Position = Point2<i32>;
 
 #[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
 enum Tile {
     Floor,
-    Guard(Direction),
+    Guard(Direction4),
     Block,
     Outside,
 }
@@ -57,20 +25,20 @@ struct Map {
 }
 
 struct Guard {
-    direction: Direction,
-    position: Coordinate,
+    direction: Direction4,
+    position: Position,
 }
 
 #[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
 struct Step {
-    coordinate: Coordinate,
-    direction: Direction,
+    position: Position,
+    direction: Direction4,
 }
 
 impl Step {
-    fn new(coordinate: Coordinate, direction: Direction) -> Step {
+    fn new(position: Position, direction: Direction4) -> Step {
         Step {
-            coordinate,
+            position,
             direction,
         }
     }
@@ -85,13 +53,13 @@ enum StepEvent {
 impl Guard {
     fn take_step(&mut self, map: &Map) -> StepEvent {
         for _ in 0..4 {
-            let next = self.position.step(self.direction);
+            let next = self.position + self.direction.delta();
             match map.get_tile(&next) {
                 Tile::Floor | Tile::Guard(_) => {
                     self.position = next;
                     return StepEvent::Walk(Step::new(next, self.direction));
                 }
-                Tile::Block => self.direction.turn(Turn::Right),
+                Tile::Block => self.direction = self.direction.turn_right(),
                 Tile::Outside => return StepEvent::Outside,
             }
         }
@@ -115,30 +83,30 @@ impl Map {
         assert!(tiles.iter().all(|row| row.len() == cols));
         Map { tiles, rows, cols }
     }
-    fn with_new_tile(&self, tile: Tile, coord: &Coordinate) -> Map {
+    fn with_new_tile(&self, tile: Tile, coord: &Position) -> Map {
         let mut tiles = self.tiles.clone();
         if self.is_inside(coord) {
-            tiles[coord.row as usize][coord.col as usize] = tile;
+            tiles[coord.y as usize][coord.x as usize] = tile;
         } else {
-            panic!("Coordinate outside map");
+            panic!("Position outside map");
         }
         Map::new(tiles)
     }
-    fn get_tile(&self, coord: &Coordinate) -> Tile {
+    fn get_tile(&self, coord: &Position) -> Tile {
         if self.is_inside(coord) {
-            self.tiles[coord.row as usize][coord.col as usize]
+            self.tiles[coord.y as usize][coord.x as usize]
         } else {
             Tile::Outside
         }
     }
-    fn get_all_coordinates(&self, tile: Tile) -> HashSet<Coordinate> {
+    fn get_all_positions(&self, tile: Tile) -> HashSet<Position> {
         self.tiles
             .iter()
             .enumerate()
             .flat_map(|(row, row_tiles)| {
                 row_tiles.iter().enumerate().filter_map(move |(col, t)| {
                     if t == &tile {
-                        Some(Coordinate::new(row as i32, col as i32))
+                        Some(Position::new(col as i32, row as i32))
                     } else {
                         None
                     }
@@ -150,7 +118,7 @@ impl Map {
         for (row, tiles) in self.tiles.iter().enumerate() {
             for (col, tile) in tiles.iter().enumerate() {
                 if let Tile::Guard(direction) = tile {
-                    let position = Coordinate::new(row as i32, col as i32);
+                    let position = Position::new(col as i32, row as i32);
                     return Guard {
                         direction: *direction,
                         position,
@@ -160,70 +128,116 @@ impl Map {
         }
         panic!("No guard found");
     }
-    fn show(&self) {
-        for row in &self.tiles {
-            for tile in row {
-                match tile {
-                    Tile::Floor => print!("."),
-                    Tile::Guard(Direction::Up) => print!("^"),
-                    Tile::Guard(Direction::Down) => print!("v"),
-                    Tile::Guard(Direction::Left) => print!("<"),
-                    Tile::Guard(Direction::Right) => print!(">"),
-                    Tile::Block => print!("#"),
-                    Tile::Outside => print!(" "),
-                }
-                print!(" ");
-            }
-            println!();
+    fn is_inside(&self, coord: &Position) -> bool {
+        coord.y >= 0 && coord.y < self.rows as i32 && coord.x >= 0 && coord.x < self.cols as i32
+    }
+    fn walk(self, limit: Option<usize>, frames: &mut Frames) -> Walk {
+        let mut patrol = Patrol::new(self, limit);
+        patrol.run(frames);
+        patrol.into_walk()
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+enum Outcome {
+    Loop,
+    Exit,
+    Limit,
+}
+
+/// The guard walking the map until they leave it, loop or reach the step limit.
+struct Patrol {
+    map: Map,
+    guard: Guard,
+    limit: Option<usize>,
+    walk: Steps,
+    walkset: HashSet<Step>,
+    outcome: Option<Outcome>,
+}
+
+impl Patrol {
+    fn new(map: Map, limit: Option<usize>) -> Patrol {
+        let guard = map.guard();
+        let walk = vec![Step::new(guard.position, guard.direction)];
+        let walkset = walk.iter().cloned().collect::<HashSet<_>>();
+        Patrol {
+            map,
+            guard,
+            limit,
+            walk,
+            walkset,
+            outcome: None,
         }
     }
-    fn is_inside(&self, coord: &Coordinate) -> bool {
-        coord.row >= 0
-            && coord.row < self.rows as i32
-            && coord.col >= 0
-            && coord.col < self.cols as i32
+    fn into_walk(self) -> Walk {
+        match self.outcome {
+            Some(Outcome::Loop) => Walk::Loop(self.walk),
+            Some(Outcome::Exit) => Walk::Exit(self.walk),
+            Some(Outcome::Limit) => Walk::Limit(self.walk),
+            None => panic!("Walk not finished"),
+        }
     }
-    fn walk(&self, limit: Option<usize>) -> Walk {
-        let mut guard = self.guard();
-        let mut walk = vec![Step::new(guard.position, guard.direction)];
-        let mut walkset = walk.iter().cloned().collect::<HashSet<_>>();
-        loop {
-            match limit {
-                Some(limit) if walk.len() > limit => return Walk::Limit(walk),
-                _ => (),
+}
+
+impl Simulation for Patrol {
+    fn step(&mut self) {
+        if self.is_done() {
+            return;
+        }
+        match self.limit {
+            Some(limit) if self.walk.len() > limit => {
+                self.outcome = Some(Outcome::Limit);
+                return;
             }
-            match guard.take_step(self) {
-                StepEvent::Walk(step) => {
-                    if walkset.contains(&step) {
-                        return Walk::Loop(walk);
-                    }
-                    walkset.insert(step);
-                    walk.push(step);
+            _ => (),
+        }
+        match self.guard.take_step(&self.map) {
+            StepEvent::Walk(step) => {
+                if self.walkset.contains(&step) {
+                    self.outcome = Some(Outcome::Loop);
+                    return;
                 }
-                StepEvent::Stuck => panic!("Guard stuck"),
-                StepEvent::Outside => break,
+                self.walkset.insert(step);
+                self.walk.push(step);
             }
+            StepEvent::Stuck => panic!("Guard stuck"),
+            StepEvent::Outside => self.outcome = Some(Outcome::Exit),
         }
-        Walk::Exit(walk)
+    }
+    fn render(&self) -> Frame {
+        Frame::capture(self)
+    }
+    fn is_done(&self) -> bool {
+        self.outcome.is_some()
+    }
+    fn status(&self) -> String {
+        let Position { x: col, y: row } = self.guard.position;
+        let direction = self.guard.direction;
+        let steps = self.walk.len() - 1;
+        format!("step {steps}, guard at row {row}, column {col} facing {direction:?}")
     }
 }
 
-#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
-struct Coordinate {
-    row: i32,
-    col: i32,
-}
-
-impl Coordinate {
-    fn new(row: i32, col: i32) -> Coordinate {
-        Coordinate { row, col }
+impl Render for Patrol {
+    fn size(&self) -> (usize, usize) {
+        (self.map.cols, self.map.rows)
     }
-    fn step(&self, direction: Direction) -> Coordinate {
-        match direction {
-            Direction::Up => Coordinate::new(self.row - 1, self.col),
-            Direction::Down => Coordinate::new(self.row + 1, self.col),
-            Direction::Left => Coordinate::new(self.row, self.col - 1),
-            Direction::Right => Coordinate::new(self.row, self.col + 1),
+    fn cell(&self, x: usize, y: usize) -> Cell {
+        let position = Position::new(x as i32, y as i32);
+        let visited = || Direction4::iter().any(|d| self.walkset.contains(&Step::new(position, d)));
+        match self.map.tiles[y][x] {
+            _ if position == self.guard.position => {
+                let glyph = match self.guard.direction {
+                    Direction4::Up => '^',
+                    Direction4::Down => 'v',
+                    Direction4::Left => '<',
+                    Direction4::Right => '>',
+                };
+                Cell::new(glyph, Color::RED)
+            }
+            Tile::Block => Cell::new('#', Color::GREY),
+            _ if visited() => Cell::new('X', Color::BLUE),
+            Tile::Floor | Tile::Guard(_) | Tile::Outside => Cell::new('.', Color::DARK_GREY),
         }
     }
 }
@@ -233,13 +247,7 @@ fn parse_map(input: &str) -> IResult<&str, Map> {
         newline,
         many1(alt((
             map(tag("."), |_| Tile::Floor),
-            map(one_of("^v<>"), |c| match c {
-                '^' => Tile::Guard(Direction::Up),
-                'v' => Tile::Guard(Direction::Down),
-                '<' => Tile::Guard(Direction::Left),
-                '>' => Tile::Guard(Direction::Right),
-                _ => unreachable!(),
-            }),
+            map_res(one_of("^v<>"), |c| Direction4::try_from(c).map(Tile::Guard)),
             map(tag("#"), |_| Tile::Block),
         ))),
     )
@@ -253,7 +261,7 @@ fn parse_input(input: &str) -> Result<Map> {
     parse_map(input).map_and_finish()
 }
 
-struct Solver {}
+pub(crate) struct Solver {}
 
 impl ResourceReader for Solver {}
 
@@ -266,31 +274,22 @@ impl Task for Solver {
         Day::Day6
     }
 
+    fn status(&self) -> Status {
+        Status::Complete
+    }
+
     fn solve_part1(&self, input: &str) -> Result<String> {
-        let map = parse_input(input)?;
-        // map.show();
-        match map.walk(None) {
-            Walk::Exit(walk) => {
-                let result = walk
-                    .iter()
-                    .map(|s| s.coordinate)
-                    .collect::<HashSet<_>>()
-                    .len();
-                Ok(result.to_string())
-            }
-            Walk::Loop(_) => Err(AdventError::Other("Loop detected".to_string())),
-            Walk::Limit(_) => Err(AdventError::Other("Walk limit reached".to_string())),
-        }
+        self.solve_with_frames(Part::Part1, input, &mut Frames::disabled())
     }
 
     fn solve_part2(&self, input: &str) -> Result<String> {
         let map = parse_input(input)?;
-        let floor_tiles = map.get_all_coordinates(Tile::Floor);
+        let floor_tiles = map.get_all_positions(Tile::Floor);
         let result = floor_tiles
             .iter()
             .filter(|coord| {
                 let test_map = map.with_new_tile(Tile::Block, coord);
-                match test_map.walk(Some(1_000_000)) {
+                match test_map.walk(Some(1_000_000), &mut Frames::disabled()) {
                     Walk::Exit(_) => false,
                     Walk::Loop(_) => true,
                     Walk::Limit(_) => panic!("Walk limit reached"),
@@ -300,12 +299,40 @@ impl Task for Solver {
 
         Ok(result.to_string())
     }
+
+    fn simulation(&self, part: Part, input: &str) -> Result<Option<Box<dyn Simulation>>> {
+        match part {
+            Part::Part1 => Ok(Some(Box::new(Patrol::new(parse_input(input)?, None)))),
+            Part::Part2 => Ok(None),
+        }
+    }
+
+    /// Only the first part is drawn; the second walks thousands of altered maps.
+    fn solve_with_frames(&self, part: Part, input: &str, frames: &mut Frames) -> Result<String> {
+        if part == Part::Part2 {
+            return self.solve_part2(input);
+        }
+        let map = parse_input(input)?;
+        match map.walk(None, frames) {
+            Walk::Exit(walk) => {
+                let result = walk
+                    .iter()
+                    .map(|s| s.position)
+                    .collect::<HashSet<_>>()
+                    .len();
+                Ok(result.to_string())
+            }
+            Walk::Loop(_) => Err(AdventError::Other("Loop detected".to_string())),
+            Walk::Limit(_) => Err(AdventError::Other("Walk limit reached".to_string())),
+        }
+    }
 }
 
 #[cfg(test)]
 mod test {
 
     use super::*;
+    use aoc_common::render::to_text;
     use rstest::*;
 
     #[fixture]
@@ -320,6 +347,16 @@ mod test {
         Ok(())
     }
 
+    #[rstest]
+    fn frames(solver: Solver) -> Result<()> {
+        let input = solver.read_resource(Input::Example1)?;
+        let mut frames = Frames::new();
+        solver.solve_with_frames(Part::Part1, &input, &mut frames)?;
+        let first = frames.frames().first().expect("No frames");
+        assert_eq!(to_text(first).trim(), input.trim());
+        Ok(())
+    }
+
     #[rstest]
     fn example2(solver: Solver) -> Result<()> {
         let input = solver.read_resource(Input::Example2)?;
@@ -88,7 +88,7 @@ fn parse_input(i: &str) -> Result<Equations> {
     many1(parse_equation).parse(i).map_and_finish()
 }
 
-struct Solver {}
+pub(crate) struct Solver {}
 
 impl ResourceReader for Solver {}
 
@@ -101,6 +101,10 @@ impl Task for Solver {
         Day::Day7
     }
 
+    fn status(&self) -> Status {
+        Status::Complete
+    }
+
     fn solve_part1(&self, input: &str) -> Result<String> {
         let equations = parse_input(input)?;
         let result: i64 = equations
@@ -1,3 +1,4 @@
+use aoc_common::geom::Point2;
 use aoc_common::prelude::*;
 use aoc_data::prelude::*;
 use nom::AsChar;
@@ -7,6 +8,7 @@ use nom::character::complete::{newline, satisfy};
 use nom::combinator::map;
 use nom::multi::{many1, separated_list1};
 use std::collections::{HashMap, HashSet};
+use std::fmt::{Display, Formatter};
 use std::ops::Range;
 
 type Frequency = char;
@@ -19,35 +21,29 @@ enum Tile {
 
 struct Antenna {
     freq: char,
-    coordinate: Coordinate,
+    position: Position,
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
-struct Coordinate {
-    row: i32,
-    col: i32,
-}
+type Position = Point2<i32>;
+type Positions = HashSet<Position>;
 
-type Coordinates = HashSet<Coordinate>;
+trait Antinodes: Sized {
+    fn antinode_for(&self, other: &Self, n: usize) -> Self;
+    fn antinodes_within(&self, other: &Self, rows: &Range<i32>, cols: &Range<i32>) -> Vec<Self>;
+}
 
-impl Coordinate {
+impl Antinodes for Position {
     fn antinode_for(&self, other: &Self, n: usize) -> Self {
-        let delta_row = self.row - other.row;
-        let delta_col = self.col - other.col;
-        let row = self.row + delta_row * n as i32;
-        let col = self.col + delta_col * n as i32;
-        Self { row, col }
+        *self + (*self - *other) * n as i32
     }
     fn antinodes_within(&self, other: &Self, rows: &Range<i32>, cols: &Range<i32>) -> Vec<Self> {
-        let delta_row = self.row - other.row;
-        let delta_col = self.col - other.col;
-        if delta_row == 0 && delta_col == 0 {
+        if self == other {
             return vec![*self];
         }
         let mut antinodes = vec![];
         for n in 0.. {
             let antinode = self.antinode_for(other, n);
-            if rows.contains(&antinode.row) && cols.contains(&antinode.col) {
+            if rows.contains(&antinode.y) && cols.contains(&antinode.x) {
                 antinodes.push(antinode);
             } else {
                 break;
@@ -78,10 +74,7 @@ impl Map {
                     .filter_map(move |(j, tile)| match tile {
                         Tile::Antenna(freq) => Some(Antenna {
                             freq: *freq,
-                            coordinate: Coordinate {
-                                row: i as i32,
-                                col: j as i32,
-                            },
+                            position: Position::new(j as i32, i as i32),
                         }),
                         _ => None,
                     })
@@ -94,71 +87,15 @@ impl Map {
             cols,
         }
     }
-    fn show(&self) {
-        for row in &self.tiles {
-            for tile in row {
-                match tile {
-                    Tile::Empty => print!("."),
-                    Tile::Antenna(f) => print!("{}", f),
-                    Tile::Antinode => print!("#"),
-                }
-                print!(" ");
-            }
-            println!();
-        }
+    fn is_inside(&self, coord: &Position) -> bool {
+        coord.y >= 0 && coord.y < self.rows as i32 && coord.x >= 0 && coord.x < self.cols as i32
     }
-    fn show_with_antinodes(&self) {
-        let antinodes = self
-            .coordinates_by_frequency()
-            .iter()
-            .flat_map(|(_freq, coord)| {
-                let rows = 0..self.rows as i32;
-                let cols = 0..self.cols as i32;
-                coord
-                    .iter()
-                    .tuple_combinations::<(_, _)>()
-                    .flat_map(move |(c1, c2)| {
-                        let a1 = c1.antinodes_within(c2, &rows, &cols);
-                        let a2 = c2.antinodes_within(c1, &rows, &cols);
-                        a1.iter().chain(a2.iter()).cloned().collect_vec()
-                    })
-            })
-            .collect::<HashSet<_>>();
-
-        for (i, row) in self.tiles.iter().enumerate() {
-            for (j, tile) in row.iter().enumerate() {
-                let coord = Coordinate {
-                    row: i as i32,
-                    col: j as i32,
-                };
-                match tile {
-                    Tile::Empty => {
-                        if antinodes.contains(&coord) {
-                            print!("#");
-                        } else {
-                            print!(".");
-                        }
-                    }
-                    Tile::Antenna(f) => print!("{}", f),
-                    Tile::Antinode => print!("#"),
-                }
-                print!(" ");
-            }
-            println!();
-        }
-    }
-    fn is_inside(&self, coord: &Coordinate) -> bool {
-        coord.row >= 0
-            && coord.row < self.rows as i32
-            && coord.col >= 0
-            && coord.col < self.cols as i32
-    }
-    fn coordinates_by_frequency(&self) -> HashMap<Frequency, Coordinates> {
+    fn positions_by_frequency(&self) -> HashMap<Frequency, Positions> {
         self.antennas
             .iter()
             .fold(HashMap::new(), |mut acc, antenna| {
                 let freq = antenna.freq;
-                let coord = antenna.coordinate;
+                let coord = antenna.position;
                 let entry = acc.entry(freq).or_default();
                 entry.insert(coord);
                 acc
@@ -166,6 +103,22 @@ impl Map {
     }
 }
 
+impl Display for Map {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        for row in &self.tiles {
+            for tile in row {
+                match tile {
+                    Tile::Empty => write!(f, ".")?,
+                    Tile::Antenna(freq) => write!(f, "{freq}")?,
+                    Tile::Antinode => write!(f, "#")?,
+                }
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
 fn parse_map(input: &str) -> IResult<&str, Map> {
     let (input, tiles) = separated_list1(
         newline,
@@ -183,7 +136,7 @@ fn parse_input(input: &str) -> Result<Map> {
     parse_map(input).map_and_finish()
 }
 
-struct Solver {}
+pub(crate) struct Solver {}
 
 impl ResourceReader for Solver {}
 
@@ -196,13 +149,17 @@ impl Task for Solver {
         Day::Day8
     }
 
+    fn status(&self) -> Status {
+        Status::Complete
+    }
+
     fn solve_part1(&self, input: &str) -> Result<String> {
         let map = parse_input(input)?;
-        // map.show();
+        trace!("\n{map}");
         let result = map
-            .coordinates_by_frequency()
-            .iter()
-            .flat_map(|(_freq, coord)| {
+            .positions_by_frequency()
+            .values()
+            .flat_map(|coord| {
                 coord
                     .iter()
                     .tuple_combinations::<(_, _)>()
@@ -221,9 +178,9 @@ impl Task for Solver {
     fn solve_part2(&self, input: &str) -> Result<String> {
         let map = parse_input(input)?;
         let result = map
-            .coordinates_by_frequency()
-            .iter()
-            .flat_map(|(_freq, coord)| {
+            .positions_by_frequency()
+            .values()
+            .flat_map(|coord| {
                 let rows = 0..map.rows as i32;
                 let cols = 0..map.cols as i32;
                 coord
@@ -281,22 +238,22 @@ mod test {
 
     #[rstest]
     fn antinode() -> Result<()> {
-        let c1 = Coordinate { row: 3, col: 4 };
-        let c2 = Coordinate { row: 5, col: 5 };
+        let c1 = Position::new(4, 3);
+        let c2 = Position::new(5, 5);
 
         let a = c1.antinode_for(&c2, 1);
-        assert_eq!(a, Coordinate { row: 1, col: 3 });
+        assert_eq!(a, Position::new(3, 1));
         let a = c1.antinode_for(&c2, 2);
-        assert_eq!(a, Coordinate { row: -1, col: 2 });
+        assert_eq!(a, Position::new(2, -1));
         let a = c1.antinode_for(&c2, 3);
-        assert_eq!(a, Coordinate { row: -3, col: 1 });
+        assert_eq!(a, Position::new(1, -3));
 
         let a = c2.antinode_for(&c1, 1);
-        assert_eq!(a, Coordinate { row: 7, col: 6 });
+        assert_eq!(a, Position::new(6, 7));
         let a = c2.antinode_for(&c1, 2);
-        assert_eq!(a, Coordinate { row: 9, col: 7 });
+        assert_eq!(a, Position::new(7, 9));
         let a = c2.antinode_for(&c1, 3);
-        assert_eq!(a, Coordinate { row: 11, col: 8 });
+        assert_eq!(a, Position::new(8, 11));
 
         Ok(())
     }
@@ -30,50 +30,18 @@ impl DiskMap {
     fn new(disk: Vec<Desc>) -> Self {
         Self { disk }
     }
-    fn show_vertical(&self) {
-        for desc in &self.disk {
-            match desc {
-                Desc::File(size, id) => println!("🟦 {}: {}", size, id),
-                Desc::Space(size) => println!("⬜️ {} ", size),
-            }
-        }
-    }
-    fn show_horizontal(&self) {
-        for desc in &self.disk {
-            match desc {
-                Desc::File(size, _) => {
-                    for _ in 0..*size {
-                        print!("🟦")
-                    }
-                }
-                Desc::Space(size) => {
-                    for _ in 0..*size {
-                        print!("⬜️")
-                    }
-                }
-            }
-        }
-        println!();
-    }
-
-    fn show_ids(&self) {
-        for desc in &self.disk {
-            match desc {
-                Desc::File(size, id) => {
-                    for _ in 0..*size {
-                        print!("{} ", id)
-                    }
-                }
-                Desc::Space(size) => {
-                    for _ in 0..*size {
-                        print!(". ")
-                    }
-                }
-            }
-        }
-        println!();
+    fn blocks(&self) -> Blocks {
+        let ids = self
+            .disk
+            .iter()
+            .flat_map(|desc| match *desc {
+                Desc::File(size, id) => vec![Some(id); size],
+                Desc::Space(size) => vec![None; size],
+            })
+            .collect_vec();
+        let width = ids.len().clamp(1, Blocks::MAX_WIDTH);
+        Blocks { ids, width }
     }
-
     fn checksum(&self) -> usize {
         let mut result = 0;
         let mut index = 0;
@@ -90,8 +58,11 @@ impl DiskMap {
         }
         result
     }
-    fn defrag_with_chunks(&mut self) {
-        while self.defrag_chunk() != DefragAction::Nothing {}
+    fn defrag_with_chunks(&mut self, frames: &mut Frames) {
+        frames.record_with(|| self.blocks());
+        while self.defrag_chunk() != DefragAction::Nothing {
+            frames.record_with(|| self.blocks());
+        }
     }
     fn defrag_chunk(&mut self) -> DefragAction {
         let index_space = self
@@ -117,7 +88,8 @@ impl DiskMap {
         }
         DefragAction::Nothing
     }
-    fn defrag_with_files(&mut self) {
+    fn defrag_with_files(&mut self, frames: &mut Frames) {
+        frames.record_with(|| self.blocks());
         let mut defragged_files = HashSet::new();
         loop {
             // Find the last file that isn't already marked as defragged
@@ -129,9 +101,9 @@ impl DiskMap {
             if let Some((rev_file_index, Desc::File(file_size, file_id))) = search_result {
                 let file_index = self.disk.len() - rev_file_index - 1;
                 defragged_files.insert(*file_id);
-                let action = self.defrag_file(file_index, *file_size);
-                // self.show_horizontal();
-                // self.show_ids();
+                if self.defrag_file(file_index, *file_size) != DefragAction::Nothing {
+                    frames.record_with(|| self.blocks());
+                }
             } else {
                 break;
             }
@@ -180,6 +152,32 @@ impl DiskMap {
     }
 }
 
+/// The disk block by block, wrapped into rows; files show the last digit of their id.
+struct Blocks {
+    ids: Vec<Option<Id>>,
+    width: usize,
+}
+
+impl Blocks {
+    const MAX_WIDTH: usize = 100;
+}
+
+impl Render for Blocks {
+    fn size(&self) -> (usize, usize) {
+        (self.width, self.ids.len().div_ceil(self.width))
+    }
+    fn cell(&self, x: usize, y: usize) -> Cell {
+        match self.ids.get(y * self.width + x) {
+            Some(Some(id)) => {
+                let glyph = char::from_digit((id % 10) as u32, 10).expect("Single digit");
+                Cell::new(glyph, Color::cycle(*id))
+            }
+            Some(None) => Cell::new('.', Color::DARK_GREY),
+            None => Cell::new(' ', Color::BLACK),
+        }
+    }
+}
+
 fn parse_disk_map(i: &str) -> IResult<&str, DiskMap> {
     let (i, sizes) = many1(map_opt(one_of("0123456789"), |c| c.to_digit(10))).parse(i)?;
     let disk = sizes
@@ -201,7 +199,7 @@ fn parse_input(input: &str) -> Result<DiskMap> {
     parse_disk_map(input).map_and_finish()
 }
 
-struct Solver {}
+pub(crate) struct Solver {}
 
 impl ResourceReader for Solver {}
 
@@ -214,15 +212,24 @@ impl Task for Solver {
         Day::Day9
     }
 
+    fn status(&self) -> Status {
+        Status::Complete
+    }
+
     fn solve_part1(&self, input: &str) -> Result<String> {
-        let mut diskmap = parse_input(input)?;
-        diskmap.defrag_with_chunks();
-        Ok(diskmap.checksum().to_string())
+        self.solve_with_frames(Part::Part1, input, &mut Frames::disabled())
     }
 
     fn solve_part2(&self, input: &str) -> Result<String> {
+        self.solve_with_frames(Part::Part2, input, &mut Frames::disabled())
+    }
+
+    fn solve_with_frames(&self, part: Part, input: &str, frames: &mut Frames) -> Result<String> {
         let mut diskmap = parse_input(input)?;
-        diskmap.defrag_with_files();
+        match part {
+            Part::Part1 => diskmap.defrag_with_chunks(frames),
+            Part::Part2 => diskmap.defrag_with_files(frames),
+        }
         Ok(diskmap.checksum().to_string())
     }
 }
@@ -230,6 +237,7 @@ impl Task for Solver {
 #[cfg(test)]
 mod test {
     use super::*;
+    use aoc_common::render::to_text;
     use rstest::*;
 
     #[fixture]
@@ -251,6 +259,23 @@ mod test {
         Ok(())
     }
 
+    #[rstest]
+    fn example2_frames(solver: Solver) -> Result<()> {
+        let input = solver.read_resource(Input::Example2)?;
+        let mut frames = Frames::new();
+        solver.solve_with_frames(Part::Part2, &input, &mut frames)?;
+        let texts = frames.frames().iter().map(to_text).collect_vec();
+        assert_eq!(
+            texts.first().map(String::as_str),
+            Some("00...111...2...333.44.5555.6666.777.888899\n")
+        );
+        assert_eq!(
+            texts.last().map(String::as_str),
+            Some("00992111777.44.333....5555.6666.....8888..\n")
+        );
+        Ok(())
+    }
+
     #[rstest]
     fn part1(solver: Solver) -> Result<()> {
         let input = solver.read_resource(Input::Part1)?;
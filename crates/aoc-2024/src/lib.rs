@@ -1,5 +1,7 @@
 #![allow(unused)]
 
+use aoc_common::prelude::*;
+
 mod day1;
 mod day10;
 mod day11;
@@ -25,3 +27,37 @@ mod day6;
 mod day7;
 mod day8;
 mod day9;
+
+pub fn solvers() -> Vec<Box<dyn Task>> {
+    vec![
+        Box::new(day1::Solver {}),
+        Box::new(day2::Solver {}),
+        Box::new(day3::Solver {}),
+        Box::new(day4::Solver {}),
+        Box::new(day5::Solver {}),
+        Box::new(day6::Solver {}),
+        Box::new(day7::Solver {}),
+        Box::new(day8::Solver {}),
+        Box::new(day9::Solver {}),
+        Box::new(day10::Solver {}),
+        Box::new(day11::Solver {}),
+        Box::new(day12::Solver {}),
+        Box::new(day13::Solver {}),
+        Box::new(day14::Solver {
+            arena_size: (101, 103),
+        }),
+        Box::new(day15::Solver {}),
+        Box::new(day16::Solver {}),
+        Box::new(day17::Solver {
+            kind: day17::SolverKind::Large,
+        }),
+        Box::new(day18::Solver::new(day18::MemorySize::Large)),
+        Box::new(day19::Solver {}),
+        Box::new(day20::Solver {}),
+        Box::new(day21::Solver {}),
+        Box::new(day22::Solver {}),
+        Box::new(day23::Solver {}),
+        Box::new(day24::Solver {}),
+        Box::new(day25::Solver {}),
+    ]
+}
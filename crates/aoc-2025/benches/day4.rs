@@ -0,0 +1,65 @@
+//! 2025 day 4 part 2 on the real input: the solver's `BitGrid` floor against the `Grid<Tile>`
+//! floor it replaced, which counted each roll's neighbours one tile at a time.
+
+use aoc_common::grid::Grid;
+use aoc_common::prelude::*;
+use aoc_data::prelude::*;
+use criterion::{Criterion, criterion_group, criterion_main};
+use std::hint::black_box;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Tile {
+    Empty,
+    Roll,
+}
+
+/// Rolls removed until none can be reached, as the solver worked before `BitGrid`.
+fn tile_grid(input: &str) -> usize {
+    let mut tiles = Grid::parse(input.trim(), |c| match c {
+        '.' => Some(Tile::Empty),
+        '@' => Some(Tile::Roll),
+        _ => None,
+    })
+    .expect("Valid floor");
+    let mut removed = 0;
+    loop {
+        let removable = tiles
+            .positions(|tile| *tile == Tile::Roll)
+            .filter(|&pos| {
+                let rolls = tiles
+                    .neighbours8(pos)
+                    .filter(|&n| tiles[n] == Tile::Roll)
+                    .count();
+                rolls < 4
+            })
+            .collect_vec();
+        if removable.is_empty() {
+            return removed;
+        }
+        removed += removable.len();
+        for pos in removable {
+            tiles[pos] = Tile::Empty;
+        }
+    }
+}
+
+fn removal(c: &mut Criterion) {
+    let input = read_resource(Event::Event2025, Day::Day4, Input::Part2).expect("Input");
+    let solvers = aoc_2025::solvers();
+    let solver = solvers
+        .iter()
+        .find(|s| s.day() == Day::Day4)
+        .expect("Day 4 solver");
+    let expected = solver.solve_part2(&input).expect("Answer");
+    assert_eq!(tile_grid(&input).to_string(), expected);
+
+    let mut group = c.benchmark_group("2025 day 4 part 2");
+    group.bench_function("tile_grid", |b| b.iter(|| tile_grid(black_box(&input))));
+    group.bench_function("bitgrid", |b| {
+        b.iter(|| solver.solve_part2(black_box(&input)))
+    });
+    group.finish();
+}
+
+criterion_group!(benches, removal);
+criterion_main!(benches);
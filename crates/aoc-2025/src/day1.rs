@@ -47,11 +47,8 @@ impl Dial {
     }
     fn apply_strategy(&mut self, rotation: &Rotation) {
         match self.strategy {
-            Some(ClickStrategy::EndsOnZero) => {
-                if self.position == 0 {
-                    self.clicks += 1
-                }
-            }
+            Some(ClickStrategy::EndsOnZero) if self.position == 0 => self.clicks += 1,
+            Some(ClickStrategy::EndsOnZero) => {}
             Some(ClickStrategy::PassesZero) => {
                 let dist_to_0 = match rotation {
                     Rotation::Left(_) if self.position > 0 => self.position,
@@ -102,7 +99,7 @@ fn parse_input(i: &str) -> Result<Vec<Rotation>> {
     many1(parse_rotation).parse(i).map_and_finish()
 }
 
-struct Solver {}
+pub(crate) struct Solver {}
 
 impl ResourceReader for Solver {}
 
@@ -115,6 +112,10 @@ impl Task for Solver {
         Day::Day1
     }
 
+    fn status(&self) -> Status {
+        Status::Complete
+    }
+
     fn solve_part1(&self, input: &str) -> Result<String> {
         let rotations = parse_input(input.trim())?;
         let dial = Dial::new(100, 50)
@@ -66,7 +66,7 @@ fn parse_input(i: &str) -> Result<Vec<Range>> {
         .map_and_finish()
 }
 
-struct Solver {}
+pub(crate) struct Solver {}
 
 impl ResourceReader for Solver {}
 
@@ -79,6 +79,10 @@ impl Task for Solver {
         Day::Day2
     }
 
+    fn status(&self) -> Status {
+        Status::Complete
+    }
+
     fn solve_part1(&self, input: &str) -> Result<String> {
         let ranges = parse_input(input.trim())?;
         let sum: u64 = ranges
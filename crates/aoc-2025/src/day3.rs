@@ -63,7 +63,7 @@ fn parse_input(input: &str) -> Result<Vec<Bank>> {
     many1(parse_bank).parse(input).map_and_finish()
 }
 
-struct Solver {}
+pub(crate) struct Solver {}
 
 impl ResourceReader for Solver {}
 
@@ -76,6 +76,10 @@ impl Task for Solver {
         Day::Day3
     }
 
+    fn status(&self) -> Status {
+        Status::Complete
+    }
+
     fn solve_part1(&self, input: &str) -> Result<String> {
         let banks = parse_input(input.trim())?;
         let sum: u64 = banks.iter().map(|bank| bank.joltage().unwrap_or(0)).sum();
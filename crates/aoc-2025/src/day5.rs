@@ -1,3 +1,4 @@
+use aoc_common::interval::IntervalSet;
 use aoc_common::prelude::*;
 use aoc_data::prelude::*;
 use nom::bytes::complete::tag;
@@ -11,66 +12,23 @@ type IngredientID = u64;
 type Range = (IngredientID, IngredientID);
 
 struct Inventory {
-    fresh: Vec<Range>,
+    fresh: IntervalSet<IngredientID>,
     available: Vec<IngredientID>,
 }
 
-fn merge(lhs: &Range, rhs: &Range) -> Option<Range> {
-    if lhs.0 <= rhs.1 + 1 && rhs.0 <= lhs.1 + 1 {
-        Some((std::cmp::min(lhs.0, rhs.0), std::cmp::max(lhs.1, rhs.1)))
-    } else {
-        None
-    }
-}
-
-fn reduce(ranges: &[Range]) -> Vec<Range> {
-    let mut sorted = ranges.to_vec();
-    sorted.sort_by_key(|r| r.0);
-    let mut reduced: Vec<Range> = Vec::new();
-
-    for range in sorted {
-        if let Some(last) = reduced.last_mut()
-            && let Some(merged) = merge(last, &range)
-        {
-            *last = merged;
-            continue;
-        }
-        reduced.push(range);
-    }
-
-    reduced
-}
-
-fn full_reduce(ranges: &[Range]) -> Vec<Range> {
-    let mut current = ranges.to_vec();
-    loop {
-        let reduced = reduce(&current);
-        if reduced.len() == current.len() {
-            return reduced;
-        }
-        current = reduced;
-    }
-}
-
 impl Inventory {
     fn new(fresh: Vec<Range>, available: Vec<IngredientID>) -> Inventory {
+        let fresh = fresh.into_iter().map(|(start, end)| start..=end).collect();
         Inventory { fresh, available }
     }
     fn num_fresh(&self) -> usize {
         self.available
             .iter()
-            .filter(|id| {
-                self.fresh
-                    .iter()
-                    .any(|(start, end)| *id >= start && *id <= end)
-            })
+            .filter(|&&id| self.fresh.contains(id))
             .count()
     }
     fn num_considered_fresh(&self) -> usize {
-        let reduced_fresh = full_reduce(&self.fresh);
-        reduced_fresh
-            .iter()
-            .fold(0, |acc, (start, end)| acc + (end - start + 1)) as usize
+        self.fresh.len() as usize
     }
 }
 
@@ -98,7 +56,7 @@ fn parse_input(input: &str) -> Result<Inventory> {
     parse_inventory.parse(input).map_and_finish()
 }
 
-struct Solver {}
+pub(crate) struct Solver {}
 
 impl ResourceReader for Solver {}
 
@@ -111,6 +69,10 @@ impl Task for Solver {
         Day::Day5
     }
 
+    fn status(&self) -> Status {
+        Status::Complete
+    }
+
     fn solve_part1(&self, input: &str) -> Result<String> {
         let inventory = parse_input(input.trim())?;
         Ok(inventory.num_fresh().to_string())
@@ -172,7 +172,7 @@ fn parse_input(input: &str) -> Result<(Homework, Offsets)> {
     ))
 }
 
-struct Solver {}
+pub(crate) struct Solver {}
 
 impl ResourceReader for Solver {}
 
@@ -185,6 +185,10 @@ impl Task for Solver {
         Day::Day6
     }
 
+    fn status(&self) -> Status {
+        Status::Complete
+    }
+
     fn solve_part1(&self, input: &str) -> Result<String> {
         let (homework, _) = parse_input(input.trim())?;
         Ok(homework.grand_total().to_string())
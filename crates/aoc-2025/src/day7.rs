@@ -1,10 +1,6 @@
+use aoc_common::grid::Grid;
 use aoc_common::prelude::*;
 use aoc_data::prelude::*;
-use nom::branch::alt;
-use nom::character::complete::{char, newline};
-use nom::combinator::{map, opt};
-use nom::multi::many1;
-use nom::sequence::terminated;
 use std::collections::HashSet;
 
 enum Tile {
@@ -14,15 +10,16 @@ enum Tile {
 }
 
 struct Manifold {
-    grid: Vec<Vec<Tile>>,
+    grid: Grid<Tile>,
 }
 
 impl Manifold {
-    fn new(grid: Vec<Vec<Tile>>) -> Self {
+    fn new(grid: Grid<Tile>) -> Self {
         Self { grid }
     }
     fn start_column(&self) -> usize {
-        self.grid[0]
+        self.grid
+            .row(0)
             .iter()
             .position(|tile| matches!(tile, Tile::Entrance))
             .expect("no entrance found")
@@ -32,7 +29,7 @@ impl Manifold {
         let mut splits = 0;
 
         beams.insert(self.start_column());
-        for row in &self.grid {
+        for row in self.grid.rows() {
             let mut new_beams = HashSet::new();
             for &col in &beams {
                 match &row[col] {
@@ -56,10 +53,10 @@ impl Manifold {
         splits
     }
     fn num_timelines(&self) -> usize {
-        let mut splits = vec![0; self.grid[0].len()];
+        let mut splits = vec![0; self.grid.width()];
 
-        for row in &self.grid {
-            let mut new_splits = vec![0; self.grid[0].len()];
+        for row in self.grid.rows() {
+            let mut new_splits = vec![0; self.grid.width()];
             for (col, &split) in splits.iter().enumerate() {
                 match &row[col] {
                     Tile::Empty => {
@@ -85,26 +82,17 @@ impl Manifold {
     }
 }
 
-fn parse_manifold(i: &str) -> IResult<&str, Manifold> {
-    map(
-        many1(terminated(
-            many1(alt((
-                map(char('.'), |_| Tile::Empty),
-                map(char('^'), |_| Tile::Splitter),
-                map(char('S'), |_| Tile::Entrance),
-            ))),
-            opt(newline),
-        )),
-        Manifold::new,
-    )
-    .parse(i)
-}
-
 fn parse_input(input: &str) -> Result<Manifold> {
-    parse_manifold(input).map_and_finish()
+    let grid = Grid::parse(input.trim(), |c| match c {
+        '.' => Some(Tile::Empty),
+        '^' => Some(Tile::Splitter),
+        'S' => Some(Tile::Entrance),
+        _ => None,
+    })?;
+    Ok(Manifold::new(grid))
 }
 
-struct Solver {}
+pub(crate) struct Solver {}
 
 impl ResourceReader for Solver {}
 
@@ -117,6 +105,10 @@ impl Task for Solver {
         Day::Day7
     }
 
+    fn status(&self) -> Status {
+        Status::Complete
+    }
+
     fn solve_part1(&self, input: &str) -> Result<String> {
         let manifold = parse_input(input)?;
         Ok(manifold.num_splits().to_string())
@@ -1,5 +1,7 @@
 #![allow(unused)]
 
+use aoc_common::prelude::*;
+
 mod day1;
 mod day10;
 mod day11;
@@ -12,3 +14,20 @@ mod day6;
 mod day7;
 mod day8;
 mod day9;
+
+pub fn solvers() -> Vec<Box<dyn Task>> {
+    vec![
+        Box::new(day1::Solver {}),
+        Box::new(day2::Solver {}),
+        Box::new(day3::Solver {}),
+        Box::new(day4::Solver {}),
+        Box::new(day5::Solver {}),
+        Box::new(day6::Solver {}),
+        Box::new(day7::Solver {}),
+        Box::new(day8::Solver {}),
+        Box::new(day9::Solver {}),
+        Box::new(day10::Solver {}),
+        Box::new(day11::Solver {}),
+        Box::new(day12::Solver {}),
+    ]
+}
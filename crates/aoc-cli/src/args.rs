@@ -0,0 +1,74 @@
+use aoc_common::prelude::*;
+
+pub fn parse_event(s: &str) -> std::result::Result<Event, String> {
+    s.parse()
+        .ok()
+        .and_then(Event::from_year)
+        .ok_or_else(|| format!("unknown event: {s}"))
+}
+
+pub fn parse_day(s: &str) -> std::result::Result<Day, String> {
+    s.parse()
+        .ok()
+        .and_then(Day::from_number)
+        .ok_or_else(|| format!("unknown day: {s}"))
+}
+
+pub fn parse_part(s: &str) -> std::result::Result<Part, String> {
+    s.parse()
+        .ok()
+        .and_then(Part::from_number)
+        .ok_or_else(|| format!("unknown part: {s}"))
+}
+
+pub fn parse_input(s: &str) -> std::result::Result<Input, String> {
+    Input::from_name(s).ok_or_else(|| format!("unknown input: {s}"))
+}
+
+/// A single day as `YEAR/DAY`, e.g. `2024/16`.
+pub fn parse_event_day(s: &str) -> std::result::Result<(Event, Day), String> {
+    let (event, day) = s
+        .split_once('/')
+        .ok_or_else(|| format!("expected YEAR/DAY: {s}"))?;
+    Ok((parse_event(event)?, parse_day(day)?))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use rstest::*;
+
+    #[rstest]
+    fn parses_known_values() {
+        assert_eq!(parse_event("2024"), Ok(Event::Event2024));
+        assert_eq!(parse_day("16"), Ok(Day::Day16));
+        assert_eq!(parse_part("2"), Ok(Part::Part2));
+        assert_eq!(parse_input("example1"), Ok(Input::Example1));
+        assert_eq!(
+            parse_event_day("2024/16"),
+            Ok((Event::Event2024, Day::Day16))
+        );
+    }
+
+    #[rstest]
+    #[case("2014")]
+    #[case("twenty")]
+    fn rejects_unknown_event(#[case] s: &str) {
+        assert!(parse_event(s).is_err());
+    }
+
+    #[rstest]
+    #[case("0")]
+    #[case("26")]
+    fn rejects_unknown_day(#[case] s: &str) {
+        assert!(parse_day(s).is_err());
+    }
+
+    #[rstest]
+    #[case("2024")]
+    #[case("2024/26")]
+    #[case("2014/1")]
+    fn rejects_unknown_event_day(#[case] s: &str) {
+        assert!(parse_event_day(s).is_err());
+    }
+}
@@ -0,0 +1,62 @@
+use aoc_common::prelude::*;
+use tracing_subscriber::EnvFilter;
+
+/// Filter directives for the verbosity flags.
+///
+/// `--trace-day` limits the output to events inside the `solve` span of that day, at `trace`
+/// level unless `-v` asks for less. That includes events from the shared helpers the solver
+/// calls, since a span directive can't also filter on the events' crate.
+fn directives(verbose: u8, trace_day: Option<(Event, Day)>) -> String {
+    let level = match (verbose, trace_day) {
+        (0, None) => return "warn".to_string(),
+        (0 | 2.., _) => "trace",
+        (1, _) => "debug",
+    };
+    match trace_day {
+        Some((event, day)) => format!(
+            "warn,[solve{{puzzle={}/{}}}]={level}",
+            event.year(),
+            day.number()
+        ),
+        None => level.to_string(),
+    }
+}
+
+/// Install a stderr subscriber; `RUST_LOG` applies when no flag is given.
+pub fn init(verbose: u8, trace_day: Option<(Event, Day)>) -> Result<()> {
+    let filter = match EnvFilter::try_from_default_env() {
+        Ok(filter) if verbose == 0 && trace_day.is_none() => filter,
+        _ => EnvFilter::try_new(directives(verbose, trace_day))
+            .map_err(|e| AdventError::Other(e.to_string()))?,
+    };
+    tracing_subscriber::fmt()
+        .with_env_filter(filter)
+        .with_writer(std::io::stderr)
+        .without_time()
+        .try_init()
+        .map_err(|e| AdventError::Other(e.to_string()))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use rstest::*;
+
+    #[rstest]
+    #[case(0, "warn")]
+    #[case(1, "debug")]
+    #[case(2, "trace")]
+    #[case(5, "trace")]
+    fn verbosity_levels(#[case] verbose: u8, #[case] expected: &str) {
+        assert_eq!(directives(verbose, None), expected);
+    }
+
+    #[rstest]
+    #[case(0, "warn,[solve{puzzle=2024/15}]=trace")]
+    #[case(1, "warn,[solve{puzzle=2024/15}]=debug")]
+    fn single_day(#[case] verbose: u8, #[case] expected: &str) {
+        let trace_day = Some((Event::Event2024, Day::Day15));
+        assert_eq!(directives(verbose, trace_day), expected);
+        assert!(EnvFilter::try_new(expected).is_ok());
+    }
+}
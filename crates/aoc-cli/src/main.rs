@@ -1,3 +1,176 @@
-fn main() {
-    println!("Hello, world!");
+mod args;
+mod logging;
+mod play;
+mod readme;
+mod run;
+mod serve;
+mod status;
+mod watch;
+
+use crate::args::{parse_day, parse_event, parse_event_day, parse_input, parse_part};
+use aoc_common::prelude::*;
+use aoc_runner::prelude::*;
+use clap::{ArgAction, Parser, Subcommand};
+use std::path::PathBuf;
+use std::time::Duration;
+
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+const DEFAULT_README: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/../../README.md");
+
+#[derive(Parser)]
+#[command(name = "aoc", about = "Advent of Code runner")]
+struct Cli {
+    /// Log solver diagnostics to stderr: -v for debug, -vv for trace.
+    #[arg(short, long, action = ArgAction::Count, global = true)]
+    verbose: u8,
+    /// Only log diagnostics from one day, given as YEAR/DAY.
+    #[arg(long, value_parser = parse_event_day, global = true)]
+    trace_day: Option<(Event, Day)>,
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Report solver completion per year.
+    Status {
+        /// Regenerate the progress sections of the README.
+        #[arg(long)]
+        update_readme: bool,
+        /// README to regenerate.
+        #[arg(long, default_value = DEFAULT_README)]
+        readme: PathBuf,
+    },
+    /// Run solvers and report answers, timings and allocations.
+    Run {
+        /// Event year, e.g. 2024.
+        #[arg(value_parser = parse_event)]
+        event: Event,
+        /// Day number; every day of the event when omitted.
+        #[arg(value_parser = parse_day)]
+        day: Option<Day>,
+        /// Part number; both parts when omitted.
+        #[arg(long, value_parser = parse_part)]
+        part: Option<Part>,
+        /// Bundled input to run against (example1, example2, part1, part2).
+        #[arg(long, value_parser = parse_input)]
+        input: Option<Input>,
+        /// Run every bundled input, examples included, against its own part.
+        #[arg(long, conflicts_with = "input")]
+        all_inputs: bool,
+        /// Number of timed repetitions per part.
+        #[arg(long, default_value_t = 1)]
+        repeat: u32,
+        /// Report format (markdown, json or csv).
+        #[arg(long, default_value = "markdown")]
+        format: ReportFormat,
+        /// Write the report to a file instead of stdout.
+        #[arg(long)]
+        output: Option<PathBuf>,
+        /// Draw the run to a file: .gif animates, .png/.ppm keep the last frame, .txt/.ans
+        /// hold every frame as text.
+        #[arg(long)]
+        render: Option<PathBuf>,
+        /// Pixels per cell in rendered images.
+        #[arg(long, default_value_t = 4, requires = "render")]
+        scale: usize,
+        /// Only keep every nth rendered frame.
+        #[arg(long, default_value_t = 1, requires = "render")]
+        render_every: usize,
+    },
+    /// Step through a simulation in the terminal.
+    Play {
+        /// Event year, e.g. 2024.
+        #[arg(value_parser = parse_event)]
+        event: Event,
+        /// Day number.
+        #[arg(value_parser = parse_day)]
+        day: Day,
+        /// Part number.
+        #[arg(long, value_parser = parse_part, default_value = "1")]
+        part: Part,
+        /// Bundled input to play (example1, example2, part1, part2); the part's own by default.
+        #[arg(long, value_parser = parse_input)]
+        input: Option<Input>,
+        /// Steps per second while playing.
+        #[arg(long, default_value_t = 10)]
+        speed: u32,
+    },
+    /// Serve a local HTTP/JSON API for running solvers.
+    Serve {
+        /// Port to listen on; the server only binds to localhost.
+        #[arg(long, default_value_t = 8025)]
+        port: u16,
+    },
+    /// Re-run a day whenever its source or inputs change.
+    Watch {
+        /// Event year, e.g. 2024.
+        #[arg(value_parser = parse_event)]
+        event: Event,
+        /// Day number.
+        #[arg(value_parser = parse_day)]
+        day: Day,
+        /// Polling interval in milliseconds.
+        #[arg(long, default_value_t = 500)]
+        interval: u64,
+    },
+}
+
+fn main() -> Result<()> {
+    let cli = Cli::parse();
+    logging::init(cli.verbose, cli.trace_day)?;
+    match cli.command {
+        Command::Status {
+            update_readme,
+            readme,
+        } => status::run(update_readme.then_some(readme.as_path())),
+        Command::Run {
+            event,
+            day,
+            part,
+            input,
+            all_inputs,
+            repeat,
+            format,
+            output,
+            render,
+            scale,
+            render_every,
+        } => {
+            let selection = run::Selection {
+                event,
+                day,
+                part,
+                input,
+                all_inputs,
+            };
+            let render = render.map(|path| run::RenderOptions {
+                path,
+                scale,
+                every: render_every,
+            });
+            run::run(selection, repeat, format, output.as_deref(), render)
+        }
+        Command::Play {
+            event,
+            day,
+            part,
+            input,
+            speed,
+        } => {
+            let input = input.unwrap_or(match part {
+                Part::Part1 => Input::Part1,
+                Part::Part2 => Input::Part2,
+            });
+            play::run(event, day, part, input, speed)
+        }
+        Command::Serve { port } => serve::run(port),
+        Command::Watch {
+            event,
+            day,
+            interval,
+        } => watch::run(event, day, Duration::from_millis(interval)),
+    }
 }
@@ -0,0 +1,430 @@
+use aoc_common::prelude::*;
+use aoc_data::prelude::*;
+use aoc_runner::prelude::*;
+use ratatui::crossterm::event::{self, Event as TermEvent, KeyCode, KeyEventKind};
+use ratatui::layout::{Constraint, Layout};
+use ratatui::style::{Color as TermColor, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Paragraph};
+use ratatui::{DefaultTerminal, Frame as TermFrame};
+use std::collections::VecDeque;
+use std::time::{Duration, Instant};
+
+const MAX_SPEED: u32 = 1024;
+/// Frames kept for stepping back without re-running the simulation.
+const HISTORY: usize = 1024;
+/// New steps `End` runs at most, for simulations that take a long time to finish.
+const END_STEPS: usize = 100_000;
+
+/// Playback over a simulation.
+///
+/// Only the latest frames are kept. Going back further restarts the simulation and runs it up
+/// to the step wanted, which relies on the simulation doing the same thing every time.
+struct Player<'a> {
+    restart: Box<dyn Fn() -> Box<dyn Simulation> + 'a>,
+    simulation: Box<dyn Simulation>,
+    /// Frames for the steps up to `latest`, oldest first.
+    history: VecDeque<(Frame, String)>,
+    capacity: usize,
+    /// The step the simulation has reached.
+    latest: usize,
+    position: usize,
+    paused: bool,
+    /// Steps per second while playing.
+    speed: u32,
+    /// The step number being typed, while seeking.
+    seek: Option<String>,
+}
+
+impl<'a> Player<'a> {
+    fn new(restart: impl Fn() -> Box<dyn Simulation> + 'a) -> Self {
+        let simulation = restart();
+        let history = VecDeque::from([(simulation.render(), simulation.status())]);
+        Self {
+            restart: Box::new(restart),
+            simulation,
+            history,
+            capacity: HISTORY,
+            latest: 0,
+            position: 0,
+            paused: true,
+            speed: 10,
+            seek: None,
+        }
+    }
+    fn with_speed(mut self, speed: u32) -> Self {
+        self.speed = speed.clamp(1, MAX_SPEED);
+        self
+    }
+    /// The earliest step still in the history.
+    fn first(&self) -> usize {
+        self.latest + 1 - self.history.len()
+    }
+    fn current(&self) -> &(Frame, String) {
+        &self.history[self.position - self.first()]
+    }
+    fn at_end(&self) -> bool {
+        self.position == self.latest && self.simulation.is_done()
+    }
+    /// Run the simulation one step, forgetting the oldest frame once the history is full.
+    fn advance(&mut self) {
+        self.simulation.step();
+        let (frame, status) = (self.simulation.render(), self.simulation.status());
+        self.history.push_back((frame, status));
+        if self.history.len() > self.capacity {
+            self.history.pop_front();
+        }
+        self.latest += 1;
+    }
+    /// Restart the simulation and run it until `step` is in the history, with room to keep
+    /// stepping back from there.
+    fn rewind(&mut self, step: usize) {
+        let end = (step + self.capacity / 2).min(self.latest);
+        self.simulation = (self.restart)();
+        let (frame, status) = (self.simulation.render(), self.simulation.status());
+        self.history = VecDeque::from([(frame, status)]);
+        self.latest = 0;
+        while self.latest < end && !self.simulation.is_done() {
+            self.advance();
+        }
+        self.position = step.min(self.latest);
+    }
+    /// Move one step on, running the simulation if this step hasn't been seen yet.
+    fn forward(&mut self) -> bool {
+        if self.position == self.latest {
+            if self.simulation.is_done() {
+                return false;
+            }
+            self.advance();
+        }
+        self.position += 1;
+        true
+    }
+    fn back(&mut self) -> bool {
+        if self.position == 0 {
+            return false;
+        }
+        if self.position == self.first() {
+            self.rewind(self.position - 1);
+        } else {
+            self.position -= 1;
+        }
+        true
+    }
+    /// Go to a step, or as close to it as the simulation gets.
+    fn seek(&mut self, step: usize) {
+        if step < self.first() {
+            self.rewind(step);
+        }
+        self.position = step.min(self.latest).max(self.first());
+        while self.position < step && self.forward() {}
+    }
+    /// Advance while playing, pausing at the end.
+    fn tick(&mut self) {
+        if !self.paused && !self.forward() {
+            self.paused = true;
+        }
+    }
+    /// Handle a key press; false means quit.
+    fn handle(&mut self, key: KeyCode) -> bool {
+        if let Some(seek) = &mut self.seek {
+            match key {
+                KeyCode::Char(c) if c.is_ascii_digit() => seek.push(c),
+                KeyCode::Backspace => _ = seek.pop(),
+                KeyCode::Enter => {
+                    let step = seek.parse().ok();
+                    self.seek = None;
+                    if let Some(step) = step {
+                        self.seek(step);
+                    }
+                }
+                KeyCode::Esc => self.seek = None,
+                _ => {}
+            }
+            return true;
+        }
+        match key {
+            KeyCode::Char('q') | KeyCode::Esc => return false,
+            KeyCode::Char(' ') => self.paused = !self.paused || self.at_end(),
+            KeyCode::Right | KeyCode::Char('n') => {
+                self.paused = true;
+                self.forward();
+            }
+            KeyCode::Left | KeyCode::Char('p') => {
+                self.paused = true;
+                self.back();
+            }
+            KeyCode::Up | KeyCode::Char('+') => self.speed = (self.speed * 2).min(MAX_SPEED),
+            KeyCode::Down | KeyCode::Char('-') => self.speed = (self.speed / 2).max(1),
+            KeyCode::Home => self.seek(0),
+            KeyCode::End => self.seek(self.latest + END_STEPS),
+            KeyCode::Char('g') => {
+                self.paused = true;
+                self.seek = Some(String::new());
+            }
+            _ => {}
+        }
+        true
+    }
+    fn status_lines(&self) -> Vec<String> {
+        let (_, status) = self.current();
+        let state = match (self.at_end(), self.paused) {
+            (true, _) => "done",
+            (false, true) => "paused",
+            (false, false) => "playing",
+        };
+        let mut lines = vec![
+            format!(
+                "step {}/{}  {state}  {} steps/s",
+                self.position, self.latest, self.speed
+            ),
+            status.clone(),
+        ];
+        lines.push(match &self.seek {
+            Some(seek) => format!("go to step: {seek}_  (enter to go, esc to cancel)"),
+            None => {
+                "space play/pause  ←/→ step  ↑/↓ speed  g go to step  home/end  q quit".to_string()
+            }
+        });
+        lines
+    }
+}
+
+fn to_term_color(color: Color) -> TermColor {
+    TermColor::Rgb(color.r, color.g, color.b)
+}
+
+/// One line per row, with runs of the same colour sharing a span.
+fn to_lines(frame: &Frame) -> Vec<Line<'static>> {
+    let (width, height) = frame.size();
+    (0..height)
+        .map(|y| {
+            let cells = (0..width).map(|x| frame.cell(x, y)).collect_vec();
+            let spans = cells
+                .chunk_by(|a, b| a.color == b.color)
+                .map(|run| {
+                    let text = run.iter().map(|c| c.glyph).collect::<String>();
+                    Span::styled(text, Style::new().fg(to_term_color(run[0].color)))
+                })
+                .collect_vec();
+            Line::from(spans)
+        })
+        .collect()
+}
+
+fn draw(term: &mut TermFrame, player: &Player<'_>, title: &str) {
+    let [board, status] =
+        Layout::vertical([Constraint::Min(1), Constraint::Length(5)]).areas(term.area());
+    let (frame, _) = player.current();
+    term.render_widget(Paragraph::new(to_lines(frame)), board);
+    let lines = player
+        .status_lines()
+        .into_iter()
+        .map(Line::from)
+        .collect_vec();
+    let block = Block::bordered().title(format!(" {title} "));
+    term.render_widget(Paragraph::new(lines).block(block), status);
+}
+
+fn play(terminal: &mut DefaultTerminal, player: &mut Player<'_>, title: &str) -> Result<()> {
+    let mut last_step = Instant::now();
+    loop {
+        terminal.draw(|term| draw(term, player, title))?;
+        let interval = Duration::from_secs(1) / player.speed;
+        let timeout = match player.paused {
+            true => Duration::from_secs(1),
+            false => interval.saturating_sub(last_step.elapsed()),
+        };
+        if event::poll(timeout)?
+            && let TermEvent::Key(key) = event::read()?
+            && key.kind == KeyEventKind::Press
+            && !player.handle(key.code)
+        {
+            return Ok(());
+        }
+        if !player.paused && last_step.elapsed() >= interval {
+            player.tick();
+            last_step = Instant::now();
+        }
+    }
+}
+
+pub fn run(event: Event, day: Day, part: Part, input: Input, speed: u32) -> Result<()> {
+    let registry = Registry::new();
+    let solver = registry
+        .get(event, day)
+        .ok_or_else(|| AdventError::InvalidInput("No solver for selection".to_string()))?;
+    let text = read_resource(event, day, input)?;
+    let title = format!(
+        "{} day {} part {} ({})",
+        event.year(),
+        day.number(),
+        part.number(),
+        input.name()
+    );
+    if solver.simulation(part, &text)?.is_none() {
+        return Err(AdventError::InvalidInput(format!(
+            "{title} is not a simulation"
+        )));
+    }
+
+    let restart = || {
+        solver
+            .simulation(part, &text)
+            .ok()
+            .flatten()
+            .expect("The simulation started once already")
+    };
+    let mut player = Player::new(restart).with_speed(speed);
+    let mut terminal = ratatui::try_init()?;
+    let result = play(&mut terminal, &mut player, &title);
+    ratatui::try_restore()?;
+    result
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use ratatui::Terminal;
+    use ratatui::backend::TestBackend;
+    use rstest::*;
+
+    /// Counts up to a limit, drawing the count as a single digit.
+    struct Counter {
+        count: usize,
+        limit: usize,
+    }
+
+    impl Render for Counter {
+        fn size(&self) -> (usize, usize) {
+            (1, 1)
+        }
+        fn cell(&self, _x: usize, _y: usize) -> Cell {
+            let glyph = char::from_digit((self.count % 10) as u32, 10).expect("Single digit");
+            Cell::new(glyph, Color::GREEN)
+        }
+    }
+
+    impl Simulation for Counter {
+        fn step(&mut self) {
+            self.count = (self.count + 1).min(self.limit);
+        }
+        fn render(&self) -> Frame {
+            Frame::capture(self)
+        }
+        fn is_done(&self) -> bool {
+            self.count == self.limit
+        }
+        fn status(&self) -> String {
+            format!("count {}", self.count)
+        }
+    }
+
+    fn counter(limit: usize) -> Player<'static> {
+        Player::new(move || Box::new(Counter { count: 0, limit }))
+    }
+
+    #[fixture]
+    fn player() -> Player<'static> {
+        counter(5)
+    }
+
+    fn glyph(player: &Player) -> char {
+        player.current().0.cell(0, 0).glyph
+    }
+
+    #[rstest]
+    fn steps_forward_and_back(mut player: Player<'static>) {
+        assert!(!player.back());
+        assert!(player.forward());
+        assert!(player.forward());
+        assert_eq!(glyph(&player), '2');
+        assert!(player.back());
+        assert_eq!(glyph(&player), '1');
+        // Stepping back doesn't rewind the simulation itself.
+        assert_eq!(player.latest, 2);
+        assert!(player.forward());
+        assert_eq!(player.latest, 2);
+    }
+
+    #[rstest]
+    fn history_is_bounded() {
+        let mut player = counter(100);
+        player.capacity = 8;
+        player.seek(60);
+        assert_eq!(player.history.len(), 8);
+        assert_eq!((player.first(), player.latest), (53, 60));
+        // Going back past the history re-runs the simulation up to there.
+        player.seek(21);
+        assert_eq!(player.current().1, "count 21");
+        assert_eq!((player.first(), player.latest), (18, 25));
+        (0..4).for_each(|_| _ = player.back());
+        assert_eq!(player.current().1, "count 17");
+        assert!(player.history.len() <= 8);
+        player.seek(0);
+        assert_eq!(player.current().1, "count 0");
+    }
+
+    #[rstest]
+    fn end_is_bounded() {
+        let mut player = counter(usize::MAX);
+        player.capacity = 4;
+        player.handle(KeyCode::End);
+        assert_eq!(player.position, END_STEPS);
+        assert!(!player.at_end());
+        player.handle(KeyCode::End);
+        assert_eq!(player.position, 2 * END_STEPS);
+    }
+
+    #[rstest]
+    fn seeks(mut player: Player<'static>) {
+        player.seek(3);
+        assert_eq!(glyph(&player), '3');
+        player.seek(1);
+        assert_eq!(glyph(&player), '1');
+        player.seek(100);
+        assert_eq!(glyph(&player), '5');
+        assert!(player.at_end());
+    }
+
+    #[rstest]
+    fn plays_until_done(mut player: Player<'static>) {
+        player.tick();
+        assert_eq!(player.position, 0);
+        player.handle(KeyCode::Char(' '));
+        (0..10).for_each(|_| player.tick());
+        assert_eq!(glyph(&player), '5');
+        assert!(player.paused);
+    }
+
+    #[rstest]
+    fn keys(mut player: Player<'static>) {
+        player.handle(KeyCode::Right);
+        player.handle(KeyCode::Char('+'));
+        assert_eq!((player.position, player.speed), (1, 20));
+        for key in [KeyCode::Char('g'), KeyCode::Char('4'), KeyCode::Enter] {
+            assert!(player.handle(key));
+        }
+        assert_eq!(glyph(&player), '4');
+        player.handle(KeyCode::Home);
+        assert_eq!(player.position, 0);
+        assert!(!player.handle(KeyCode::Char('q')));
+    }
+
+    #[rstest]
+    fn draws_board_and_status(mut player: Player<'static>) {
+        player.seek(2);
+        let mut terminal = Terminal::new(TestBackend::new(80, 8)).expect("Terminal");
+        terminal
+            .draw(|term| draw(term, &player, "test"))
+            .expect("Draw");
+        let buffer = terminal.backend().buffer();
+        let rows = (0..8)
+            .map(|y| (0..80).map(|x| buffer[(x, y)].symbol()).collect::<String>())
+            .collect_vec();
+        assert!(rows[0].starts_with('2'));
+        assert!(rows[3].contains(" test "));
+        assert!(rows[4].contains("step 2/2  paused  10 steps/s"));
+        assert!(rows[5].contains("count 2"));
+    }
+}
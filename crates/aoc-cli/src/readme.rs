@@ -0,0 +1,144 @@
+use aoc_common::prelude::*;
+use aoc_runner::prelude::*;
+use std::fs;
+use std::path::Path;
+
+/// Rewrite the generated sections of the README in place.
+pub fn update(path: &Path, progress: &Progress) -> Result<()> {
+    let text = fs::read_to_string(path)?;
+    let text = replace_section(&text, "quick-stats", &quick_stats(progress))?;
+    let text = replace_section(&text, "stars", &star_table(progress))?;
+    fs::write(path, text)?;
+    Ok(())
+}
+
+/// Replace everything between the `begin` and `end` markers of a section.
+fn replace_section(text: &str, name: &str, body: &str) -> Result<String> {
+    let begin = format!("<!-- aoc-status:{name}:begin -->");
+    let end = format!("<!-- aoc-status:{name}:end -->");
+    let missing = |marker: &str| AdventError::Other(format!("Missing marker {marker}"));
+
+    let start = text.find(&begin).ok_or_else(|| missing(&begin))? + begin.len();
+    let finish = text[start..].find(&end).ok_or_else(|| missing(&end))? + start;
+    Ok(format!("{}\n{}{}", &text[..start], body, &text[finish..]))
+}
+
+fn quick_stats(progress: &Progress) -> String {
+    let years = progress.events.iter().map(|e| e.event.year()).collect_vec();
+    let first = years.first().copied().unwrap_or_default();
+    let last = years.last().copied().unwrap_or_default();
+    format!(
+        "\
+- ✅ Years scaffolded: **{first} → {last}** (that is {} seasons and counting)
+- ⭐ Stars collected: **{} / {}** across {} days
+- 🧩 Daily coverage: **{}** complete, **{}** part 1 only, **{}** unimplemented
+",
+        years.len(),
+        progress.stars(),
+        progress.max_stars(),
+        progress.days(),
+        progress.complete(),
+        progress.part1(),
+        progress.unimplemented(),
+    )
+}
+
+fn star_table(progress: &Progress) -> String {
+    let header = [
+        "Year",
+        "Days",
+        "Complete",
+        "Part 1 only",
+        "Unimplemented",
+        "Stars",
+    ];
+    let rows = progress
+        .events
+        .iter()
+        .map(|event| {
+            [
+                event.event.year().to_string(),
+                event.days.to_string(),
+                event.complete.to_string(),
+                event.part1.to_string(),
+                event.unimplemented.to_string(),
+                format!("{} / {}", event.stars(), event.max_stars()),
+            ]
+        })
+        .collect_vec();
+    let widths = (0..header.len())
+        .map(|col| {
+            rows.iter()
+                .map(|row| row[col].len())
+                .chain([header[col].len()])
+                .max()
+                .unwrap_or_default()
+        })
+        .collect_vec();
+
+    // The year is left-aligned, every count is right-aligned.
+    let line = |cells: Vec<String>| format!("| {} |\n", cells.join(" | "));
+    let align = |row: &[String]| {
+        row.iter()
+            .zip(&widths)
+            .enumerate()
+            .map(|(col, (cell, &width))| match col {
+                0 => format!("{cell:<width$}"),
+                _ => format!("{cell:>width$}"),
+            })
+            .collect_vec()
+    };
+    let rule = widths
+        .iter()
+        .enumerate()
+        .map(|(col, &width)| match col {
+            0 => "-".repeat(width),
+            _ => format!("{}:", "-".repeat(width - 1)),
+        })
+        .collect_vec();
+
+    let mut table = line(align(&header.map(String::from)));
+    table += &line(rule);
+    for row in rows {
+        table += &line(align(&row));
+    }
+    table
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use rstest::*;
+
+    #[rstest]
+    fn replaces_between_markers() -> Result<()> {
+        let text = "\
+intro
+<!-- aoc-status:stars:begin -->
+stale
+<!-- aoc-status:stars:end -->
+outro
+";
+        let expected = "\
+intro
+<!-- aoc-status:stars:begin -->
+fresh
+<!-- aoc-status:stars:end -->
+outro
+";
+        assert_eq!(replace_section(text, "stars", "fresh\n")?, expected);
+        Ok(())
+    }
+
+    #[rstest]
+    fn missing_marker_is_an_error() {
+        assert!(replace_section("no markers here", "stars", "").is_err());
+    }
+
+    #[rstest]
+    fn table_has_a_row_per_event() {
+        let progress = Progress::new(&Registry::new());
+        let table = star_table(&progress);
+        assert_eq!(table.lines().count(), 2 + progress.events.len());
+    }
+}
@@ -0,0 +1,113 @@
+use aoc_common::prelude::*;
+use aoc_common::render::{Frames, RenderFormat};
+use aoc_data::prelude::*;
+use aoc_runner::prelude::*;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+pub struct Selection {
+    pub event: Event,
+    pub day: Option<Day>,
+    pub part: Option<Part>,
+    pub input: Option<Input>,
+    pub all_inputs: bool,
+}
+
+/// Where and how to draw the frames of a single run.
+pub struct RenderOptions {
+    pub path: PathBuf,
+    pub scale: usize,
+    pub every: usize,
+}
+
+pub fn run(
+    selection: Selection,
+    repeat: u32,
+    format: ReportFormat,
+    output: Option<&Path>,
+    render: Option<RenderOptions>,
+) -> Result<()> {
+    let registry = Registry::new();
+    let runner = Runner::new().with_repeat(repeat);
+
+    let solvers = registry
+        .days(selection.event)
+        .filter(|s| selection.day.is_none_or(|day| s.day() == day))
+        .collect_vec();
+    if solvers.is_empty() {
+        return Err(AdventError::InvalidInput(
+            "No solver for selection".to_string(),
+        ));
+    }
+
+    let parts = match selection.part {
+        Some(part) => vec![part],
+        None => Part::iter().collect(),
+    };
+    let runs = solvers
+        .into_iter()
+        .flat_map(|solver| {
+            let runs = if selection.all_inputs {
+                // Every bundled input, each against the part it belongs to.
+                Input::iter()
+                    .filter(|input| parts.contains(&input.part()))
+                    .map(|input| (input.part(), input))
+                    .collect_vec()
+            } else {
+                // Unless told otherwise, each part runs against its own puzzle input.
+                parts
+                    .iter()
+                    .map(|&part| match part {
+                        Part::Part1 => (part, selection.input.unwrap_or(Input::Part1)),
+                        Part::Part2 => (part, selection.input.unwrap_or(Input::Part2)),
+                    })
+                    .collect_vec()
+            };
+            runs.into_iter()
+                .map(move |(part, input)| (solver, part, input))
+        })
+        .collect_vec();
+    if let Some(options) = render {
+        let [(solver, part, input)] = runs.as_slice() else {
+            return Err(AdventError::InvalidInput(
+                "Rendering needs a single day, part and input".to_string(),
+            ));
+        };
+        render_run(*solver, *part, *input, &options)?;
+    }
+    let records = runs
+        .into_iter()
+        .map(|(solver, part, input)| runner.run(solver, part, input))
+        .collect();
+
+    let rendered = RunReport::new(records).render(format)?;
+    match output {
+        Some(path) => fs::write(path, rendered)?,
+        None => print!("{rendered}"),
+    }
+    Ok(())
+}
+
+fn render_run(solver: &dyn Task, part: Part, input: Input, options: &RenderOptions) -> Result<()> {
+    let format = RenderFormat::from_path(&options.path)?;
+    let text = read_resource(solver.event(), solver.day(), input)?;
+    let mut frames = if format.is_animated() {
+        Frames::new().with_every(options.every)
+    } else {
+        Frames::last_only()
+    };
+    solver.solve_with_frames(part, &text, &mut frames)?;
+    if frames.frames().is_empty() {
+        return Err(AdventError::InvalidInput(format!(
+            "{} day {} part {} does not render",
+            solver.event().year(),
+            solver.day().number(),
+            part.number()
+        )));
+    }
+    fs::write(
+        &options.path,
+        format.encode(frames.frames(), options.scale)?,
+    )?;
+    Ok(())
+}
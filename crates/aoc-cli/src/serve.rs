@@ -0,0 +1,270 @@
+use aoc_common::prelude::*;
+use aoc_runner::prelude::*;
+use serde_json::{Value, json};
+use std::any::Any;
+use std::net::{Ipv4Addr, SocketAddr};
+use std::panic::{self, AssertUnwindSafe};
+use tiny_http::{Header, Method, Request, Response};
+
+/// A local HTTP/JSON front end to the solver registry.
+///
+/// Routes:
+/// - `GET /events` lists every event with its progress.
+/// - `GET /events/{year}` lists the days of an event and their status.
+/// - `POST /run/{year}/{day}/{part}` solves the request body and returns the run record.
+pub struct Server {
+    http: tiny_http::Server,
+    registry: Registry,
+}
+
+struct Reply {
+    code: u16,
+    body: Value,
+}
+
+impl Reply {
+    fn ok(body: Value) -> Self {
+        Self { code: 200, body }
+    }
+    fn error(code: u16, message: impl ToString) -> Self {
+        let body = json!({ "error": message.to_string() });
+        Self { code, body }
+    }
+}
+
+impl Server {
+    /// Bind to localhost only; port 0 picks a free port.
+    pub fn bind(port: u16) -> Result<Self> {
+        let http = tiny_http::Server::http((Ipv4Addr::LOCALHOST, port))
+            .map_err(|e| AdventError::Other(e.to_string()))?;
+        let registry = Registry::new();
+        Ok(Self { http, registry })
+    }
+    pub fn addr(&self) -> Option<SocketAddr> {
+        self.http.server_addr().to_ip()
+    }
+    pub fn serve(&self) -> Result<()> {
+        for request in self.http.incoming_requests() {
+            // A client hanging up mid-response should not take the server down.
+            if let Err(e) = self.respond(request) {
+                warn!("Failed to respond: {e}");
+            }
+        }
+        Ok(())
+    }
+    fn respond(&self, mut request: Request) -> Result<()> {
+        let mut body = String::new();
+        let reply = match request.as_reader().read_to_string(&mut body) {
+            Ok(_) => self.route(request.method(), request.url(), &body),
+            Err(e) => Reply::error(400, e),
+        };
+        let header = Header::from_bytes("Content-Type", "application/json").expect("Header");
+        let response = Response::from_string(reply.body.to_string())
+            .with_status_code(reply.code)
+            .with_header(header);
+        request.respond(response)?;
+        Ok(())
+    }
+    fn route(&self, method: &Method, url: &str, body: &str) -> Reply {
+        let path = url.split('?').next().unwrap_or_default();
+        let segments = path.split('/').filter(|s| !s.is_empty()).collect_vec();
+        match (method, segments.as_slice()) {
+            (Method::Get, ["events"]) => self.events(),
+            (Method::Get, ["events", year]) => self.event(year),
+            (Method::Post, ["run", year, day, part]) => self.run(year, day, part, body),
+            (_, ["events"] | ["events", _] | ["run", _, _, _]) => {
+                Reply::error(405, format!("Method {method} not allowed"))
+            }
+            _ => Reply::error(404, format!("No route for {path}")),
+        }
+    }
+    fn events(&self) -> Reply {
+        let progress = Progress::new(&self.registry);
+        let events = progress
+            .events
+            .iter()
+            .map(|e| {
+                json!({
+                    "event": e.event.year(),
+                    "days": e.days,
+                    "complete": e.complete,
+                    "part1": e.part1,
+                    "unimplemented": e.unimplemented,
+                    "stars": e.stars(),
+                })
+            })
+            .collect_vec();
+        Reply::ok(json!(events))
+    }
+    fn event(&self, year: &str) -> Reply {
+        let Some(event) = year.parse().ok().and_then(Event::from_year) else {
+            return Reply::error(404, format!("Unknown event {year}"));
+        };
+        let days = self
+            .registry
+            .days(event)
+            .map(|s| {
+                json!({
+                    "day": s.day().number(),
+                    "status": status_name(s.status()),
+                    "strategy": s.strategy(),
+                })
+            })
+            .collect_vec();
+        Reply::ok(json!({ "event": event.year(), "days": days }))
+    }
+    fn run(&self, year: &str, day: &str, part: &str, body: &str) -> Reply {
+        let solver = match (
+            year.parse().ok().and_then(Event::from_year),
+            day.parse().ok().and_then(Day::from_number),
+        ) {
+            (Some(event), Some(day)) => self.registry.get(event, day),
+            _ => None,
+        };
+        match (solver, part.parse().ok().and_then(Part::from_number)) {
+            (Some(_), _) if body.trim().is_empty() => Reply::error(400, "Empty input"),
+            (Some(solver), Some(part)) => {
+                // Solvers trust their input, so a malformed post can panic; that should fail
+                // the one request rather than the whole server.
+                let run = || Runner::new().run_text(solver, part, "request", body, None);
+                match panic::catch_unwind(AssertUnwindSafe(run)).map(serde_json::to_value) {
+                    Ok(Ok(value)) => Reply::ok(value),
+                    Ok(Err(e)) => Reply::error(500, e),
+                    Err(payload) => Reply::error(500, panic_message(payload)),
+                }
+            }
+            (Some(_), None) => Reply::error(404, format!("Unknown part {part}")),
+            (None, _) => Reply::error(404, format!("No solver for {year} day {day}")),
+        }
+    }
+}
+
+fn panic_message(payload: Box<dyn Any + Send>) -> String {
+    let message = match payload.downcast::<String>() {
+        Ok(message) => *message,
+        Err(payload) => match payload.downcast::<&str>() {
+            Ok(message) => message.to_string(),
+            Err(_) => "Unknown panic".to_string(),
+        },
+    };
+    format!("Solver panicked: {message}")
+}
+
+fn status_name(status: Status) -> &'static str {
+    match status {
+        Status::Unimplemented => "unimplemented",
+        Status::Part1 => "part1",
+        Status::Complete => "complete",
+    }
+}
+
+pub fn run(port: u16) -> Result<()> {
+    let server = Server::bind(port)?;
+    if let Some(addr) = server.addr() {
+        println!("Listening on http://{addr}");
+    }
+    server.serve()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use rstest::*;
+    use std::sync::mpsc;
+    use std::thread;
+
+    /// Start a server on a free port in the background and return its base URL.
+    #[fixture]
+    #[once]
+    fn base_url() -> String {
+        let (sender, receiver) = mpsc::channel();
+        thread::spawn(move || {
+            let server = Server::bind(0).expect("Bind");
+            sender.send(server.addr().expect("Address")).expect("Send");
+            server.serve().expect("Serve");
+        });
+        let addr = receiver.recv().expect("Receive");
+        assert!(addr.ip().is_loopback());
+        format!("http://{addr}")
+    }
+
+    fn get(url: &str) -> (u16, Value) {
+        let response = match ureq::get(url).call() {
+            Ok(response) => response,
+            Err(ureq::Error::Status(_, response)) => response,
+            Err(e) => panic!("Request failed: {e}"),
+        };
+        (response.status(), response.into_json().expect("JSON"))
+    }
+
+    fn post(url: &str, body: &str) -> (u16, Value) {
+        let response = match ureq::post(url).send_string(body) {
+            Ok(response) => response,
+            Err(ureq::Error::Status(_, response)) => response,
+            Err(e) => panic!("Request failed: {e}"),
+        };
+        (response.status(), response.into_json().expect("JSON"))
+    }
+
+    #[rstest]
+    fn lists_events(base_url: &String) {
+        let (code, body) = get(&format!("{base_url}/events"));
+        assert_eq!(code, 200);
+        let events = body.as_array().expect("Array");
+        assert_eq!(events.len(), Event::iter().count());
+        assert_eq!(events[0]["event"], 2015);
+        assert_eq!(events[0]["days"], 25);
+    }
+
+    #[rstest]
+    fn lists_days(base_url: &String) {
+        let (code, body) = get(&format!("{base_url}/events/2016"));
+        assert_eq!(code, 200);
+        assert_eq!(body["days"][0]["day"], 1);
+        assert_eq!(body["days"][0]["status"], "complete");
+        assert_eq!(body["days"][1]["status"], "unimplemented");
+    }
+
+    #[rstest]
+    fn runs_posted_input(base_url: &String) {
+        let (code, body) = post(&format!("{base_url}/run/2016/1/1"), "R5, L5, R5, R3");
+        assert_eq!(code, 200);
+        assert_eq!(body["answer"], "12");
+        assert_eq!(body["status"], "unverified");
+        assert!(body["mean_ns"].as_u64().is_some());
+    }
+
+    #[rstest]
+    #[case("/run/2016/1/3", "R2")]
+    #[case("/run/2016/26/1", "R2")]
+    #[case("/run/2014/1/1", "R2")]
+    #[case("/nowhere", "")]
+    fn unknown_routes(base_url: &String, #[case] path: &str, #[case] body: &str) {
+        let (code, body) = post(&format!("{base_url}{path}"), body);
+        assert_eq!(code, 404);
+        assert!(body["error"].is_string());
+    }
+
+    #[rstest]
+    fn rejects_empty_input(base_url: &String) {
+        let (code, _) = post(&format!("{base_url}/run/2016/1/1"), "");
+        assert_eq!(code, 400);
+    }
+
+    #[rstest]
+    fn survives_panicking_solver(base_url: &String) {
+        // A map with no guard on it.
+        let (code, body) = post(&format!("{base_url}/run/2024/6/1"), "...\n...\n");
+        assert_eq!(code, 500);
+        assert_eq!(body["error"], "Solver panicked: No guard found");
+        let (code, body) = post(&format!("{base_url}/run/2016/1/1"), "R2, L3");
+        assert_eq!(code, 200);
+        assert_eq!(body["answer"], "5");
+    }
+
+    #[rstest]
+    fn rejects_wrong_method(base_url: &String) {
+        let (code, _) = get(&format!("{base_url}/run/2016/1/1"));
+        assert_eq!(code, 405);
+    }
+}
@@ -0,0 +1,38 @@
+use crate::readme;
+use aoc_common::prelude::*;
+use aoc_runner::prelude::*;
+use std::path::Path;
+
+pub fn run(readme: Option<&Path>) -> Result<()> {
+    let registry = Registry::new();
+    let progress = Progress::new(&registry);
+
+    println!(
+        "{:<6} {:>6} {:>9} {:>7} {:>14}",
+        "Year", "Stars", "Complete", "Part 1", "Unimplemented"
+    );
+    for event in &progress.events {
+        println!(
+            "{:<6} {:>6} {:>9} {:>7} {:>14}",
+            event.event.year(),
+            format!("{}/{}", event.stars(), event.max_stars()),
+            event.complete,
+            event.part1,
+            event.unimplemented
+        );
+    }
+    println!(
+        "{:<6} {:>6} {:>9} {:>7} {:>14}",
+        "Total",
+        format!("{}/{}", progress.stars(), progress.max_stars()),
+        progress.complete(),
+        progress.part1(),
+        progress.unimplemented()
+    );
+
+    if let Some(path) = readme {
+        readme::update(path, &progress)?;
+        println!("Updated {}", path.display());
+    }
+    Ok(())
+}
@@ -0,0 +1,219 @@
+use aoc_common::prelude::*;
+use aoc_data::prelude::*;
+use aoc_runner::prelude::*;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::thread;
+use std::time::{Duration, SystemTime};
+
+const WORKSPACE_DIR: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/../..");
+
+/// Polls a set of files and directories for modifications.
+struct Watcher {
+    paths: Vec<PathBuf>,
+    last_modified: Option<SystemTime>,
+}
+
+impl Watcher {
+    fn new(paths: Vec<PathBuf>) -> Self {
+        let last_modified = None;
+        Self {
+            paths,
+            last_modified,
+        }
+    }
+    /// True on the first call, then whenever any watched file is newer than before.
+    fn changed(&mut self) -> bool {
+        let modified = self.paths.iter().filter_map(|p| latest_modified(p)).max();
+        let changed = modified.is_some() && modified != self.last_modified;
+        self.last_modified = modified;
+        changed
+    }
+}
+
+fn latest_modified(path: &Path) -> Option<SystemTime> {
+    let metadata = fs::metadata(path).ok()?;
+    if metadata.is_dir() {
+        fs::read_dir(path)
+            .ok()?
+            .filter_map(|entry| entry.ok())
+            .filter_map(|entry| latest_modified(&entry.path()))
+            .max()
+    } else {
+        metadata.modified().ok()
+    }
+}
+
+/// Rebuild the runner and run every bundled input of the day in a child process.
+///
+/// The child uses its own target directory so that rebuilding never touches this binary.
+fn build_and_run(event: Event, day: Day) -> Result<RunReport> {
+    let cargo = std::env::var("CARGO").unwrap_or_else(|_| "cargo".to_string());
+    let output = Command::new(cargo)
+        .current_dir(WORKSPACE_DIR)
+        .args([
+            "run",
+            "--release",
+            "--quiet",
+            "--target-dir",
+            "target/watch",
+        ])
+        .args(["-p", "aoc-cli", "--", "run"])
+        .args([event.year().to_string(), day.number().to_string()])
+        .args(["--all-inputs", "--format", "json"])
+        .output()?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(AdventError::Other(stderr.trim().to_string()));
+    }
+    RunReport::from_json(&String::from_utf8_lossy(&output.stdout))
+}
+
+/// One line per run, flagging answers that changed since the previous report.
+fn compare(current: &RunReport, previous: Option<&RunReport>) -> Vec<String> {
+    current
+        .records
+        .iter()
+        .map(|record| {
+            let before = previous
+                .and_then(|p| {
+                    p.records
+                        .iter()
+                        .find(|r| r.part == record.part && r.input == record.input)
+                })
+                .and_then(|r| r.answer.as_deref());
+            let answer = record.answer.as_deref().unwrap_or("-");
+            let marker = match record.status {
+                RunStatus::Correct => "✅",
+                RunStatus::Incorrect => "❌",
+                RunStatus::Failed => "💥",
+                RunStatus::Unverified => "❔",
+                RunStatus::Unimplemented => "⬜",
+            };
+            let mut line = format!(
+                "{marker} part {} {:<8} {answer:>20}",
+                record.part, record.input
+            );
+            if let Some(expected) = record.expected.as_deref().filter(|&e| e != answer) {
+                line += &format!("  expected {expected}");
+            }
+            match before {
+                Some(before) if before != answer => line += &format!("  (was {before})"),
+                None if previous.is_some() && record.answer.is_some() => line += "  (new)",
+                _ => {}
+            }
+            if let Some(error) = &record.error {
+                line += &format!("  {error}");
+            }
+            line
+        })
+        .collect()
+}
+
+pub fn run(event: Event, day: Day, interval: Duration) -> Result<()> {
+    let source: PathBuf = [
+        WORKSPACE_DIR,
+        "crates",
+        &format!("aoc-{}", event.year()),
+        "src",
+        &format!("day{}.rs", day.number()),
+    ]
+    .iter()
+    .collect();
+    let resources = resource_dir(event, day);
+    let source = fs::canonicalize(&source)
+        .map_err(|_| AdventError::InvalidInput(format!("No source file {}", source.display())))?;
+
+    println!("Watching {} and {}", source.display(), resources.display());
+    let mut watcher = Watcher::new(vec![source, resources]);
+    let mut previous: Option<RunReport> = None;
+    loop {
+        if watcher.changed() {
+            println!("\n🔨 {} day {}", event.year(), day.number());
+            match build_and_run(event, day) {
+                Ok(report) => {
+                    compare(&report, previous.as_ref())
+                        .iter()
+                        .for_each(|line| println!("{line}"));
+                    previous = Some(report);
+                }
+                Err(e) => println!("{e}"),
+            }
+        }
+        thread::sleep(interval);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use rstest::*;
+
+    fn record(input: &str, answer: &str, expected: Option<&str>, status: RunStatus) -> RunRecord {
+        RunRecord {
+            event: 2024,
+            day: 16,
+            part: 1,
+            input: input.to_string(),
+            strategy: "default".to_string(),
+            answer: Some(answer.to_string()),
+            expected: expected.map(String::from),
+            status,
+            error: None,
+            runs: 1,
+            min_ns: 0,
+            mean_ns: 0,
+            max_ns: 0,
+            allocations: None,
+            allocated_bytes: None,
+        }
+    }
+
+    #[rstest]
+    fn first_run_has_no_history() {
+        let current = RunReport::new(vec![record("part1", "7", Some("7"), RunStatus::Correct)]);
+        let lines = compare(&current, None);
+        assert_eq!(lines.len(), 1);
+        assert!(lines[0].starts_with("✅ part 1 part1"));
+        assert!(!lines[0].contains("was"));
+    }
+
+    #[rstest]
+    fn changed_answers_are_flagged() {
+        let previous = RunReport::new(vec![record("part1", "7", Some("8"), RunStatus::Incorrect)]);
+        let current = RunReport::new(vec![record("part1", "9", Some("8"), RunStatus::Incorrect)]);
+        let lines = compare(&current, Some(&previous));
+        assert!(lines[0].starts_with("❌"));
+        assert!(lines[0].ends_with("expected 8  (was 7)"));
+    }
+
+    #[rstest]
+    fn unchanged_answers_are_quiet() {
+        let previous = RunReport::new(vec![record("example1", "7", None, RunStatus::Unverified)]);
+        let lines = compare(&previous, Some(&previous));
+        assert!(lines[0].ends_with(" 7"));
+    }
+
+    #[rstest]
+    fn watcher_sees_modifications() -> Result<()> {
+        let dir = std::env::temp_dir().join(format!("aoc-watch-{}", std::process::id()));
+        fs::create_dir_all(&dir)?;
+        let file = dir.join("input.txt");
+        fs::write(&file, "1")?;
+
+        let mut watcher = Watcher::new(vec![dir.clone()]);
+        assert!(watcher.changed());
+        assert!(!watcher.changed());
+
+        let later = SystemTime::now() + Duration::from_secs(5);
+        fs::File::options()
+            .write(true)
+            .open(&file)?
+            .set_modified(later)?;
+        assert!(watcher.changed());
+
+        fs::remove_dir_all(dir)?;
+        Ok(())
+    }
+}
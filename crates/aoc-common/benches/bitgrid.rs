@@ -0,0 +1,77 @@
+//! `BitGrid` against the `Grid<bool>` it replaces, on the two kinds of whole-grid update the
+//! puzzles lean on: a neighbour-count generation and a batch of rectangle toggles.
+
+use aoc_common::bitgrid::BitGrid;
+use aoc_common::grid::Grid;
+use criterion::{Criterion, criterion_group, criterion_main};
+use std::hint::black_box;
+
+fn random_grid(size: usize) -> Grid<bool> {
+    let mut state = 0x2545_f491_4f6c_dd1d_u64;
+    Grid::from_fn(size, size, |_| {
+        state ^= state << 13;
+        state ^= state >> 7;
+        state ^= state << 17;
+        state.is_multiple_of(3)
+    })
+}
+
+fn rectangles() -> Vec<((usize, usize), (usize, usize))> {
+    (0..50)
+        .map(|i| ((i * 7, i * 11), (500 + i * 9, 400 + i * 12)))
+        .collect()
+}
+
+fn generation(c: &mut Criterion) {
+    let grid = random_grid(140);
+    let bits = BitGrid::from(&grid);
+    let mut group = c.benchmark_group("generation");
+    group.bench_function("grid", |b| {
+        b.iter(|| {
+            let grid = black_box(&grid);
+            Grid::from_fn(grid.width(), grid.height(), |pos| {
+                let live = grid.neighbours8(pos).filter(|&n| grid[n]).count();
+                live == 3 || (grid[pos] && live == 2)
+            })
+        })
+    });
+    group.bench_function("bitgrid", |b| {
+        b.iter(|| {
+            let bits = black_box(&bits);
+            let counts = bits.neighbour_counts();
+            &counts.exactly(3) | &(bits & &counts.exactly(2))
+        })
+    });
+    group.finish();
+}
+
+fn toggles(c: &mut Criterion) {
+    let rectangles = rectangles();
+    let mut group = c.benchmark_group("toggle_rect");
+    group.bench_function("grid", |b| {
+        b.iter(|| {
+            let mut lights = Grid::filled(1000, 1000, false);
+            for &((top, left), (bottom, right)) in black_box(&rectangles) {
+                for row in top..=bottom {
+                    for col in left..=right {
+                        lights[(row, col)] = !lights[(row, col)];
+                    }
+                }
+            }
+            lights
+        })
+    });
+    group.bench_function("bitgrid", |b| {
+        b.iter(|| {
+            let mut lights = BitGrid::new(1000, 1000);
+            for &(from, to) in black_box(&rectangles) {
+                lights.toggle_rect(from, to);
+            }
+            lights
+        })
+    });
+    group.finish();
+}
+
+criterion_group!(benches, generation, toggles);
+criterion_main!(benches);
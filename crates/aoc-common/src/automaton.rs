@@ -0,0 +1,436 @@
+//! Cellular automata on square lattices of any dimension.
+//!
+//! An [`Automaton`] holds cells addressed by `[i64; D]` coordinates, `[x, y]` in two
+//! dimensions, and steps them all at once with a rule closure that sees a cell's state and its
+//! neighbours' states. Cells are kept either in a dense box or sparsely, storing only the cells
+//! that differ from the background; everything not stored is in the background state.
+//!
+//! The background itself is stepped too, as a cell surrounded by background. Usually it stays
+//! put, but a rule that turns an empty neighbourhood alive makes the whole infinite plane flash
+//! on and off, as in 2021 day 20.
+
+use crate::grid::Grid;
+use std::collections::{HashMap, HashSet};
+
+pub type Coord<const D: usize> = [i64; D];
+
+/// Which surrounding cells count as neighbours.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Neighbourhood {
+    /// Every cell differing by at most one on each axis: 8 in 2D, 26 in 3D.
+    Moore,
+    /// Cells one step along a single axis: 4 in 2D, 6 in 3D.
+    VonNeumann,
+}
+
+impl Neighbourhood {
+    /// The offsets in reading order, the last axis varying slowest: row by row in 2D.
+    pub fn offsets<const D: usize>(self) -> Vec<Coord<D>> {
+        let mut offsets = vec![[0; D]];
+        for axis in (0..D).rev() {
+            offsets = offsets
+                .into_iter()
+                .flat_map(|o| {
+                    (-1..=1).map(move |d| {
+                        let mut o = o;
+                        o[axis] = d;
+                        o
+                    })
+                })
+                .collect();
+        }
+        offsets.retain(|o| {
+            let moved = o.iter().filter(|&&d| d != 0).count();
+            match self {
+                Neighbourhood::Moore => moved > 0,
+                Neighbourhood::VonNeumann => moved == 1,
+            }
+        });
+        offsets
+    }
+}
+
+/// What a dense automaton sees past the edge of its box.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Edge {
+    /// The background, which never changes.
+    Dead,
+    /// The opposite side of the box, as on a torus.
+    Wrap,
+    /// The background, with the box growing each generation to take in any cell that changes.
+    Infinite,
+}
+
+#[derive(Debug, Clone)]
+enum Cells<S, const D: usize> {
+    Dense {
+        min: Coord<D>,
+        dims: [usize; D],
+        cells: Vec<S>,
+    },
+    Sparse(HashMap<Coord<D>, S>),
+}
+
+#[derive(Debug, Clone)]
+pub struct Automaton<S, const D: usize> {
+    cells: Cells<S, D>,
+    background: S,
+    edge: Edge,
+    offsets: Vec<Coord<D>>,
+    generation: usize,
+}
+
+fn add<const D: usize>(a: Coord<D>, b: Coord<D>) -> Coord<D> {
+    std::array::from_fn(|i| a[i] + b[i])
+}
+
+impl<S: Copy + Eq, const D: usize> Automaton<S, D> {
+    fn new(cells: Cells<S, D>, background: S, edge: Edge) -> Self {
+        Self {
+            cells,
+            background,
+            edge,
+            offsets: Neighbourhood::Moore.offsets(),
+            generation: 0,
+        }
+    }
+    /// A box of `dims` cells from the origin, all in the background state, with dead edges.
+    pub fn dense(dims: [usize; D], background: S) -> Self {
+        let cells = vec![background; dims.iter().product()];
+        let dense = Cells::Dense {
+            min: [0; D],
+            dims,
+            cells,
+        };
+        Self::new(dense, background, Edge::Dead)
+    }
+    /// An unbounded automaton storing only cells that differ from the background.
+    pub fn sparse(background: S) -> Self {
+        Self::new(Cells::Sparse(HashMap::new()), background, Edge::Infinite)
+    }
+    /// How a dense automaton treats its edges; a sparse one is always [`Edge::Infinite`].
+    pub fn with_edge(mut self, edge: Edge) -> Self {
+        if matches!(self.cells, Cells::Dense { .. }) {
+            self.edge = edge;
+        }
+        self
+    }
+    pub fn with_neighbourhood(mut self, neighbourhood: Neighbourhood) -> Self {
+        self.offsets = neighbourhood.offsets();
+        self
+    }
+    pub fn with_cells(mut self, cells: impl IntoIterator<Item = (Coord<D>, S)>) -> Self {
+        for (coord, state) in cells {
+            self.set(coord, state);
+        }
+        self
+    }
+
+    pub fn generation(&self) -> usize {
+        self.generation
+    }
+    pub fn background(&self) -> S {
+        self.background
+    }
+    /// The neighbour offsets the rule sees, in the order it sees them.
+    pub fn offsets(&self) -> &[Coord<D>] {
+        &self.offsets
+    }
+
+    fn index(min: &Coord<D>, dims: &[usize; D], coord: Coord<D>) -> Option<usize> {
+        let mut index = 0;
+        for axis in (0..D).rev() {
+            let offset = usize::try_from(coord[axis] - min[axis]).ok()?;
+            if offset >= dims[axis] {
+                return None;
+            }
+            index = index * dims[axis] + offset;
+        }
+        Some(index)
+    }
+    fn coord(min: &Coord<D>, dims: &[usize; D], mut index: usize) -> Coord<D> {
+        std::array::from_fn(|axis| {
+            let offset = index % dims[axis];
+            index /= dims[axis];
+            min[axis] + offset as i64
+        })
+    }
+
+    /// The state at `coord`, following the edge behaviour outside a dense box.
+    pub fn get(&self, coord: Coord<D>) -> S {
+        match &self.cells {
+            Cells::Dense { min, dims, cells } => {
+                let coord = if self.edge == Edge::Wrap {
+                    std::array::from_fn(|i| min[i] + (coord[i] - min[i]).rem_euclid(dims[i] as i64))
+                } else {
+                    coord
+                };
+                Self::index(min, dims, coord).map_or(self.background, |i| cells[i])
+            }
+            Cells::Sparse(cells) => cells.get(&coord).copied().unwrap_or(self.background),
+        }
+    }
+    /// Sets one cell, which for a dense automaton must be inside its box.
+    pub fn set(&mut self, coord: Coord<D>, state: S) {
+        match &mut self.cells {
+            Cells::Dense { min, dims, cells } => {
+                let i = Self::index(min, dims, coord)
+                    .unwrap_or_else(|| panic!("{coord:?} is outside the automaton"));
+                cells[i] = state;
+            }
+            Cells::Sparse(cells) if state == self.background => {
+                cells.remove(&coord);
+            }
+            Cells::Sparse(cells) => {
+                cells.insert(coord, state);
+            }
+        }
+    }
+
+    /// Every stored cell: the whole box when dense, only non-background cells when sparse.
+    pub fn cells(&self) -> Box<dyn Iterator<Item = (Coord<D>, S)> + '_> {
+        match &self.cells {
+            Cells::Dense { min, dims, cells } => Box::new(
+                cells
+                    .iter()
+                    .enumerate()
+                    .map(|(i, &state)| (Self::coord(min, dims, i), state)),
+            ),
+            Cells::Sparse(cells) => Box::new(cells.iter().map(|(&coord, &state)| (coord, state))),
+        }
+    }
+    /// How many stored cells are in `state`; the endless background beyond them isn't counted.
+    pub fn count(&self, state: S) -> usize {
+        self.cells().filter(|&(_, s)| s == state).count()
+    }
+
+    /// Advances one generation, each cell's next state being `rule(state, neighbours)`.
+    pub fn step(&mut self, mut rule: impl FnMut(S, &[S]) -> S) {
+        // Past dead or wrapped edges the background is never seen changing, so it stays put.
+        let background = if self.edge == Edge::Infinite {
+            rule(self.background, &vec![self.background; self.offsets.len()])
+        } else {
+            self.background
+        };
+        let mut neighbours = Vec::with_capacity(self.offsets.len());
+        let mut next = |automaton: &Self, coord: Coord<D>| {
+            neighbours.clear();
+            neighbours.extend(
+                automaton
+                    .offsets
+                    .iter()
+                    .map(|&o| automaton.get(add(coord, o))),
+            );
+            rule(automaton.get(coord), &neighbours)
+        };
+
+        let cells = match &self.cells {
+            Cells::Dense { min, dims, .. } => {
+                let grow = i64::from(self.edge == Edge::Infinite);
+                let min = min.map(|m| m - grow);
+                let dims = dims.map(|d| d + 2 * grow as usize);
+                let cells = (0..dims.iter().product())
+                    .map(|i| next(self, Self::coord(&min, &dims, i)))
+                    .collect();
+                Cells::Dense { min, dims, cells }
+            }
+            Cells::Sparse(cells) => {
+                let candidates: HashSet<Coord<D>> = cells
+                    .keys()
+                    .flat_map(|&c| self.offsets.iter().map(move |&o| add(c, o)).chain([c]))
+                    .collect();
+                Cells::Sparse(
+                    candidates
+                        .into_iter()
+                        .map(|c| (c, next(self, c)))
+                        .filter(|&(_, state)| state != background)
+                        .collect(),
+                )
+            }
+        };
+        self.cells = cells;
+        self.background = background;
+        self.generation += 1;
+    }
+    /// Advances `generations` steps with the same rule.
+    pub fn run(&mut self, generations: usize, mut rule: impl FnMut(S, &[S]) -> S) {
+        for _ in 0..generations {
+            self.step(&mut rule);
+        }
+    }
+}
+
+impl<S: Copy + Eq> Automaton<S, 2> {
+    /// A dense automaton over a grid, with `x` as the column and `y` as the row.
+    pub fn from_grid(grid: &Grid<S>, background: S) -> Self {
+        let mut automaton = Self::dense([grid.width(), grid.height()], background);
+        for ((row, col), &state) in grid.iter() {
+            automaton.set([col as i64, row as i64], state);
+        }
+        automaton
+    }
+    /// The box of a dense automaton, or the bounding box of a sparse one's stored cells.
+    pub fn to_grid(&self) -> Grid<S> {
+        let (min, max) = match &self.cells {
+            Cells::Dense { min, dims, .. } => (
+                *min,
+                [min[0] + dims[0] as i64 - 1, min[1] + dims[1] as i64 - 1],
+            ),
+            Cells::Sparse(cells) if cells.is_empty() => return Grid::filled(0, 0, self.background),
+            Cells::Sparse(cells) => {
+                let lo = |axis: usize| cells.keys().map(|c| c[axis]).min().unwrap();
+                let hi = |axis: usize| cells.keys().map(|c| c[axis]).max().unwrap();
+                ([lo(0), lo(1)], [hi(0), hi(1)])
+            }
+        };
+        let width = (max[0] - min[0] + 1) as usize;
+        let height = (max[1] - min[1] + 1) as usize;
+        Grid::from_fn(width, height, |(row, col)| {
+            self.get([min[0] + col as i64, min[1] + row as i64])
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use rstest::*;
+
+    fn life(alive: bool, neighbours: &[bool]) -> bool {
+        let live = neighbours.iter().filter(|&&n| n).count();
+        live == 3 || (alive && live == 2)
+    }
+
+    fn live_cells<const D: usize>(picture: &str) -> Vec<(Coord<D>, bool)> {
+        picture
+            .lines()
+            .enumerate()
+            .flat_map(|(y, line)| {
+                line.chars()
+                    .enumerate()
+                    .filter(|&(_, c)| c == '#')
+                    .map(move |(x, _)| {
+                        let mut coord = [0; D];
+                        coord[0] = x as i64;
+                        coord[1] = y as i64;
+                        (coord, true)
+                    })
+            })
+            .collect()
+    }
+
+    #[rstest]
+    fn neighbourhoods() {
+        assert_eq!(
+            Neighbourhood::Moore.offsets::<2>(),
+            vec![
+                [-1, -1],
+                [0, -1],
+                [1, -1],
+                [-1, 0],
+                [1, 0],
+                [-1, 1],
+                [0, 1],
+                [1, 1]
+            ]
+        );
+        assert_eq!(
+            Neighbourhood::VonNeumann.offsets::<2>(),
+            vec![[0, -1], [-1, 0], [1, 0], [0, 1]]
+        );
+        assert_eq!(Neighbourhood::VonNeumann.offsets::<3>().len(), 6);
+        assert_eq!(Neighbourhood::Moore.offsets::<4>().len(), 80);
+    }
+
+    #[rstest]
+    fn blinker() {
+        let mut automaton =
+            Automaton::dense([5, 5], false).with_cells(live_cells(".....\n.....\n.###."));
+        automaton.step(life);
+        assert_eq!(
+            automaton.to_grid(),
+            Automaton::dense([5, 5], false)
+                .with_cells(live_cells("..\n..#\n..#\n..#"))
+                .to_grid()
+        );
+        automaton.step(life);
+        assert_eq!(automaton.count(true), 3);
+        assert!(automaton.get([1, 2]) && automaton.get([3, 2]));
+        assert_eq!(automaton.generation(), 2);
+    }
+
+    #[rstest]
+    fn animated_lights() {
+        // 2015 day 18's example: a 6x6 grid whose edges are dark.
+        let picture = ".#.#.#\n...##.\n#....#\n..#...\n#.#..#\n####..";
+        let mut lights = Automaton::dense([6, 6], false).with_cells(live_cells(picture));
+        lights.run(4, life);
+        assert_eq!(lights.count(true), 4);
+    }
+
+    #[rstest]
+    fn glider_wraps() {
+        let glider = live_cells(".#\n..#\n###");
+        let start = Automaton::dense([5, 5], false)
+            .with_edge(Edge::Wrap)
+            .with_cells(glider.clone());
+        let mut automaton = start.clone();
+        // Every four generations the glider moves one cell diagonally.
+        automaton.run(20, life);
+        assert_eq!(automaton.to_grid(), start.to_grid());
+        let mut dead = Automaton::dense([5, 5], false).with_cells(glider);
+        dead.run(20, life);
+        assert_ne!(dead.count(true), 5);
+    }
+
+    #[rstest]
+    fn glider_is_unbounded_when_sparse() {
+        let mut automaton = Automaton::sparse(false).with_cells(live_cells(".#\n..#\n###"));
+        automaton.run(40, life);
+        let moved: Vec<_> = live_cells(".#\n..#\n###")
+            .into_iter()
+            .map(|([x, y], _)| [x + 10, y + 10])
+            .collect();
+        assert_eq!(automaton.count(true), 5);
+        assert!(moved.into_iter().all(|c| automaton.get(c)));
+    }
+
+    #[rstest]
+    fn pocket_dimension() {
+        // 2020 day 17's example, in three dimensions and then four.
+        let picture = ".#.\n..#\n###";
+        let mut cubes = Automaton::<_, 3>::sparse(false).with_cells(live_cells(picture));
+        cubes.run(6, life);
+        assert_eq!(cubes.count(true), 112);
+        let mut hypercubes = Automaton::<_, 4>::sparse(false).with_cells(live_cells(picture));
+        hypercubes.run(6, life);
+        assert_eq!(hypercubes.count(true), 848);
+    }
+
+    #[rstest]
+    fn flipping_background() {
+        // An empty neighbourhood lights up and a full one goes dark, so the infinite plane
+        // flashes every generation like 2021 day 20's image enhancement.
+        let flash = |alive: bool, neighbours: &[bool]| {
+            neighbours.iter().filter(|&&n| n).count() + usize::from(alive) <= 4
+        };
+        let seed = live_cells("#.\n.#");
+        let mut sparse = Automaton::sparse(false).with_cells(seed.clone());
+        let mut dense = Automaton::dense([2, 2], false)
+            .with_edge(Edge::Infinite)
+            .with_cells(seed);
+        sparse.step(flash);
+        dense.step(flash);
+        assert!(sparse.background() && dense.background());
+        assert!(sparse.get([100, -100]) && dense.get([100, -100]));
+        sparse.step(flash);
+        dense.step(flash);
+        assert!(!sparse.background() && !dense.background());
+        assert_eq!(sparse.count(true), dense.count(true));
+        let grid = dense.to_grid();
+        assert_eq!((grid.width(), grid.height()), (6, 6));
+        for (x, y) in (-3..5).flat_map(|x| (-3..5).map(move |y| (x, y))) {
+            assert_eq!(sparse.get([x, y]), dense.get([x, y]));
+        }
+    }
+}
@@ -0,0 +1,375 @@
+//! A grid of booleans packed 64 to a word.
+//!
+//! Each row starts on a fresh `u64`, with column `c` in bit `c % 64` of word `c / 64`, and the
+//! bits past the last column are always zero. Whole-grid updates work a word at a time: set
+//! operations are the bitwise operators, and [`BitGrid::neighbour_counts`] adds up the eight
+//! shifted copies of the grid with a bit-sliced adder instead of visiting each cell.
+
+use crate::grid::{Grid, GridIndex};
+use std::fmt::{Display, Formatter};
+use std::ops::{BitAnd, BitOr, BitXor, Not};
+
+const BITS: usize = u64::BITS as usize;
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct BitGrid {
+    width: usize,
+    height: usize,
+    stride: usize,
+    words: Vec<u64>,
+}
+
+impl BitGrid {
+    /// A grid with every cell clear.
+    pub fn new(width: usize, height: usize) -> Self {
+        let stride = width.div_ceil(BITS);
+        Self {
+            width,
+            height,
+            stride,
+            words: vec![0; stride * height],
+        }
+    }
+    pub fn width(&self) -> usize {
+        self.width
+    }
+    pub fn height(&self) -> usize {
+        self.height
+    }
+    pub fn contains(&self, pos: impl GridIndex) -> bool {
+        pos.row_col()
+            .is_some_and(|(row, col)| row < self.height && col < self.width)
+    }
+    pub fn get(&self, pos: impl GridIndex) -> Option<bool> {
+        let (row, col) = pos.row_col()?;
+        (row < self.height && col < self.width)
+            .then(|| self.words[row * self.stride + col / BITS] >> (col % BITS) & 1 == 1)
+    }
+    /// Sets one cell, panicking if it's outside the grid like indexing a [`Grid`] does.
+    pub fn set(&mut self, pos: impl GridIndex, value: bool) {
+        let (row, col) = self.checked(pos);
+        let word = &mut self.words[row * self.stride + col / BITS];
+        let bit = 1 << (col % BITS);
+        if value {
+            *word |= bit;
+        } else {
+            *word &= !bit;
+        }
+    }
+    pub fn toggle(&mut self, pos: impl GridIndex) {
+        let (row, col) = self.checked(pos);
+        self.words[row * self.stride + col / BITS] ^= 1 << (col % BITS);
+    }
+    fn checked(&self, pos: impl GridIndex) -> (usize, usize) {
+        pos.row_col()
+            .filter(|&(row, col)| row < self.height && col < self.width)
+            .unwrap_or_else(|| panic!("position outside a {}x{} grid", self.width, self.height))
+    }
+
+    /// How many cells are set.
+    pub fn count_ones(&self) -> usize {
+        self.words.iter().map(|w| w.count_ones() as usize).sum()
+    }
+    pub fn is_empty(&self) -> bool {
+        self.words.iter().all(|&w| w == 0)
+    }
+    /// The positions of the set cells, row by row.
+    pub fn positions(&self) -> impl Iterator<Item = (usize, usize)> + '_ {
+        self.words.iter().enumerate().flat_map(move |(i, &word)| {
+            let (row, base) = (i / self.stride, i % self.stride * BITS);
+            std::iter::successors((word != 0).then_some(word), |&w| {
+                let rest = w & (w - 1);
+                (rest != 0).then_some(rest)
+            })
+            .map(move |w| (row, base + w.trailing_zeros() as usize))
+        })
+    }
+
+    /// Applies `f(word, mask)` to the words covering the rectangle between two corners,
+    /// inclusive, where `mask` picks out the rectangle's columns in that word.
+    fn rect(&mut self, from: (usize, usize), to: (usize, usize), f: impl Fn(u64, u64) -> u64) {
+        let (top, left) = self.checked(from);
+        let (bottom, right) = self.checked(to);
+        for row in top..=bottom {
+            for w in left / BITS..=right / BITS {
+                let low = if w == left / BITS { left % BITS } else { 0 };
+                let high = if w == right / BITS {
+                    right % BITS
+                } else {
+                    BITS - 1
+                };
+                let mask = (u64::MAX >> (BITS - 1 - high)) & (u64::MAX << low);
+                let word = &mut self.words[row * self.stride + w];
+                *word = f(*word, mask);
+            }
+        }
+    }
+    /// Sets every cell from `from` to `to`, both `(row, col)` corners included.
+    pub fn set_rect(&mut self, from: (usize, usize), to: (usize, usize)) {
+        self.rect(from, to, |word, mask| word | mask);
+    }
+    pub fn clear_rect(&mut self, from: (usize, usize), to: (usize, usize)) {
+        self.rect(from, to, |word, mask| word & !mask);
+    }
+    pub fn toggle_rect(&mut self, from: (usize, usize), to: (usize, usize)) {
+        self.rect(from, to, |word, mask| word ^ mask);
+    }
+
+    fn row(&self, row: usize) -> &[u64] {
+        &self.words[row * self.stride..(row + 1) * self.stride]
+    }
+    /// Clears the unused bits past the last column of every row.
+    fn trim(&mut self) {
+        if !self.width.is_multiple_of(BITS) {
+            let mask = u64::MAX >> (BITS - self.width % BITS);
+            for row in self.words.chunks_mut(self.stride) {
+                row[self.stride - 1] &= mask;
+            }
+        }
+    }
+
+    /// How many of each cell's eight neighbours are set, counting outside the grid as clear.
+    pub fn neighbour_counts(&self) -> NeighbourCounts {
+        let mut planes: [BitGrid; 4] =
+            std::array::from_fn(|_| BitGrid::new(self.width, self.height));
+        let empty = vec![0; self.stride];
+        for row in 0..self.height {
+            let above = if row > 0 { self.row(row - 1) } else { &empty };
+            let below = if row + 1 < self.height {
+                self.row(row + 1)
+            } else {
+                &empty
+            };
+            let here = self.row(row);
+            for w in 0..self.stride {
+                let mut sum = [0u64; 4];
+                for (line, centre) in [(above, true), (here, false), (below, true)] {
+                    let previous = if w > 0 { line[w - 1] } else { 0 };
+                    let next = line.get(w + 1).copied().unwrap_or(0);
+                    // A cell's left neighbour is one bit lower, so shifting up lines them up.
+                    let left = line[w] << 1 | previous >> (BITS - 1);
+                    let right = line[w] >> 1 | next << (BITS - 1);
+                    let inputs = [left, right, if centre { line[w] } else { 0 }];
+                    for mut carry in inputs {
+                        for plane in &mut sum {
+                            let overflow = *plane & carry;
+                            *plane ^= carry;
+                            carry = overflow;
+                        }
+                    }
+                }
+                for (plane, bits) in planes.iter_mut().zip(sum) {
+                    plane.words[row * self.stride + w] = bits;
+                }
+            }
+        }
+        for plane in &mut planes {
+            plane.trim();
+        }
+        NeighbourCounts { planes }
+    }
+}
+
+/// Per-cell neighbour counts from [`BitGrid::neighbour_counts`], held as four bit planes.
+#[derive(Debug, Clone)]
+pub struct NeighbourCounts {
+    planes: [BitGrid; 4],
+}
+
+impl NeighbourCounts {
+    /// The cells with exactly `n` neighbours set.
+    pub fn exactly(&self, n: u8) -> BitGrid {
+        let mut cells = !&BitGrid::new(self.planes[0].width, self.planes[0].height);
+        for (i, plane) in self.planes.iter().enumerate() {
+            cells = if n >> i & 1 == 1 {
+                &cells & plane
+            } else {
+                &cells & &!plane
+            };
+        }
+        cells
+    }
+    /// The cells with fewer than `n` neighbours set.
+    pub fn fewer_than(&self, n: u8) -> BitGrid {
+        let empty = BitGrid::new(self.planes[0].width, self.planes[0].height);
+        (0..n.min(9)).fold(empty, |cells, k| &cells | &self.exactly(k))
+    }
+    pub fn at_least(&self, n: u8) -> BitGrid {
+        !&self.fewer_than(n)
+    }
+}
+
+macro_rules! bitwise {
+    ($trait:ident, $method:ident, $op:tt) => {
+        impl $trait for &BitGrid {
+            type Output = BitGrid;
+            fn $method(self, other: &BitGrid) -> BitGrid {
+                assert_eq!(
+                    (self.width, self.height),
+                    (other.width, other.height),
+                    "grids must be the same size"
+                );
+                let words = self.words.iter().zip(&other.words).map(|(a, b)| a $op b);
+                BitGrid {
+                    width: self.width,
+                    height: self.height,
+                    stride: self.stride,
+                    words: words.collect(),
+                }
+            }
+        }
+    };
+}
+
+bitwise!(BitAnd, bitand, &);
+bitwise!(BitOr, bitor, |);
+bitwise!(BitXor, bitxor, ^);
+
+impl Not for &BitGrid {
+    type Output = BitGrid;
+    fn not(self) -> BitGrid {
+        let mut inverse = BitGrid {
+            width: self.width,
+            height: self.height,
+            stride: self.stride,
+            words: self.words.iter().map(|w| !w).collect(),
+        };
+        inverse.trim();
+        inverse
+    }
+}
+
+impl From<&Grid<bool>> for BitGrid {
+    fn from(grid: &Grid<bool>) -> Self {
+        let mut bits = BitGrid::new(grid.width(), grid.height());
+        for (pos, _) in grid.iter().filter(|&(_, &set)| set) {
+            bits.set(pos, true);
+        }
+        bits
+    }
+}
+
+impl From<&BitGrid> for Grid<bool> {
+    fn from(bits: &BitGrid) -> Self {
+        Grid::from_fn(bits.width, bits.height, |pos| bits.get(pos) == Some(true))
+    }
+}
+
+impl Display for BitGrid {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        for row in 0..self.height {
+            for col in 0..self.width {
+                write!(
+                    f,
+                    "{}",
+                    if self.get((row, col)) == Some(true) {
+                        '#'
+                    } else {
+                        '.'
+                    }
+                )?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use proptest::prelude::*;
+    use rstest::*;
+
+    fn bits(picture: &str) -> BitGrid {
+        BitGrid::from(&Grid::parse(picture, |c| Some(c == '#')).unwrap())
+    }
+
+    #[rstest]
+    fn cells() {
+        let mut grid = BitGrid::new(70, 3);
+        grid.set((1usize, 69usize), true);
+        grid.set((2usize, 0usize), true);
+        grid.toggle((2usize, 0usize));
+        grid.toggle((0usize, 64usize));
+        assert_eq!(grid.get((1usize, 69usize)), Some(true));
+        assert_eq!(grid.get((2usize, 0usize)), Some(false));
+        assert_eq!(grid.get((1usize, 70usize)), None);
+        assert_eq!(grid.get((-1, 0)), None);
+        assert_eq!(grid.count_ones(), 2);
+        assert_eq!(grid.positions().collect::<Vec<_>>(), vec![(0, 64), (1, 69)]);
+    }
+
+    #[rstest]
+    fn rectangles() {
+        // 2015 day 6's first instructions on a grid wide enough to span words.
+        let mut lights = BitGrid::new(1000, 1000);
+        lights.set_rect((0, 0), (999, 999));
+        assert_eq!(lights.count_ones(), 1_000_000);
+        lights.toggle_rect((0, 0), (0, 999));
+        assert_eq!(lights.count_ones(), 999_000);
+        lights.clear_rect((499, 499), (500, 500));
+        assert_eq!(lights.count_ones(), 998_996);
+        lights.toggle_rect((10, 60), (10, 130));
+        assert_eq!(lights.count_ones(), 998_996 - 71);
+        assert_eq!(lights.get((10usize, 59usize)), Some(true));
+        assert_eq!(lights.get((10usize, 131usize)), Some(true));
+    }
+
+    #[rstest]
+    fn neighbours() {
+        let grid = bits("#.#\n.#.\n###");
+        let counts = grid.neighbour_counts();
+        assert_eq!(counts.exactly(5), bits("...\n.#.\n..."));
+        assert_eq!(counts.exactly(4), bits("...\n#.#\n..."));
+        assert_eq!(counts.exactly(2), bits("...\n...\n#.#"));
+        assert_eq!(counts.fewer_than(3), bits("#.#\n...\n#.#"));
+        assert_eq!(counts.at_least(3), bits(".#.\n###\n.#."));
+    }
+
+    #[rstest]
+    fn forklift_access() {
+        // 2025 day 4's example: a roll can be reached with fewer than four rolls around it.
+        let rolls = bits(
+            "..##.####.\n###.#.#.##\n#####.#.##\n#.####..#.\n##.####.##\n\
+             .#######.#\n.#.#.#.###\n#.###.####\n.########.\n#.#.###.#.",
+        );
+        let removable = &rolls & &rolls.neighbour_counts().fewer_than(4);
+        assert_eq!(removable.count_ones(), 13);
+    }
+
+    #[rstest]
+    fn display() {
+        let picture = "#..\n.##\n";
+        assert_eq!(bits(picture).to_string(), picture);
+    }
+
+    fn naive_counts(grid: &Grid<bool>) -> Grid<u8> {
+        Grid::from_fn(grid.width(), grid.height(), |pos| {
+            grid.neighbours8(pos).filter(|&n| grid[n]).count() as u8
+        })
+    }
+
+    proptest! {
+        #[test]
+        fn counts_match_grid(width in 1usize..150, height in 1usize..6, seed: u64) {
+            let mut state = seed | 1;
+            let cells = Grid::from_fn(width, height, |_| {
+                state ^= state << 13;
+                state ^= state >> 7;
+                state ^= state << 17;
+                state & 1 == 1
+            });
+            let bits = BitGrid::from(&cells);
+            prop_assert_eq!(Grid::from(&bits), cells.clone());
+            prop_assert_eq!(bits.count_ones(), cells.iter().filter(|&(_, &c)| c).count());
+            let counts = bits.neighbour_counts();
+            let expected = naive_counts(&cells);
+            for n in 0..=8 {
+                let exactly = Grid::from(&counts.exactly(n));
+                prop_assert_eq!(exactly, expected.map(|&c| c == n));
+            }
+            prop_assert_eq!(Grid::from(&!&bits), cells.map(|&c| !c));
+        }
+    }
+}
@@ -0,0 +1,253 @@
+//! Finding where a repeated step starts going round in circles.
+//!
+//! Every function here iterates `state, step(state), step(step(state)), …`, with `step`
+//! advancing a state in place, so a simulation's own `tick(&mut self)` can be passed directly.
+//! Once a state recurs the sequence is periodic, and any step count can be folded back onto
+//! the first pass round the loop.
+
+use std::collections::HashMap;
+use std::hash::Hash;
+
+/// States from step `start` on repeat every `length` steps.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Cycle {
+    pub start: usize,
+    pub length: usize,
+}
+
+impl Cycle {
+    /// The earliest step whose state matches step `n`.
+    pub fn reduce(&self, n: usize) -> usize {
+        if n < self.start {
+            n
+        } else {
+            self.start + (n - self.start) % self.length
+        }
+    }
+}
+
+/// The states seen up to the first repeat, along with the cycle they fall into.
+#[derive(Debug, Clone)]
+pub struct Orbit<S> {
+    pub cycle: Cycle,
+    states: Vec<S>,
+}
+
+impl<S> Orbit<S> {
+    /// The state after `n` steps.
+    pub fn nth(&self, n: usize) -> &S {
+        &self.states[self.cycle.reduce(n)]
+    }
+    /// Every state before the first repeat, in order.
+    pub fn states(&self) -> &[S] {
+        &self.states
+    }
+    pub fn into_nth(mut self, n: usize) -> S {
+        self.states.swap_remove(self.cycle.reduce(n))
+    }
+}
+
+/// Brent's algorithm, which holds two states and steps each about once per step of the cycle.
+pub fn brent<S: Clone + PartialEq>(initial: S, mut step: impl FnMut(&mut S)) -> Cycle {
+    let mut power = 1;
+    let mut length = 1;
+    let mut tortoise = initial.clone();
+    let mut hare = initial.clone();
+    step(&mut hare);
+    while tortoise != hare {
+        if power == length {
+            tortoise = hare.clone();
+            power *= 2;
+            length = 0;
+        }
+        step(&mut hare);
+        length += 1;
+    }
+
+    let mut tortoise = initial.clone();
+    let mut hare = initial;
+    for _ in 0..length {
+        step(&mut hare);
+    }
+    let mut start = 0;
+    while tortoise != hare {
+        step(&mut tortoise);
+        step(&mut hare);
+        start += 1;
+    }
+    Cycle { start, length }
+}
+
+/// Floyd's tortoise and hare, which holds two states and steps the hare twice as fast.
+pub fn floyd<S: Clone + PartialEq>(initial: S, mut step: impl FnMut(&mut S)) -> Cycle {
+    let mut tortoise = initial.clone();
+    let mut hare = initial.clone();
+    step(&mut tortoise);
+    step(&mut hare);
+    step(&mut hare);
+    while tortoise != hare {
+        step(&mut tortoise);
+        step(&mut hare);
+        step(&mut hare);
+    }
+
+    let mut tortoise = initial;
+    let mut start = 0;
+    while tortoise != hare {
+        step(&mut tortoise);
+        step(&mut hare);
+        start += 1;
+    }
+
+    let mut length = 1;
+    step(&mut hare);
+    while tortoise != hare {
+        step(&mut hare);
+        length += 1;
+    }
+    Cycle { start, length }
+}
+
+/// Remembers every state until a key repeats.
+///
+/// Two states with the same key must step to states with the same key, e.g. a falling-rock
+/// simulation keyed on the shape of its surface rather than everything below it.
+pub fn detect_by_key<S: Clone, K: Hash + Eq>(
+    initial: S,
+    mut step: impl FnMut(&mut S),
+    mut key: impl FnMut(&S) -> K,
+) -> Orbit<S> {
+    let mut seen = HashMap::new();
+    let mut states = Vec::new();
+    let mut state = initial;
+    loop {
+        if let Some(&start) = seen.get(&key(&state)) {
+            let length = states.len() - start;
+            return Orbit {
+                cycle: Cycle { start, length },
+                states,
+            };
+        }
+        seen.insert(key(&state), states.len());
+        states.push(state.clone());
+        step(&mut state);
+    }
+}
+
+/// [`detect_by_key`] keyed on the whole state.
+pub fn detect<S: Clone + Hash + Eq>(initial: S, step: impl FnMut(&mut S)) -> Orbit<S> {
+    detect_by_key(initial, step, S::clone)
+}
+
+/// The state after `n` steps, skipping whole laps of any cycle met on the way.
+pub fn fast_forward<S: Clone + Hash + Eq>(initial: S, step: impl FnMut(&mut S), n: usize) -> S {
+    fast_forward_by_key(initial, step, S::clone, n)
+}
+
+/// [`fast_forward`], with states compared by key as in [`detect_by_key`].
+pub fn fast_forward_by_key<S: Clone, K: Hash + Eq>(
+    initial: S,
+    mut step: impl FnMut(&mut S),
+    mut key: impl FnMut(&S) -> K,
+    n: usize,
+) -> S {
+    let mut seen = HashMap::new();
+    let mut states = Vec::new();
+    let mut state = initial;
+    for i in 0..n {
+        if let Some(&start) = seen.get(&key(&state)) {
+            let cycle = Cycle {
+                start,
+                length: i - start,
+            };
+            return states.swap_remove(cycle.reduce(n));
+        }
+        seen.insert(key(&state), i);
+        states.push(state.clone());
+        step(&mut state);
+    }
+    state
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use rstest::*;
+
+    /// 2, 5, 26, 677, … mod 1009, which runs into a loop after a few steps.
+    fn square_plus_one(x: &mut u64) {
+        *x = (*x * *x + 1) % 1009;
+    }
+
+    fn naive(mut x: u64, n: usize) -> u64 {
+        for _ in 0..n {
+            square_plus_one(&mut x);
+        }
+        x
+    }
+
+    #[rstest]
+    #[case(2)]
+    #[case(3)]
+    #[case(500)]
+    fn detectors_agree(#[case] initial: u64) {
+        let orbit = detect(initial, square_plus_one);
+        assert_eq!(brent(initial, square_plus_one), orbit.cycle);
+        assert_eq!(floyd(initial, square_plus_one), orbit.cycle);
+        let Cycle { start, length } = orbit.cycle;
+        assert_eq!(orbit.states().len(), start + length);
+        assert_eq!(naive(initial, start), naive(initial, start + length));
+        if start > 0 {
+            assert_ne!(
+                naive(initial, start - 1),
+                naive(initial, start + length - 1)
+            );
+        }
+    }
+
+    #[rstest]
+    fn pure_cycle() {
+        let rotate = |x: &mut u8| *x = (*x + 1) % 5;
+        assert_eq!(
+            brent(0, rotate),
+            Cycle {
+                start: 0,
+                length: 5
+            }
+        );
+        assert_eq!(
+            floyd(3, rotate),
+            Cycle {
+                start: 0,
+                length: 5
+            }
+        );
+        assert_eq!(detect(4, rotate).nth(1_000_000_001), &0);
+    }
+
+    #[rstest]
+    #[case(0)]
+    #[case(7)]
+    #[case(1_000_000_000)]
+    fn fast_forwarding(#[case] n: usize) {
+        let expected = detect(2, square_plus_one).into_nth(n);
+        assert_eq!(fast_forward(2, square_plus_one, n), expected);
+        if n < 100 {
+            assert_eq!(naive(2, n), expected);
+        }
+    }
+
+    #[rstest]
+    fn keyed_states() {
+        // The counter is carried along but only the residue decides what happens next.
+        let step = |(x, count): &mut (u64, usize)| {
+            square_plus_one(x);
+            *count += 1;
+        };
+        let orbit = detect_by_key((2, 0), step, |&(x, _)| x);
+        assert_eq!(orbit.cycle, detect(2, square_plus_one).cycle);
+        let (x, count) = fast_forward_by_key((2, 0), step, |&(x, _)| x, 1_000_000);
+        assert_eq!(x, naive(2, 1_000_000));
+        assert_eq!(count, orbit.cycle.reduce(1_000_000));
+    }
+}
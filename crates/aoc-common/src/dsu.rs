@@ -0,0 +1,230 @@
+//! Disjoint sets, for merging things into groups as connections turn up.
+//!
+//! [`UnionFind`] works over dense indices `0..n`; [`KeyedUnionFind`] puts any hashable key in
+//! front of one, for grids of coordinates or named nodes.
+
+use std::collections::HashMap;
+use std::hash::Hash;
+
+/// Union by size with path compression, so every operation is effectively constant time.
+#[derive(Debug, Clone)]
+pub struct UnionFind {
+    parent: Vec<usize>,
+    size: Vec<usize>,
+    sets: usize,
+}
+
+impl UnionFind {
+    /// `n` singleton sets.
+    pub fn new(n: usize) -> Self {
+        Self {
+            parent: (0..n).collect(),
+            size: vec![1; n],
+            sets: n,
+        }
+    }
+    pub fn len(&self) -> usize {
+        self.parent.len()
+    }
+    pub fn is_empty(&self) -> bool {
+        self.parent.is_empty()
+    }
+    /// Adds a new singleton set, returning its index.
+    pub fn push(&mut self) -> usize {
+        let id = self.parent.len();
+        self.parent.push(id);
+        self.size.push(1);
+        self.sets += 1;
+        id
+    }
+    /// The representative of the set holding `x`.
+    pub fn find(&mut self, mut x: usize) -> usize {
+        while self.parent[x] != x {
+            self.parent[x] = self.parent[self.parent[x]];
+            x = self.parent[x];
+        }
+        x
+    }
+    /// Merges the sets holding `a` and `b`, returning false if they were already one.
+    pub fn union(&mut self, a: usize, b: usize) -> bool {
+        let (mut a, mut b) = (self.find(a), self.find(b));
+        if a == b {
+            return false;
+        }
+        if self.size[a] < self.size[b] {
+            (a, b) = (b, a);
+        }
+        self.parent[b] = a;
+        self.size[a] += self.size[b];
+        self.sets -= 1;
+        true
+    }
+    pub fn same(&mut self, a: usize, b: usize) -> bool {
+        self.find(a) == self.find(b)
+    }
+    /// How many elements share a set with `x`.
+    pub fn size(&mut self, x: usize) -> usize {
+        let root = self.find(x);
+        self.size[root]
+    }
+    /// How many disjoint sets there are.
+    pub fn count(&self) -> usize {
+        self.sets
+    }
+    /// Every set, each in ascending order, ordered by their smallest element.
+    pub fn components(&mut self) -> Vec<Vec<usize>> {
+        let mut slots = HashMap::new();
+        let mut components: Vec<Vec<usize>> = Vec::with_capacity(self.sets);
+        for x in 0..self.len() {
+            let root = self.find(x);
+            let slot = *slots.entry(root).or_insert_with(|| {
+                components.push(Vec::new());
+                components.len() - 1
+            });
+            components[slot].push(x);
+        }
+        components
+    }
+}
+
+/// A [`UnionFind`] over keys, each added the first time it's seen.
+#[derive(Debug, Clone)]
+pub struct KeyedUnionFind<K> {
+    keys: Vec<K>,
+    ids: HashMap<K, usize>,
+    sets: UnionFind,
+}
+
+impl<K> Default for KeyedUnionFind<K> {
+    fn default() -> Self {
+        Self {
+            keys: Vec::new(),
+            ids: HashMap::new(),
+            sets: UnionFind::new(0),
+        }
+    }
+}
+
+impl<K: Clone + Hash + Eq> KeyedUnionFind<K> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+    pub fn len(&self) -> usize {
+        self.keys.len()
+    }
+    pub fn is_empty(&self) -> bool {
+        self.keys.is_empty()
+    }
+    pub fn contains(&self, key: &K) -> bool {
+        self.ids.contains_key(key)
+    }
+    /// The index of `key`, adding it as a singleton set if it's new.
+    pub fn insert(&mut self, key: K) -> usize {
+        if let Some(&id) = self.ids.get(&key) {
+            return id;
+        }
+        let id = self.sets.push();
+        self.keys.push(key.clone());
+        self.ids.insert(key, id);
+        id
+    }
+    /// Merges the sets holding `a` and `b`, adding either if it's new.
+    pub fn union(&mut self, a: K, b: K) -> bool {
+        let (a, b) = (self.insert(a), self.insert(b));
+        self.sets.union(a, b)
+    }
+    /// The key representing the set holding `key`, if it's been added.
+    pub fn find(&mut self, key: &K) -> Option<&K> {
+        let id = *self.ids.get(key)?;
+        let root = self.sets.find(id);
+        Some(&self.keys[root])
+    }
+    /// Whether both keys have been added and are in the same set.
+    pub fn same(&mut self, a: &K, b: &K) -> bool {
+        match (self.ids.get(a), self.ids.get(b)) {
+            (Some(&a), Some(&b)) => self.sets.same(a, b),
+            _ => false,
+        }
+    }
+    /// How many keys share a set with `key`, or 0 if it hasn't been added.
+    pub fn size(&mut self, key: &K) -> usize {
+        self.ids.get(key).map_or(0, |&id| self.sets.size(id))
+    }
+    pub fn count(&self) -> usize {
+        self.sets.count()
+    }
+    /// Every set, with keys in the order they were added.
+    pub fn components(&mut self) -> Vec<Vec<K>> {
+        self.sets
+            .components()
+            .into_iter()
+            .map(|ids| ids.into_iter().map(|id| self.keys[id].clone()).collect())
+            .collect()
+    }
+}
+
+impl<K: Clone + Hash + Eq> FromIterator<K> for KeyedUnionFind<K> {
+    /// Singleton sets of the given keys.
+    fn from_iter<I: IntoIterator<Item = K>>(iter: I) -> Self {
+        let mut sets = Self::new();
+        for key in iter {
+            sets.insert(key);
+        }
+        sets
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use rstest::*;
+
+    #[rstest]
+    fn dense() {
+        let mut sets = UnionFind::new(7);
+        assert!(sets.union(0, 2));
+        assert!(sets.union(2, 4));
+        assert!(sets.union(6, 5));
+        assert!(!sets.union(4, 0));
+        assert!(sets.same(0, 4));
+        assert!(!sets.same(0, 5));
+        assert_eq!(sets.size(2), 3);
+        assert_eq!(sets.count(), 4);
+        assert_eq!(
+            sets.components(),
+            vec![vec![0, 2, 4], vec![1], vec![3], vec![5, 6]]
+        );
+        let extra = sets.push();
+        sets.union(extra, 3);
+        assert_eq!(sets.size(3), 2);
+        assert_eq!(sets.count(), 4);
+    }
+
+    #[rstest]
+    fn keyed() {
+        // Pipes between programs: 0 reaches 2, 3, 4, 5 and 6 but 1 only itself.
+        let pipes = [(0, 2), (2, 3), (2, 4), (3, 4), (4, 6), (5, 6), (1, 1)];
+        let mut programs = KeyedUnionFind::new();
+        for (a, b) in pipes {
+            programs.union(a, b);
+        }
+        assert_eq!(programs.size(&0), 6);
+        assert_eq!(programs.size(&9), 0);
+        assert_eq!(programs.count(), 2);
+        assert!(programs.same(&5, &3));
+        assert!(!programs.same(&1, &9));
+        let root = *programs.find(&6).unwrap();
+        assert_eq!(programs.find(&0), Some(&root));
+        assert_eq!(programs.components(), vec![vec![0, 2, 3, 4, 6, 5], vec![1]]);
+    }
+
+    #[rstest]
+    fn from_keys() {
+        let mut letters: KeyedUnionFind<char> = "abcde".chars().collect();
+        letters.union('a', 'e');
+        letters.union('f', 'b');
+        assert_eq!(letters.len(), 6);
+        assert_eq!(letters.count(), 4);
+        assert!(letters.contains(&'f'));
+    }
+}
@@ -1,6 +1,8 @@
 #![allow(dead_code)]
 use std::path::Path;
+use strum::{EnumIter, IntoEnumIterator};
 
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, EnumIter)]
 pub enum Event {
     Event2015,
     Event2016,
@@ -15,6 +17,7 @@ pub enum Event {
     Event2025,
 }
 
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, EnumIter)]
 pub enum Day {
     Day1,
     Day2,
@@ -43,6 +46,7 @@ pub enum Day {
     Day25,
 }
 
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, EnumIter)]
 pub enum Input {
     Example1,
     Example2,
@@ -50,6 +54,19 @@ pub enum Input {
     Part2,
 }
 
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, EnumIter)]
+pub enum Part {
+    Part1,
+    Part2,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, EnumIter)]
+pub enum Status {
+    Unimplemented,
+    Part1,
+    Complete,
+}
+
 impl Event {
     pub fn folder_name(&self) -> &Path {
         let name = match self {
@@ -67,6 +84,24 @@ impl Event {
         };
         Path::new(name)
     }
+    pub fn from_year(year: u16) -> Option<Self> {
+        Self::iter().find(|e| e.year() == year)
+    }
+    pub fn year(&self) -> u16 {
+        match self {
+            Event::Event2015 => 2015,
+            Event::Event2016 => 2016,
+            Event::Event2017 => 2017,
+            Event::Event2018 => 2018,
+            Event::Event2019 => 2019,
+            Event::Event2020 => 2020,
+            Event::Event2021 => 2021,
+            Event::Event2022 => 2022,
+            Event::Event2023 => 2023,
+            Event::Event2024 => 2024,
+            Event::Event2025 => 2025,
+        }
+    }
 }
 
 impl Day {
@@ -100,6 +135,38 @@ impl Day {
         };
         Path::new(name)
     }
+    pub fn from_number(number: u8) -> Option<Self> {
+        Self::iter().find(|d| d.number() == number)
+    }
+    pub fn number(&self) -> u8 {
+        match self {
+            Day::Day1 => 1,
+            Day::Day2 => 2,
+            Day::Day3 => 3,
+            Day::Day4 => 4,
+            Day::Day5 => 5,
+            Day::Day6 => 6,
+            Day::Day7 => 7,
+            Day::Day8 => 8,
+            Day::Day9 => 9,
+            Day::Day10 => 10,
+            Day::Day11 => 11,
+            Day::Day12 => 12,
+            Day::Day13 => 13,
+            Day::Day14 => 14,
+            Day::Day15 => 15,
+            Day::Day16 => 16,
+            Day::Day17 => 17,
+            Day::Day18 => 18,
+            Day::Day19 => 19,
+            Day::Day20 => 20,
+            Day::Day21 => 21,
+            Day::Day22 => 22,
+            Day::Day23 => 23,
+            Day::Day24 => 24,
+            Day::Day25 => 25,
+        }
+    }
 }
 
 impl Input {
@@ -112,4 +179,50 @@ impl Input {
         };
         Path::new(name)
     }
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::iter().find(|i| i.name() == name)
+    }
+    pub fn name(&self) -> &str {
+        match self {
+            Input::Example1 => "example1",
+            Input::Example2 => "example2",
+            Input::Part1 => "part1",
+            Input::Part2 => "part2",
+        }
+    }
+    /// The part each input is checked against by convention.
+    pub fn part(&self) -> Part {
+        match self {
+            Input::Example1 | Input::Part1 => Part::Part1,
+            Input::Example2 | Input::Part2 => Part::Part2,
+        }
+    }
+}
+
+impl Part {
+    pub fn from_number(number: u8) -> Option<Self> {
+        Self::iter().find(|p| p.number() == number)
+    }
+    pub fn number(&self) -> u8 {
+        match self {
+            Part::Part1 => 1,
+            Part::Part2 => 2,
+        }
+    }
+}
+
+impl Status {
+    pub fn solves(&self, part: Part) -> bool {
+        match part {
+            Part::Part1 => *self != Status::Unimplemented,
+            Part::Part2 => *self == Status::Complete,
+        }
+    }
+    pub fn stars(&self) -> usize {
+        match self {
+            Status::Unimplemented => 0,
+            Status::Part1 => 1,
+            Status::Complete => 2,
+        }
+    }
 }
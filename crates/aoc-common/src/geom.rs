@@ -0,0 +1,487 @@
+//! Points, vectors and compass directions on the integer plane.
+//!
+//! `y` grows downwards, as rows do in puzzle inputs, so [`Direction4::Up`] is `(0, -1)`. A
+//! [`Point2`] with `x` as the column and `y` as the row also indexes a [`Grid`](crate::grid::Grid).
+
+use crate::grid::GridIndex;
+use std::fmt::{Display, Formatter};
+use std::ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign};
+use strum::EnumIter;
+
+/// `|a - b|`, without needing a signed type.
+fn abs_diff<T: Copy + PartialOrd + Sub<Output = T>>(a: T, b: T) -> T {
+    if a > b { a - b } else { b - a }
+}
+
+/// A position on the plane.
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Point2<T> {
+    pub x: T,
+    pub y: T,
+}
+
+/// A displacement between two [`Point2`]s.
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Vec2<T> {
+    pub x: T,
+    pub y: T,
+}
+
+/// A position in space.
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Point3<T> {
+    pub x: T,
+    pub y: T,
+    pub z: T,
+}
+
+impl<T> Point2<T> {
+    pub const fn new(x: T, y: T) -> Self {
+        Self { x, y }
+    }
+    /// The same point in a wider type, e.g. `i32` to `i64`.
+    pub fn convert<U: From<T>>(self) -> Point2<U> {
+        Point2::new(self.x.into(), self.y.into())
+    }
+    /// The same point in a narrower type, if it fits.
+    pub fn try_convert<U: TryFrom<T>>(self) -> Option<Point2<U>> {
+        Some(Point2::new(
+            self.x.try_into().ok()?,
+            self.y.try_into().ok()?,
+        ))
+    }
+}
+
+impl<T: Copy + PartialOrd + Add<Output = T> + Sub<Output = T>> Point2<T> {
+    /// Steps between the points, moving only orthogonally.
+    pub fn manhattan(self, other: Self) -> T {
+        abs_diff(self.x, other.x) + abs_diff(self.y, other.y)
+    }
+    /// Steps between the points, moving diagonally as well.
+    pub fn chebyshev(self, other: Self) -> T {
+        let (dx, dy) = (abs_diff(self.x, other.x), abs_diff(self.y, other.y));
+        if dx > dy { dx } else { dy }
+    }
+}
+
+impl<T> Vec2<T> {
+    pub const fn new(x: T, y: T) -> Self {
+        Self { x, y }
+    }
+    pub fn convert<U: From<T>>(self) -> Vec2<U> {
+        Vec2::new(self.x.into(), self.y.into())
+    }
+    pub fn try_convert<U: TryFrom<T>>(self) -> Option<Vec2<U>> {
+        Some(Vec2::new(self.x.try_into().ok()?, self.y.try_into().ok()?))
+    }
+}
+
+impl<T: Copy + Default + PartialOrd + Add<Output = T> + Sub<Output = T>> Vec2<T> {
+    pub fn manhattan(self) -> T {
+        Point2::default().manhattan(Point2::new(self.x, self.y))
+    }
+    pub fn chebyshev(self) -> T {
+        Point2::default().chebyshev(Point2::new(self.x, self.y))
+    }
+}
+
+impl<T> Point3<T> {
+    pub const fn new(x: T, y: T, z: T) -> Self {
+        Self { x, y, z }
+    }
+    pub fn convert<U: From<T>>(self) -> Point3<U> {
+        Point3::new(self.x.into(), self.y.into(), self.z.into())
+    }
+    pub fn try_convert<U: TryFrom<T>>(self) -> Option<Point3<U>> {
+        let (x, y, z) = (self.x.try_into(), self.y.try_into(), self.z.try_into());
+        Some(Point3::new(x.ok()?, y.ok()?, z.ok()?))
+    }
+}
+
+impl<T: Copy + PartialOrd + Add<Output = T> + Sub<Output = T>> Point3<T> {
+    pub fn manhattan(self, other: Self) -> T {
+        abs_diff(self.x, other.x) + abs_diff(self.y, other.y) + abs_diff(self.z, other.z)
+    }
+    pub fn chebyshev(self, other: Self) -> T {
+        let max = |a: T, b: T| if a > b { a } else { b };
+        let dx = abs_diff(self.x, other.x);
+        max(
+            max(dx, abs_diff(self.y, other.y)),
+            abs_diff(self.z, other.z),
+        )
+    }
+}
+
+impl<T: Add<Output = T>> Add<Vec2<T>> for Point2<T> {
+    type Output = Self;
+
+    fn add(self, v: Vec2<T>) -> Self::Output {
+        Self::new(self.x + v.x, self.y + v.y)
+    }
+}
+
+impl<T: Sub<Output = T>> Sub<Vec2<T>> for Point2<T> {
+    type Output = Self;
+
+    fn sub(self, v: Vec2<T>) -> Self::Output {
+        Self::new(self.x - v.x, self.y - v.y)
+    }
+}
+
+impl<T: Sub<Output = T>> Sub for Point2<T> {
+    type Output = Vec2<T>;
+
+    fn sub(self, other: Self) -> Self::Output {
+        Vec2::new(self.x - other.x, self.y - other.y)
+    }
+}
+
+impl<T: AddAssign> AddAssign<Vec2<T>> for Point2<T> {
+    fn add_assign(&mut self, v: Vec2<T>) {
+        self.x += v.x;
+        self.y += v.y;
+    }
+}
+
+impl<T: SubAssign> SubAssign<Vec2<T>> for Point2<T> {
+    fn sub_assign(&mut self, v: Vec2<T>) {
+        self.x -= v.x;
+        self.y -= v.y;
+    }
+}
+
+impl<T: Add<Output = T>> Add for Vec2<T> {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self::Output {
+        Self::new(self.x + other.x, self.y + other.y)
+    }
+}
+
+impl<T: Sub<Output = T>> Sub for Vec2<T> {
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self::Output {
+        Self::new(self.x - other.x, self.y - other.y)
+    }
+}
+
+impl<T: AddAssign> AddAssign for Vec2<T> {
+    fn add_assign(&mut self, other: Self) {
+        self.x += other.x;
+        self.y += other.y;
+    }
+}
+
+impl<T: Copy + Mul<Output = T>> Mul<T> for Vec2<T> {
+    type Output = Self;
+
+    fn mul(self, k: T) -> Self::Output {
+        Self::new(self.x * k, self.y * k)
+    }
+}
+
+impl<T: Neg<Output = T>> Neg for Vec2<T> {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        Self::new(-self.x, -self.y)
+    }
+}
+
+impl<T: Add<Output = T>> Add for Point3<T> {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self::Output {
+        Self::new(self.x + other.x, self.y + other.y, self.z + other.z)
+    }
+}
+
+impl<T: Sub<Output = T>> Sub for Point3<T> {
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self::Output {
+        Self::new(self.x - other.x, self.y - other.y, self.z - other.z)
+    }
+}
+
+impl<T> From<(T, T)> for Point2<T> {
+    fn from((x, y): (T, T)) -> Self {
+        Self::new(x, y)
+    }
+}
+
+impl<T> From<Point2<T>> for (T, T) {
+    fn from(p: Point2<T>) -> Self {
+        (p.x, p.y)
+    }
+}
+
+impl<T> From<(T, T)> for Vec2<T> {
+    fn from((x, y): (T, T)) -> Self {
+        Self::new(x, y)
+    }
+}
+
+impl<T> From<(T, T, T)> for Point3<T> {
+    fn from((x, y, z): (T, T, T)) -> Self {
+        Self::new(x, y, z)
+    }
+}
+
+impl<T: Display> Display for Point2<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "({}, {})", self.x, self.y)
+    }
+}
+
+/// Column `x`, row `y`.
+impl<T: Copy + TryInto<usize>> GridIndex for Point2<T> {
+    fn row_col(self) -> Option<(usize, usize)> {
+        Some((self.y.try_into().ok()?, self.x.try_into().ok()?))
+    }
+}
+
+/// Which way to turn, a quarter turn for [`Direction4`] and an eighth for [`Direction8`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum Turn {
+    Left,
+    Right,
+}
+
+/// The orthogonal directions, clockwise from up.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, EnumIter)]
+pub enum Direction4 {
+    Up,
+    Right,
+    Down,
+    Left,
+}
+
+impl Direction4 {
+    const ALL: [Self; 4] = [Self::Up, Self::Right, Self::Down, Self::Left];
+
+    fn rotated(self, quarters: usize) -> Self {
+        Self::ALL[(self as usize + quarters) % 4]
+    }
+    pub fn turn_right(self) -> Self {
+        self.rotated(1)
+    }
+    pub fn turn_left(self) -> Self {
+        self.rotated(3)
+    }
+    pub fn turn(self, turn: Turn) -> Self {
+        match turn {
+            Turn::Left => self.turn_left(),
+            Turn::Right => self.turn_right(),
+        }
+    }
+    pub fn opposite(self) -> Self {
+        self.rotated(2)
+    }
+    /// One step this way, in any signed type.
+    pub fn delta<T: From<i8>>(self) -> Vec2<T> {
+        let (x, y) = match self {
+            Self::Up => (0, -1),
+            Self::Right => (1, 0),
+            Self::Down => (0, 1),
+            Self::Left => (-1, 0),
+        };
+        Vec2::new(T::from(x), T::from(y))
+    }
+}
+
+/// Arrows (`^>v<`) or initials (`URDL`, `NESW`).
+impl TryFrom<char> for Direction4 {
+    type Error = char;
+
+    fn try_from(c: char) -> Result<Self, Self::Error> {
+        match c {
+            '^' | 'U' | 'N' => Ok(Self::Up),
+            '>' | 'R' | 'E' => Ok(Self::Right),
+            'v' | 'D' | 'S' => Ok(Self::Down),
+            '<' | 'L' | 'W' => Ok(Self::Left),
+            _ => Err(c),
+        }
+    }
+}
+
+/// The orthogonal and diagonal directions, clockwise from up.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, EnumIter)]
+pub enum Direction8 {
+    Up,
+    UpRight,
+    Right,
+    DownRight,
+    Down,
+    DownLeft,
+    Left,
+    UpLeft,
+}
+
+impl Direction8 {
+    const ALL: [Self; 8] = [
+        Self::Up,
+        Self::UpRight,
+        Self::Right,
+        Self::DownRight,
+        Self::Down,
+        Self::DownLeft,
+        Self::Left,
+        Self::UpLeft,
+    ];
+
+    fn rotated(self, eighths: usize) -> Self {
+        Self::ALL[(self as usize + eighths) % 8]
+    }
+    pub fn turn_right(self) -> Self {
+        self.rotated(1)
+    }
+    pub fn turn_left(self) -> Self {
+        self.rotated(7)
+    }
+    pub fn turn(self, turn: Turn) -> Self {
+        match turn {
+            Turn::Left => self.turn_left(),
+            Turn::Right => self.turn_right(),
+        }
+    }
+    pub fn opposite(self) -> Self {
+        self.rotated(4)
+    }
+    pub fn is_diagonal(self) -> bool {
+        self as usize % 2 == 1
+    }
+    pub fn delta<T: From<i8>>(self) -> Vec2<T> {
+        let (x, y) = match self {
+            Self::Up => (0, -1),
+            Self::UpRight => (1, -1),
+            Self::Right => (1, 0),
+            Self::DownRight => (1, 1),
+            Self::Down => (0, 1),
+            Self::DownLeft => (-1, 1),
+            Self::Left => (-1, 0),
+            Self::UpLeft => (-1, -1),
+        };
+        Vec2::new(T::from(x), T::from(y))
+    }
+}
+
+impl From<Direction4> for Direction8 {
+    fn from(d: Direction4) -> Self {
+        match d {
+            Direction4::Up => Self::Up,
+            Direction4::Right => Self::Right,
+            Direction4::Down => Self::Down,
+            Direction4::Left => Self::Left,
+        }
+    }
+}
+
+/// Only the orthogonal directions convert back.
+impl TryFrom<Direction8> for Direction4 {
+    type Error = Direction8;
+
+    fn try_from(d: Direction8) -> Result<Self, Self::Error> {
+        match d {
+            Direction8::Up => Ok(Self::Up),
+            Direction8::Right => Ok(Self::Right),
+            Direction8::Down => Ok(Self::Down),
+            Direction8::Left => Ok(Self::Left),
+            _ => Err(d),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::grid::Grid;
+    use rstest::*;
+    use strum::IntoEnumIterator;
+
+    #[rstest]
+    fn arithmetic() {
+        let (a, b) = (Point2::new(1, 2), Point2::new(4, -2));
+        let v = b - a;
+        assert_eq!(v, Vec2::new(3, -4));
+        assert_eq!(a + v, b);
+        assert_eq!(b - v, a);
+        assert_eq!(v * 2 + -v, v);
+        let mut c = a;
+        c += v;
+        c -= Vec2::new(1, 1);
+        assert_eq!(c, Point2::new(3, -3));
+        assert_eq!(
+            Point3::new(1, 2, 3) + Point3::new(1, 1, 1),
+            Point3::new(2, 3, 4)
+        );
+    }
+
+    #[rstest]
+    #[case(Point2::new(1, 2), Point2::new(4, -2), 7, 4)]
+    #[case(Point2::new(0, 0), Point2::new(0, 0), 0, 0)]
+    #[case(Point2::new(-3, 5), Point2::new(3, 5), 6, 6)]
+    fn distances(
+        #[case] a: Point2<i32>,
+        #[case] b: Point2<i32>,
+        #[case] manhattan: i32,
+        #[case] chebyshev: i32,
+    ) {
+        assert_eq!((a.manhattan(b), a.chebyshev(b)), (manhattan, chebyshev));
+        assert_eq!(
+            (b.manhattan(a), (b - a).chebyshev()),
+            (manhattan, chebyshev)
+        );
+    }
+
+    #[rstest]
+    fn unsigned_distances() {
+        let (a, b) = (Point2::<usize>::new(5, 1), Point2::new(2, 3));
+        assert_eq!((a.manhattan(b), a.chebyshev(b)), (5, 3));
+        let (p, q) = (Point3::<u8>::new(0, 9, 2), Point3::new(3, 1, 2));
+        assert_eq!((p.manhattan(q), p.chebyshev(q)), (11, 8));
+    }
+
+    #[rstest]
+    fn turns() {
+        for d in Direction4::iter() {
+            assert_eq!(d.turn_left().turn_right(), d);
+            assert_eq!(d.turn_right().turn_right(), d.opposite());
+            assert_eq!(d.delta::<i32>() + d.opposite().delta(), Vec2::new(0, 0));
+        }
+        for d in Direction8::iter() {
+            assert_eq!(d.turn(Turn::Left).turn(Turn::Right), d);
+            assert_eq!(d.delta::<i64>(), -d.opposite().delta());
+            assert_eq!(d.delta::<i64>().chebyshev(), 1);
+        }
+        assert_eq!(Direction4::Up.turn(Turn::Left), Direction4::Left);
+        assert_eq!(Direction8::Up.turn_right(), Direction8::UpRight);
+    }
+
+    #[rstest]
+    fn conversions() {
+        let p = Point2::<i32>::new(-1, 7);
+        assert_eq!(p.convert::<i64>(), Point2::new(-1i64, 7));
+        assert_eq!(p.try_convert::<usize>(), None);
+        assert_eq!(Point2::from((2, 3)), Point2::new(2, 3));
+        assert_eq!(<(i32, i32)>::from(p), (-1, 7));
+        assert_eq!(Direction4::try_from('v'), Ok(Direction4::Down));
+        assert_eq!(Direction4::try_from('?'), Err('?'));
+        let diagonals = Direction8::iter().filter(|d| d.is_diagonal()).count();
+        assert_eq!(diagonals, 4);
+        for d in Direction4::iter() {
+            assert_eq!(Direction4::try_from(Direction8::from(d)), Ok(d));
+        }
+        assert!(Direction4::try_from(Direction8::UpLeft).is_err());
+    }
+
+    #[rstest]
+    fn indexes_grids() -> crate::error::Result<()> {
+        let grid = Grid::parse("ab\ncd", Some)?;
+        assert_eq!(grid[Point2::new(1, 0)], 'b');
+        assert_eq!(grid.get(Point2::new(0i64, 1)), Some(&'c'));
+        assert_eq!(grid.get(Point2::new(-1i64, 1)), None);
+        Ok(())
+    }
+}
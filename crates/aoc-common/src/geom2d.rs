@@ -0,0 +1,318 @@
+//! Exact computational geometry on integer points.
+//!
+//! Polygons are slices of vertices in order, closed implicitly from the last back to the first.
+//! Like the rest of [`geom`](crate::geom), `y` grows downwards, so "clockwise" is as drawn on
+//! screen. Nothing here goes through floats: areas are kept doubled and intersections are
+//! [`Rational`].
+
+use crate::geom::{Point2, Vec2};
+use crate::linear::Rational;
+use crate::math::gcd;
+use std::cmp::Ordering;
+
+type Point = Point2<i64>;
+type Vector = Vec2<i64>;
+
+/// The z component of `a × b`, positive when `b` is clockwise of `a`.
+pub fn cross(a: Vector, b: Vector) -> i64 {
+    a.x * b.y - a.y * b.x
+}
+
+pub fn dot(a: Vector, b: Vector) -> i64 {
+    a.x * b.x + a.y * b.y
+}
+
+fn edges(vertices: &[Point]) -> impl Iterator<Item = (Point, Point)> + '_ {
+    vertices
+        .iter()
+        .copied()
+        .zip(vertices.iter().copied().cycle().skip(1))
+}
+
+/// Twice the signed area by the shoelace formula: positive when the vertices run clockwise.
+pub fn signed_double_area(vertices: &[Point]) -> i64 {
+    edges(vertices).map(|(a, b)| a.x * b.y - b.x * a.y).sum()
+}
+
+/// Twice the area, which is always a whole number for lattice polygons.
+pub fn double_area(vertices: &[Point]) -> i64 {
+    signed_double_area(vertices).abs()
+}
+
+/// Lattice points on the edges, counting each vertex once.
+pub fn boundary_points(vertices: &[Point]) -> i64 {
+    edges(vertices)
+        .map(|(a, b)| gcd(a.x.abs_diff(b.x), a.y.abs_diff(b.y)) as i64)
+        .sum()
+}
+
+/// Lattice points strictly inside, by Pick's theorem `A = I + B/2 - 1`.
+pub fn interior_points(vertices: &[Point]) -> i64 {
+    (double_area(vertices) - boundary_points(vertices) + 2) / 2
+}
+
+/// Lattice points inside or on the boundary, e.g. the cells a dug-out trench encloses.
+pub fn lattice_points(vertices: &[Point]) -> i64 {
+    interior_points(vertices) + boundary_points(vertices)
+}
+
+/// Where the lines `p + t r` and `q + u s` cross, as `(t, u)`; `None` if they're parallel.
+///
+/// Restricting `t` and `u` to `[0, 1]` gives segments, and to `t, u >= 0` rays.
+pub fn crossing(p: Point, r: Vector, q: Point, s: Vector) -> Option<(Rational, Rational)> {
+    let denominator = cross(r, s) as i128;
+    if denominator == 0 {
+        return None;
+    }
+    let pq = q - p;
+    Some((
+        Rational::new(cross(pq, s) as i128, denominator),
+        Rational::new(cross(pq, r) as i128, denominator),
+    ))
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct Segment {
+    pub start: Point,
+    pub end: Point,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Intersection {
+    Point(Point2<Rational>),
+    /// Collinear segments sharing more than a point.
+    Overlap(Segment),
+}
+
+impl Segment {
+    pub fn new(start: Point, end: Point) -> Self {
+        Self { start, end }
+    }
+    pub fn direction(&self) -> Vector {
+        self.end - self.start
+    }
+    /// Where two segments, endpoints included, meet.
+    pub fn intersection(&self, other: &Segment) -> Option<Intersection> {
+        let (r, s) = (self.direction(), other.direction());
+        let unit = Rational::ZERO..=Rational::ONE;
+        if let Some((t, u)) = crossing(self.start, r, other.start, s) {
+            if !unit.contains(&t) || !unit.contains(&u) {
+                return None;
+            }
+            let along = |start: i64, delta: i64| Rational::from(start) + t * Rational::from(delta);
+            let point = Point2::new(along(self.start.x, r.x), along(self.start.y, r.y));
+            return Some(Intersection::Point(point));
+        }
+        // Parallel, or one is a single point: check against whichever direction isn't zero.
+        let zero = Vec2::new(0, 0);
+        let (origin, direction) = if r != zero {
+            (self.start, r)
+        } else {
+            (other.start, s)
+        };
+        if direction == zero {
+            return (self.start == other.start).then(|| exact(self.start));
+        }
+        let endpoints = [self.start, self.end, other.start, other.end];
+        if endpoints.iter().any(|&p| cross(p - origin, direction) != 0) {
+            return None;
+        }
+        // Collinear: order the endpoints along the shared line and clip.
+        let key = |p: &Point| dot(*p - self.start, direction);
+        let ordered = |a: Point, b: Point| if key(&a) <= key(&b) { (a, b) } else { (b, a) };
+        let (a0, a1) = ordered(self.start, self.end);
+        let (b0, b1) = ordered(other.start, other.end);
+        let low = if key(&a0) >= key(&b0) { a0 } else { b0 };
+        let high = if key(&a1) <= key(&b1) { a1 } else { b1 };
+        match key(&low).cmp(&key(&high)) {
+            Ordering::Greater => None,
+            Ordering::Equal => Some(exact(low)),
+            Ordering::Less => Some(Intersection::Overlap(Segment::new(low, high))),
+        }
+    }
+}
+
+fn exact(p: Point) -> Intersection {
+    Intersection::Point(Point2::new(Rational::from(p.x), Rational::from(p.y)))
+}
+
+/// The shortest vector with the same direction, so every point along a line of sight maps to
+/// the same key: `(4, -6)` becomes `(2, -3)`.
+pub fn primitive(v: Vector) -> Vector {
+    match gcd(v.x.unsigned_abs(), v.y.unsigned_abs()) as i64 {
+        0 => v,
+        g => Vec2::new(v.x / g, v.y / g),
+    }
+}
+
+/// Orders directions clockwise starting from straight up, exactly.
+///
+/// Vectors pointing the same way compare equal, whatever their lengths.
+pub fn clockwise_cmp(a: Vector, b: Vector) -> Ordering {
+    // The half from up (inclusive) round to down (exclusive) comes first.
+    let half = |v: Vector| !(v.x > 0 || (v.x == 0 && v.y < 0));
+    half(a).cmp(&half(b)).then_with(|| 0.cmp(&cross(a, b)))
+}
+
+/// Sorts directions clockwise from straight up, keeping equal directions in their given order.
+pub fn sort_clockwise(vectors: &mut [Vector]) {
+    vectors.sort_by(|&a, &b| clockwise_cmp(a, b));
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use rstest::*;
+
+    fn polygon(points: &[(i64, i64)]) -> Vec<Point> {
+        points.iter().map(|&p| p.into()).collect()
+    }
+
+    #[rstest]
+    fn square() {
+        let square = polygon(&[(0, 0), (4, 0), (4, 4), (0, 4)]);
+        assert_eq!(signed_double_area(&square), 32);
+        assert_eq!(boundary_points(&square), 16);
+        assert_eq!(interior_points(&square), 9);
+        assert_eq!(lattice_points(&square), 25);
+        let reversed = square.iter().rev().copied().collect::<Vec<_>>();
+        assert_eq!(signed_double_area(&reversed), -32);
+    }
+
+    #[rstest]
+    fn half_unit_triangle() {
+        let triangle = polygon(&[(0, 0), (1, 0), (0, 1)]);
+        assert_eq!(double_area(&triangle), 1);
+        assert_eq!(interior_points(&triangle), 0);
+        assert_eq!(lattice_points(&triangle), 3);
+    }
+
+    #[rstest]
+    fn lagoon() {
+        // The trench from 2023 day 18's example, as corner points.
+        let corners = polygon(&[
+            (0, 0),
+            (6, 0),
+            (6, 5),
+            (4, 5),
+            (4, 7),
+            (6, 7),
+            (6, 9),
+            (1, 9),
+            (1, 7),
+            (0, 7),
+            (0, 5),
+            (2, 5),
+            (2, 2),
+            (0, 2),
+        ]);
+        assert_eq!(lattice_points(&corners), 62);
+    }
+
+    fn segment(a: (i64, i64), b: (i64, i64)) -> Segment {
+        Segment::new(a.into(), b.into())
+    }
+
+    fn at(x: i64, y: i64) -> Option<Intersection> {
+        Some(exact(Point2::new(x, y)))
+    }
+
+    #[rstest]
+    #[case(segment((0, 0), (4, 4)), segment((0, 4), (4, 0)), at(2, 2))]
+    #[case(segment((0, 0), (4, 0)), segment((4, 0), (4, 3)), at(4, 0))]
+    #[case(segment((0, 0), (4, 0)), segment((5, 1), (5, -1)), None)]
+    #[case(segment((0, 0), (4, 0)), segment((0, 1), (4, 1)), None)]
+    #[case(segment((0, 0), (4, 0)), segment((6, 0), (2, 0)), Some(Intersection::Overlap(segment((2, 0), (4, 0)))))]
+    #[case(segment((0, 0), (2, 2)), segment((4, 4), (2, 2)), at(2, 2))]
+    #[case(segment((0, 0), (2, 2)), segment((3, 3), (5, 5)), None)]
+    #[case(segment((5, 5), (5, 5)), segment((0, 0), (10, 0)), None)]
+    #[case(segment((5, 0), (5, 0)), segment((0, 0), (10, 0)), at(5, 0))]
+    #[case(segment((5, 0), (5, 0)), segment((5, 0), (5, 0)), at(5, 0))]
+    #[case(segment((5, 0), (5, 0)), segment((5, 1), (5, 1)), None)]
+    fn segments(#[case] a: Segment, #[case] b: Segment, #[case] expected: Option<Intersection>) {
+        assert_eq!(a.intersection(&b), expected);
+        match expected {
+            Some(Intersection::Overlap(_)) => assert!(b.intersection(&a).is_some()),
+            _ => assert_eq!(b.intersection(&a), expected),
+        }
+    }
+
+    #[rstest]
+    fn fractional_crossing() {
+        let a = segment((0, 0), (1, 1));
+        let b = segment((0, 1), (2, 0));
+        let third = Rational::new(2, 3);
+        assert_eq!(
+            a.intersection(&b),
+            Some(Intersection::Point(Point2::new(third, third)))
+        );
+    }
+
+    #[rstest]
+    fn hailstone_rays() {
+        // Two hailstones from 2023 day 24's example cross ahead of both.
+        let (t, u) = crossing(
+            Point2::new(19, 13),
+            Vec2::new(-2, 1),
+            Point2::new(18, 19),
+            Vec2::new(-1, -1),
+        )
+        .unwrap();
+        assert!(t > Rational::ZERO && u > Rational::ZERO);
+        assert_eq!(
+            Rational::from(19) + t * Rational::from(-2),
+            Rational::new(43, 3)
+        );
+        assert_eq!(
+            crossing(
+                Point2::new(0, 0),
+                Vec2::new(1, 1),
+                Point2::new(1, 0),
+                Vec2::new(-2, -2)
+            ),
+            None
+        );
+    }
+
+    #[rstest]
+    fn lines_of_sight() {
+        assert_eq!(primitive(Vec2::new(4, -6)), Vec2::new(2, -3));
+        assert_eq!(primitive(Vec2::new(0, -5)), Vec2::new(0, -1));
+        assert_eq!(primitive(Vec2::new(0, 0)), Vec2::new(0, 0));
+    }
+
+    #[rstest]
+    fn clockwise_from_up() {
+        let mut directions = [
+            (-1, -1),
+            (0, 1),
+            (1, 0),
+            (-1, 0),
+            (1, -1),
+            (0, -1),
+            (1, 1),
+            (-1, 1),
+        ]
+        .map(Vec2::from);
+        sort_clockwise(&mut directions);
+        let expected = [
+            (0, -1),
+            (1, -1),
+            (1, 0),
+            (1, 1),
+            (0, 1),
+            (-1, 1),
+            (-1, 0),
+            (-1, -1),
+        ];
+        assert_eq!(directions, expected.map(Vec2::from));
+        assert_eq!(
+            clockwise_cmp(Vec2::new(2, -4), Vec2::new(1, -2)),
+            Ordering::Equal
+        );
+        assert_eq!(
+            clockwise_cmp(Vec2::new(1, -3), Vec2::new(1, -2)),
+            Ordering::Less
+        );
+    }
+}
@@ -0,0 +1,681 @@
+//! Graphs over interned node names.
+//!
+//! A [`Graph`] hands out a dense [`NodeId`] the first time it sees a name, so puzzles can build
+//! it straight from `"abc: def ghi"` style input and run the algorithms over plain indices,
+//! mapping back to names with [`Graph::name`] at the end.
+
+use crate::dsu::UnionFind;
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap, HashSet, VecDeque};
+use std::hash::Hash;
+use std::ops::Range;
+
+pub type NodeId = usize;
+
+#[derive(Debug, Clone)]
+pub struct Graph<N> {
+    directed: bool,
+    names: Vec<N>,
+    ids: HashMap<N, NodeId>,
+    edges: Vec<Vec<(NodeId, u64)>>,
+}
+
+impl<N: Clone + Hash + Eq> Graph<N> {
+    pub fn directed() -> Self {
+        Self::new(true)
+    }
+    pub fn undirected() -> Self {
+        Self::new(false)
+    }
+    fn new(directed: bool) -> Self {
+        Self {
+            directed,
+            names: Vec::new(),
+            ids: HashMap::new(),
+            edges: Vec::new(),
+        }
+    }
+    /// The id for `name`, adding it as a node if it's new.
+    pub fn node(&mut self, name: N) -> NodeId {
+        if let Some(&id) = self.ids.get(&name) {
+            return id;
+        }
+        let id = self.names.len();
+        self.names.push(name.clone());
+        self.ids.insert(name, id);
+        self.edges.push(Vec::new());
+        id
+    }
+    pub fn add_edge(&mut self, from: N, to: N) {
+        self.add_weighted_edge(from, to, 1);
+    }
+    pub fn add_weighted_edge(&mut self, from: N, to: N, weight: u64) {
+        let (from, to) = (self.node(from), self.node(to));
+        self.edges[from].push((to, weight));
+        if !self.directed && from != to {
+            self.edges[to].push((from, weight));
+        }
+    }
+    pub fn id(&self, name: &N) -> Option<NodeId> {
+        self.ids.get(name).copied()
+    }
+}
+
+impl<N> Graph<N> {
+    pub fn is_directed(&self) -> bool {
+        self.directed
+    }
+    pub fn name(&self, id: NodeId) -> &N {
+        &self.names[id]
+    }
+    pub fn len(&self) -> usize {
+        self.names.len()
+    }
+    pub fn is_empty(&self) -> bool {
+        self.names.is_empty()
+    }
+    pub fn nodes(&self) -> Range<NodeId> {
+        0..self.len()
+    }
+    /// Outgoing edges with their weights; for an undirected graph, every edge at the node.
+    pub fn edges(&self, id: NodeId) -> &[(NodeId, u64)] {
+        &self.edges[id]
+    }
+    pub fn neighbours(&self, id: NodeId) -> impl Iterator<Item = NodeId> + '_ {
+        self.edges[id].iter().map(|&(to, _)| to)
+    }
+
+    /// Neighbour sets ignoring direction, weights, repeats and self-loops.
+    fn undirected_neighbours(&self) -> Vec<HashSet<NodeId>> {
+        let mut neighbours = vec![HashSet::new(); self.len()];
+        for from in self.nodes() {
+            for to in self.neighbours(from).filter(|&to| to != from) {
+                neighbours[from].insert(to);
+                neighbours[to].insert(from);
+            }
+        }
+        neighbours
+    }
+
+    /// Kahn's algorithm, for taking nodes in dependency order one at a time.
+    pub fn kahn(&self) -> Kahn<'_, N> {
+        let mut indegree = vec![0; self.len()];
+        for to in self.nodes().flat_map(|from| self.neighbours(from)) {
+            indegree[to] += 1;
+        }
+        let ready = self.nodes().filter(|&id| indegree[id] == 0).collect();
+        Kahn {
+            graph: self,
+            indegree,
+            ready,
+        }
+    }
+    /// Every node after all of its predecessors, or `None` if there's a cycle.
+    ///
+    /// Ties go to the node added to the graph first.
+    pub fn topological_sort(&self) -> Option<Vec<NodeId>> {
+        self.topological_sort_by_key(|id| id)
+    }
+    /// A topological order that takes the ready node with the smallest key at each step.
+    pub fn topological_sort_by_key<K: Ord>(
+        &self,
+        mut key: impl FnMut(NodeId) -> K,
+    ) -> Option<Vec<NodeId>> {
+        let mut kahn = self.kahn();
+        let mut heap: BinaryHeap<_> = kahn
+            .ready
+            .drain(..)
+            .map(|id| Reverse((key(id), id)))
+            .collect();
+        let mut order = Vec::with_capacity(self.len());
+        while let Some(Reverse((_, id))) = heap.pop() {
+            order.push(id);
+            kahn.complete(id);
+            heap.extend(kahn.ready.drain(..).map(|id| Reverse((key(id), id))));
+        }
+        (order.len() == self.len()).then_some(order)
+    }
+
+    /// Tarjan's strongly connected components, each listed before any component that reaches it.
+    pub fn strongly_connected_components(&self) -> Vec<Vec<NodeId>> {
+        let n = self.len();
+        let mut index = vec![usize::MAX; n];
+        let mut low = vec![0; n];
+        let mut on_stack = vec![false; n];
+        let mut stack = Vec::new();
+        let mut components = Vec::new();
+        let mut next = 0;
+        for root in self.nodes() {
+            if index[root] != usize::MAX {
+                continue;
+            }
+            index[root] = next;
+            low[root] = next;
+            next += 1;
+            stack.push(root);
+            on_stack[root] = true;
+            // Each frame is a node and how many of its edges have been followed.
+            let mut work = vec![(root, 0)];
+            while let Some(&(v, i)) = work.last() {
+                if let Some(&(w, _)) = self.edges[v].get(i) {
+                    work.last_mut().unwrap().1 += 1;
+                    if index[w] == usize::MAX {
+                        index[w] = next;
+                        low[w] = next;
+                        next += 1;
+                        stack.push(w);
+                        on_stack[w] = true;
+                        work.push((w, 0));
+                    } else if on_stack[w] {
+                        low[v] = low[v].min(index[w]);
+                    }
+                    continue;
+                }
+                work.pop();
+                if let Some(&(u, _)) = work.last() {
+                    low[u] = low[u].min(low[v]);
+                }
+                if low[v] == index[v] {
+                    let mut component = Vec::new();
+                    while let Some(w) = stack.pop() {
+                        on_stack[w] = false;
+                        component.push(w);
+                        if w == v {
+                            break;
+                        }
+                    }
+                    components.push(component);
+                }
+            }
+        }
+        components
+    }
+
+    /// Groups of nodes joined by edges in either direction, in order of their first node.
+    pub fn connected_components(&self) -> Vec<Vec<NodeId>> {
+        let neighbours = self.undirected_neighbours();
+        let mut seen = vec![false; self.len()];
+        let mut components = Vec::new();
+        for root in self.nodes() {
+            if seen[root] {
+                continue;
+            }
+            seen[root] = true;
+            let mut component = vec![root];
+            let mut queue = VecDeque::from([root]);
+            while let Some(v) = queue.pop_front() {
+                for &w in &neighbours[v] {
+                    if !seen[w] {
+                        seen[w] = true;
+                        component.push(w);
+                        queue.push_back(w);
+                    }
+                }
+            }
+            component.sort_unstable();
+            components.push(component);
+        }
+        components
+    }
+
+    /// Every clique that can't be extended, by Bron–Kerbosch with pivoting.
+    pub fn maximal_cliques(&self) -> Vec<Vec<NodeId>> {
+        let neighbours = self.undirected_neighbours();
+        let mut cliques = Vec::new();
+        bron_kerbosch(
+            &neighbours,
+            &mut Vec::new(),
+            self.nodes().collect(),
+            HashSet::new(),
+            &mut cliques,
+        );
+        for clique in &mut cliques {
+            clique.sort_unstable();
+        }
+        cliques
+    }
+    pub fn maximum_clique(&self) -> Vec<NodeId> {
+        self.maximal_cliques()
+            .into_iter()
+            .max_by_key(Vec::len)
+            .unwrap_or_default()
+    }
+
+    /// The undirected edge weights as a dense matrix, summing parallel edges.
+    fn weight_matrix(&self) -> Vec<Vec<u64>> {
+        let mut weights = vec![vec![0; self.len()]; self.len()];
+        for from in self.nodes() {
+            for &(to, weight) in self.edges(from).iter().filter(|&&(to, _)| to != from) {
+                weights[from][to] += weight;
+                if self.directed {
+                    weights[to][from] += weight;
+                }
+            }
+        }
+        weights
+    }
+    /// The lightest global cut by Stoer–Wagner: its weight and the nodes on one side.
+    ///
+    /// Directed edges are treated as undirected. `None` with fewer than two nodes.
+    pub fn min_cut(&self) -> Option<(u64, Vec<NodeId>)> {
+        if self.len() < 2 {
+            return None;
+        }
+        let mut weights = self.weight_matrix();
+        let mut groups: Vec<Vec<NodeId>> = self.nodes().map(|id| vec![id]).collect();
+        let mut active: Vec<NodeId> = self.nodes().collect();
+        let mut best: Option<(u64, Vec<NodeId>)> = None;
+        while active.len() > 1 {
+            // Grow a set by always adding the node most tightly connected to it; the cut
+            // separating the last node added is a minimum cut between the last two.
+            let mut added = vec![false; self.len()];
+            let mut connection = vec![0; self.len()];
+            let mut previous = active[0];
+            added[previous] = true;
+            for &v in &active {
+                connection[v] = weights[previous][v];
+            }
+            for phase in 1..active.len() {
+                let last = active
+                    .iter()
+                    .copied()
+                    .filter(|&v| !added[v])
+                    .max_by_key(|&v| connection[v])
+                    .unwrap();
+                if phase < active.len() - 1 {
+                    added[last] = true;
+                    for &v in &active {
+                        connection[v] += weights[last][v];
+                    }
+                    previous = last;
+                    continue;
+                }
+                if best.as_ref().is_none_or(|(cut, _)| connection[last] < *cut) {
+                    best = Some((connection[last], groups[last].clone()));
+                }
+                for &v in &active {
+                    weights[previous][v] += weights[last][v];
+                    weights[v][previous] = weights[previous][v];
+                }
+                let merged = std::mem::take(&mut groups[last]);
+                groups[previous].extend(merged);
+                active.retain(|&v| v != last);
+            }
+        }
+        best.map(|(cut, mut side)| {
+            side.sort_unstable();
+            (cut, side)
+        })
+    }
+    /// Karger's randomised contraction, keeping the lightest of `trials` attempts.
+    ///
+    /// Much quicker per trial than [`Graph::min_cut`] on sparse graphs, but only finds the
+    /// minimum with high probability. The same `seed` gives the same answer.
+    pub fn karger_min_cut(&self, trials: usize, seed: u64) -> Option<(u64, Vec<NodeId>)> {
+        if self.len() < 2 {
+            return None;
+        }
+        let edges = self
+            .nodes()
+            .flat_map(|from| self.edges(from).iter().map(move |&(to, w)| (from, to, w)))
+            .filter(|&(from, to, _)| from < to || (self.directed && from != to))
+            .collect::<Vec<_>>();
+        let mut rng = XorShift(seed | 1);
+        let mut best: Option<(u64, Vec<NodeId>)> = None;
+        for _ in 0..trials {
+            let mut sets = UnionFind::new(self.len());
+            let mut order = edges.clone();
+            for i in (1..order.len()).rev() {
+                order.swap(i, rng.below(i + 1));
+            }
+            for &(a, b, _) in &order {
+                if sets.count() == 2 {
+                    break;
+                }
+                sets.union(a, b);
+            }
+            let cut = edges
+                .iter()
+                .filter(|&&(a, b, _)| !sets.same(a, b))
+                .map(|&(_, _, w)| w)
+                .sum::<u64>();
+            if best.as_ref().is_none_or(|(best, _)| cut < *best) {
+                let side = self.nodes().filter(|&id| sets.same(id, 0)).collect();
+                best = Some((cut, side));
+            }
+        }
+        best
+    }
+}
+
+/// A small deterministic generator, enough to shuffle edges.
+struct XorShift(u64);
+
+impl XorShift {
+    fn below(&mut self, n: usize) -> usize {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        (self.0 % n as u64) as usize
+    }
+}
+
+fn bron_kerbosch(
+    neighbours: &[HashSet<NodeId>],
+    clique: &mut Vec<NodeId>,
+    mut candidates: HashSet<NodeId>,
+    mut excluded: HashSet<NodeId>,
+    cliques: &mut Vec<Vec<NodeId>>,
+) {
+    let Some(&pivot) = candidates
+        .iter()
+        .chain(&excluded)
+        .max_by_key(|&&v| neighbours[v].len())
+    else {
+        cliques.push(clique.clone());
+        return;
+    };
+    let branches = candidates
+        .difference(&neighbours[pivot])
+        .copied()
+        .collect::<Vec<_>>();
+    for v in branches {
+        clique.push(v);
+        bron_kerbosch(
+            neighbours,
+            clique,
+            candidates.intersection(&neighbours[v]).copied().collect(),
+            excluded.intersection(&neighbours[v]).copied().collect(),
+            cliques,
+        );
+        clique.pop();
+        candidates.remove(&v);
+        excluded.insert(v);
+    }
+}
+
+/// Topological ordering in progress: nodes become ready once everything before them completes.
+///
+/// Completing nodes at different times, rather than as soon as they're taken, is what lets
+/// several workers share a dependency graph.
+pub struct Kahn<'a, N> {
+    graph: &'a Graph<N>,
+    indegree: Vec<usize>,
+    ready: Vec<NodeId>,
+}
+
+impl<N> Kahn<'_, N> {
+    /// Nodes with nothing left before them that haven't been taken yet.
+    pub fn ready(&self) -> &[NodeId] {
+        &self.ready
+    }
+    /// Takes the ready node with the smallest key.
+    pub fn take_min_by_key<K: Ord>(&mut self, mut key: impl FnMut(&NodeId) -> K) -> Option<NodeId> {
+        let (i, _) = self
+            .ready
+            .iter()
+            .enumerate()
+            .min_by_key(|(_, id)| key(id))?;
+        Some(self.ready.swap_remove(i))
+    }
+    /// Marks a taken node done, readying any successor now free of predecessors.
+    pub fn complete(&mut self, id: NodeId) {
+        for to in self.graph.neighbours(id) {
+            self.indegree[to] -= 1;
+            if self.indegree[to] == 0 {
+                self.ready.push(to);
+            }
+        }
+    }
+}
+
+/// Lowest common ancestors in a tree, by binary lifting.
+///
+/// The tree is everything reachable from `root`, following edges away from it.
+pub struct Lca {
+    depth: Vec<Option<usize>>,
+    /// `up[k][v]` is the ancestor `2^k` levels above `v`, or the root.
+    up: Vec<Vec<NodeId>>,
+}
+
+impl Lca {
+    pub fn new<N>(graph: &Graph<N>, root: NodeId) -> Self {
+        let n = graph.len();
+        let mut depth = vec![None; n];
+        let mut parent: Vec<NodeId> = graph.nodes().collect();
+        depth[root] = Some(0);
+        let mut queue = VecDeque::from([root]);
+        while let Some(v) = queue.pop_front() {
+            let d = depth[v].map(|d| d + 1);
+            for w in graph.neighbours(v) {
+                if depth[w].is_none() {
+                    depth[w] = d;
+                    parent[w] = v;
+                    queue.push_back(w);
+                }
+            }
+        }
+        let levels = (usize::BITS - n.leading_zeros()).max(1) as usize;
+        let mut up = vec![parent];
+        for k in 1..levels {
+            let next = up[k - 1].iter().map(|&p| up[k - 1][p]).collect();
+            up.push(next);
+        }
+        Self { depth, up }
+    }
+    /// Edges from the root, or `None` if the node isn't in the tree.
+    pub fn depth(&self, id: NodeId) -> Option<usize> {
+        self.depth[id]
+    }
+    pub fn parent(&self, id: NodeId) -> Option<NodeId> {
+        (self.depth[id]? > 0).then(|| self.up[0][id])
+    }
+    pub fn lca(&self, a: NodeId, b: NodeId) -> Option<NodeId> {
+        let (mut a, mut b) = (a, b);
+        let (mut da, mut db) = (self.depth[a]?, self.depth[b]?);
+        if da < db {
+            (a, b, da, db) = (b, a, db, da);
+        }
+        for k in (0..self.up.len()).rev() {
+            if da - db >= 1 << k {
+                a = self.up[k][a];
+                da -= 1 << k;
+            }
+        }
+        if a == b {
+            return Some(a);
+        }
+        for k in (0..self.up.len()).rev() {
+            if self.up[k][a] != self.up[k][b] {
+                a = self.up[k][a];
+                b = self.up[k][b];
+            }
+        }
+        Some(self.up[0][a])
+    }
+    /// Edges on the tree path between two nodes.
+    pub fn distance(&self, a: NodeId, b: NodeId) -> Option<usize> {
+        let ancestor = self.lca(a, b)?;
+        Some(self.depth[a]? + self.depth[b]? - 2 * self.depth[ancestor]?)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use itertools::Itertools;
+    use rstest::*;
+
+    fn names<'a>(graph: &Graph<&'a str>, ids: &[NodeId]) -> Vec<&'a str> {
+        ids.iter().map(|&id| *graph.name(id)).collect()
+    }
+
+    fn from_pairs<'a>(mut graph: Graph<&'a str>, pairs: &[(&'a str, &'a str)]) -> Graph<&'a str> {
+        for &(from, to) in pairs {
+            graph.add_edge(from, to);
+        }
+        graph
+    }
+
+    #[rstest]
+    fn interning() {
+        let mut graph = Graph::undirected();
+        let a = graph.node("a".to_string());
+        graph.add_edge("a".to_string(), "b".to_string());
+        assert_eq!(graph.node("a".to_string()), a);
+        assert_eq!(graph.len(), 2);
+        assert_eq!(graph.id(&"b".to_string()), Some(1));
+        assert_eq!(graph.neighbours(1).collect::<Vec<_>>(), vec![a]);
+    }
+
+    #[rstest]
+    fn lexicographic_order() {
+        let steps = [
+            ("C", "A"),
+            ("C", "F"),
+            ("A", "B"),
+            ("A", "D"),
+            ("B", "E"),
+            ("D", "E"),
+            ("F", "E"),
+        ];
+        let graph = from_pairs(Graph::directed(), &steps);
+        let order = graph.topological_sort_by_key(|id| *graph.name(id)).unwrap();
+        assert_eq!(names(&graph, &order).concat(), "CABDFE");
+
+        let mut kahn = graph.kahn();
+        assert_eq!(names(&graph, kahn.ready()), vec!["C"]);
+        let c = kahn.take_min_by_key(|&id| *graph.name(id)).unwrap();
+        kahn.complete(c);
+        let mut ready = names(&graph, kahn.ready());
+        ready.sort();
+        assert_eq!(ready, vec!["A", "F"]);
+
+        let cyclic = from_pairs(Graph::directed(), &[("a", "b"), ("b", "c"), ("c", "a")]);
+        assert_eq!(cyclic.topological_sort(), None);
+    }
+
+    #[rstest]
+    fn strongly_connected() {
+        let pairs = [
+            ("a", "b"),
+            ("b", "c"),
+            ("c", "a"),
+            ("c", "d"),
+            ("d", "e"),
+            ("e", "d"),
+            ("f", "e"),
+        ];
+        let graph = from_pairs(Graph::directed(), &pairs);
+        let components = graph
+            .strongly_connected_components()
+            .iter()
+            .map(|c| names(&graph, c).into_iter().sorted().collect::<Vec<_>>())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            components,
+            vec![vec!["d", "e"], vec!["a", "b", "c"], vec!["f"]]
+        );
+    }
+
+    #[rstest]
+    fn components() {
+        let pipes = [
+            ("0", "2"),
+            ("1", "1"),
+            ("2", "3"),
+            ("2", "4"),
+            ("3", "4"),
+            ("4", "6"),
+            ("5", "6"),
+        ];
+        let graph = from_pairs(Graph::undirected(), &pipes);
+        let components = graph.connected_components();
+        assert_eq!(components.len(), 2);
+        assert_eq!(components[0].len(), 6);
+        assert_eq!(names(&graph, &components[1]), vec!["1"]);
+    }
+
+    #[rstest]
+    fn cliques() {
+        // A square with one diagonal, plus a triangle hanging off a corner.
+        let pairs = [
+            ("a", "b"),
+            ("b", "c"),
+            ("c", "d"),
+            ("d", "a"),
+            ("a", "c"),
+            ("d", "e"),
+            ("e", "f"),
+            ("f", "d"),
+        ];
+        let graph = from_pairs(Graph::undirected(), &pairs);
+        let cliques = graph
+            .maximal_cliques()
+            .iter()
+            .map(|c| names(&graph, c).concat())
+            .sorted()
+            .collect::<Vec<_>>();
+        assert_eq!(cliques, vec!["abc", "acd", "def"]);
+        assert_eq!(graph.maximum_clique().len(), 3);
+    }
+
+    fn wiring() -> Graph<&'static str> {
+        let input = "\
+jqt: rhn xhk nvd
+rsh: frs pzl lsr
+xhk: hfx
+cmg: qnr nvd lhk bvb
+rhn: xhk bvb hfx
+bvb: xhk hfx
+pzl: lsr hfx nvd
+qnr: nvd
+ntq: jqt hfx bvb xhk
+nvd: lhk
+lsr: lhk
+rzs: qnr cmg lsr rsh
+frs: qnr lhk lsr";
+        let mut graph = Graph::undirected();
+        for line in input.lines() {
+            let (from, tos) = line.split_once(": ").unwrap();
+            for to in tos.split(' ') {
+                graph.add_edge(from, to);
+            }
+        }
+        graph
+    }
+
+    #[rstest]
+    fn stoer_wagner() {
+        let graph = wiring();
+        let (cut, side) = graph.min_cut().unwrap();
+        assert_eq!(cut, 3);
+        assert_eq!(side.len() * (graph.len() - side.len()), 54);
+    }
+
+    #[rstest]
+    fn karger() {
+        let graph = wiring();
+        let (cut, side) = graph.karger_min_cut(200, 2023).unwrap();
+        assert_eq!(cut, 3);
+        assert_eq!(side.len() * (graph.len() - side.len()), 54);
+    }
+
+    #[rstest]
+    fn orbits() {
+        let orbits = "COM)B B)C C)D D)E E)F B)G G)H D)I E)J J)K K)L K)YOU I)SAN";
+        let mut graph = Graph::directed();
+        for orbit in orbits.split(' ') {
+            let (centre, satellite) = orbit.split_once(')').unwrap();
+            graph.add_edge(centre, satellite);
+        }
+        let lca = Lca::new(&graph, graph.id(&"COM").unwrap());
+        let total: usize = graph.nodes().filter_map(|id| lca.depth(id)).sum();
+        assert_eq!(total, 42 + 7 + 5);
+        let you = lca.parent(graph.id(&"YOU").unwrap()).unwrap();
+        let san = lca.parent(graph.id(&"SAN").unwrap()).unwrap();
+        assert_eq!(*graph.name(lca.lca(you, san).unwrap()), "D");
+        assert_eq!(lca.distance(you, san), Some(4));
+        assert_eq!(lca.parent(graph.id(&"COM").unwrap()), None);
+    }
+}
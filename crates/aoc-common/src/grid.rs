@@ -0,0 +1,524 @@
+//! A rectangular grid of cells, the shape most puzzle inputs come in.
+//!
+//! Cells are stored row-major and addressed as `(row, col)`. Anything that implements
+//! [`GridIndex`] can be used to look a cell up, so days with their own signed coordinates can
+//! index without first checking that they're in bounds.
+
+use crate::error::{AdventError, Result};
+use std::fmt::{Display, Formatter};
+use std::ops::{Add, Index, IndexMut, Range, Sub};
+
+/// Orthogonal `(drow, dcol)` offsets, in the order up, right, down, left.
+const DELTAS4: [(isize, isize); 4] = [(-1, 0), (0, 1), (1, 0), (0, -1)];
+
+/// Orthogonal and diagonal offsets, clockwise from up.
+const DELTAS8: [(isize, isize); 8] = [
+    (-1, 0),
+    (-1, 1),
+    (0, 1),
+    (1, 1),
+    (1, 0),
+    (1, -1),
+    (0, -1),
+    (-1, -1),
+];
+
+/// A position that may name a cell of a grid.
+pub trait GridIndex: Copy {
+    /// The position as `(row, col)`, or `None` if it can't be one, e.g. because it's negative.
+    fn row_col(self) -> Option<(usize, usize)>;
+}
+
+impl GridIndex for (usize, usize) {
+    fn row_col(self) -> Option<(usize, usize)> {
+        Some(self)
+    }
+}
+
+macro_rules! signed_grid_index {
+    ($($t:ty),*) => {
+        $(
+            impl GridIndex for ($t, $t) {
+                fn row_col(self) -> Option<(usize, usize)> {
+                    Some((usize::try_from(self.0).ok()?, usize::try_from(self.1).ok()?))
+                }
+            }
+        )*
+    };
+}
+
+signed_grid_index!(i32, i64, isize);
+
+impl<P: GridIndex> GridIndex for &P {
+    fn row_col(self) -> Option<(usize, usize)> {
+        (*self).row_col()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    /// A grid over row-major cells, which must number `width * height`.
+    pub fn new(width: usize, height: usize, cells: Vec<T>) -> Result<Self> {
+        if cells.len() != width * height {
+            return Err(AdventError::InvalidInput(format!(
+                "A {width}x{height} grid needs {} cells, not {}",
+                width * height,
+                cells.len()
+            )));
+        }
+        Ok(Self {
+            width,
+            height,
+            cells,
+        })
+    }
+    pub fn filled(width: usize, height: usize, value: T) -> Self
+    where
+        T: Clone,
+    {
+        let cells = vec![value; width * height];
+        Self {
+            width,
+            height,
+            cells,
+        }
+    }
+    pub fn from_fn(width: usize, height: usize, mut f: impl FnMut((usize, usize)) -> T) -> Self {
+        let cells = (0..height)
+            .flat_map(|row| (0..width).map(move |col| (row, col)))
+            .map(&mut f)
+            .collect();
+        Self {
+            width,
+            height,
+            cells,
+        }
+    }
+    /// A grid from its rows, which must all be the same length.
+    pub fn from_rows(rows: Vec<Vec<T>>) -> Result<Self> {
+        let height = rows.len();
+        let width = rows.first().map_or(0, Vec::len);
+        if let Some(row) = rows.iter().position(|r| r.len() != width) {
+            return Err(AdventError::InvalidInput(format!(
+                "Row {row} has {} cells, but row 0 has {width}",
+                rows[row].len()
+            )));
+        }
+        let cells = rows.into_iter().flatten().collect();
+        Ok(Self {
+            width,
+            height,
+            cells,
+        })
+    }
+    /// Parse one row per line, mapping each character to a cell.
+    ///
+    /// Fails on ragged lines, or on a character the mapping has no cell for.
+    pub fn parse(text: &str, mut f: impl FnMut(char) -> Option<T>) -> Result<Self> {
+        let rows = text
+            .lines()
+            .enumerate()
+            .map(|(row, line)| {
+                line.chars()
+                    .enumerate()
+                    .map(|(col, c)| {
+                        f(c).ok_or_else(|| {
+                            AdventError::InvalidInput(format!(
+                                "Unexpected {c:?} at row {row}, column {col}"
+                            ))
+                        })
+                    })
+                    .collect::<Result<Vec<_>>>()
+            })
+            .collect::<Result<Vec<_>>>()?;
+        Self::from_rows(rows)
+    }
+    pub fn width(&self) -> usize {
+        self.width
+    }
+    pub fn height(&self) -> usize {
+        self.height
+    }
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+    fn index_of(&self, pos: impl GridIndex) -> Option<usize> {
+        let (row, col) = pos.row_col()?;
+        (row < self.height && col < self.width).then_some(row * self.width + col)
+    }
+    fn position_of(&self, index: usize) -> (usize, usize) {
+        (index / self.width, index % self.width)
+    }
+    pub fn contains(&self, pos: impl GridIndex) -> bool {
+        self.index_of(pos).is_some()
+    }
+    pub fn get(&self, pos: impl GridIndex) -> Option<&T> {
+        self.index_of(pos).map(|i| &self.cells[i])
+    }
+    pub fn get_mut(&mut self, pos: impl GridIndex) -> Option<&mut T> {
+        self.index_of(pos).map(|i| &mut self.cells[i])
+    }
+    /// The position `(drow, dcol)` away, if it's in the grid.
+    pub fn offset(
+        &self,
+        (row, col): (usize, usize),
+        (drow, dcol): (isize, isize),
+    ) -> Option<(usize, usize)> {
+        let pos = (row.checked_add_signed(drow)?, col.checked_add_signed(dcol)?);
+        self.contains(pos).then_some(pos)
+    }
+    /// The orthogonal neighbours in the grid, in the order up, right, down, left.
+    pub fn neighbours4(&self, pos: (usize, usize)) -> impl Iterator<Item = (usize, usize)> + '_ {
+        DELTAS4
+            .into_iter()
+            .filter_map(move |delta| self.offset(pos, delta))
+    }
+    /// The orthogonal and diagonal neighbours in the grid, clockwise from up.
+    pub fn neighbours8(&self, pos: (usize, usize)) -> impl Iterator<Item = (usize, usize)> + '_ {
+        DELTAS8
+            .into_iter()
+            .filter_map(move |delta| self.offset(pos, delta))
+    }
+    /// Every position, row by row.
+    pub fn coordinates(&self) -> impl Iterator<Item = (usize, usize)> + use<T> {
+        let width = self.width;
+        (0..self.height).flat_map(move |row| (0..width).map(move |col| (row, col)))
+    }
+    /// Every cell with its position, row by row.
+    pub fn iter(&self) -> impl Iterator<Item = ((usize, usize), &T)> {
+        self.cells
+            .iter()
+            .enumerate()
+            .map(|(i, cell)| (self.position_of(i), cell))
+    }
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = ((usize, usize), &mut T)> {
+        let width = self.width;
+        self.cells
+            .iter_mut()
+            .enumerate()
+            .map(move |(i, cell)| ((i / width, i % width), cell))
+    }
+    /// The cells, row-major.
+    pub fn cells(&self) -> &[T] {
+        &self.cells
+    }
+    /// The first position, row by row, whose cell matches.
+    pub fn find(&self, mut predicate: impl FnMut(&T) -> bool) -> Option<(usize, usize)> {
+        self.cells
+            .iter()
+            .position(&mut predicate)
+            .map(|i| self.position_of(i))
+    }
+    /// Every position, row by row, whose cell matches.
+    pub fn positions(
+        &self,
+        mut predicate: impl FnMut(&T) -> bool,
+    ) -> impl Iterator<Item = (usize, usize)> {
+        self.iter()
+            .filter(move |(_, cell)| predicate(cell))
+            .map(|(pos, _)| pos)
+    }
+    pub fn row(&self, row: usize) -> &[T] {
+        &self.cells[row * self.width..(row + 1) * self.width]
+    }
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        (0..self.height).map(|row| self.row(row))
+    }
+    pub fn column(&self, col: usize) -> impl Iterator<Item = &T> {
+        assert!(col < self.width, "Column {col} is outside the grid");
+        self.cells.iter().skip(col).step_by(self.width)
+    }
+    pub fn columns(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        (0..self.width).map(|col| self.column(col))
+    }
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        let cells = self.cells.iter().map(f).collect();
+        Grid {
+            width: self.width,
+            height: self.height,
+            cells,
+        }
+    }
+    /// Rows become columns.
+    pub fn transpose(&self) -> Self
+    where
+        T: Clone,
+    {
+        Self::from_fn(self.height, self.width, |(row, col)| {
+            self[(col, row)].clone()
+        })
+    }
+    /// A quarter turn clockwise.
+    pub fn rotate_cw(&self) -> Self
+    where
+        T: Clone,
+    {
+        let height = self.height;
+        Self::from_fn(height, self.width, |(row, col)| {
+            self[(height - 1 - col, row)].clone()
+        })
+    }
+    /// A quarter turn anticlockwise.
+    pub fn rotate_ccw(&self) -> Self
+    where
+        T: Clone,
+    {
+        let width = self.width;
+        Self::from_fn(self.height, width, |(row, col)| {
+            self[(col, width - 1 - row)].clone()
+        })
+    }
+    /// Mirrored left to right.
+    pub fn flip_horizontal(&self) -> Self
+    where
+        T: Clone,
+    {
+        let width = self.width;
+        Self::from_fn(width, self.height, |(row, col)| {
+            self[(row, width - 1 - col)].clone()
+        })
+    }
+    /// Mirrored top to bottom.
+    pub fn flip_vertical(&self) -> Self
+    where
+        T: Clone,
+    {
+        let height = self.height;
+        Self::from_fn(self.width, height, |(row, col)| {
+            self[(height - 1 - row, col)].clone()
+        })
+    }
+}
+
+/// Running totals of a grid, so the sum over any rectangle takes four lookups.
+///
+/// Each entry holds the sum of every cell above and to the left of it, with an extra row and
+/// column of zeros along the top and left so that rectangles touching the edge need no special
+/// case. `T::default()` is taken as zero.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SummedArea<T> {
+    sums: Grid<T>,
+}
+
+impl<T: Copy + Default + Add<Output = T> + Sub<Output = T>> SummedArea<T> {
+    pub fn new(grid: &Grid<T>) -> Self {
+        let mut sums = Grid::filled(grid.width + 1, grid.height + 1, T::default());
+        for ((row, col), &cell) in grid.iter() {
+            sums[(row + 1, col + 1)] =
+                cell + sums[(row, col + 1)] + sums[(row + 1, col)] - sums[(row, col)];
+        }
+        Self { sums }
+    }
+    /// The width and height of the summed grid.
+    pub fn size(&self) -> (usize, usize) {
+        (self.sums.width - 1, self.sums.height - 1)
+    }
+    /// The total of the cells in `rows` and `cols`, which must lie within the grid.
+    pub fn sum(&self, rows: Range<usize>, cols: Range<usize>) -> T {
+        let s = &self.sums;
+        s[(rows.end, cols.end)] + s[(rows.start, cols.start)]
+            - s[(rows.start, cols.end)]
+            - s[(rows.end, cols.start)]
+    }
+    /// Every `width` by `height` window that fits, by its top-left corner, with its total.
+    pub fn windows(
+        &self,
+        width: usize,
+        height: usize,
+    ) -> impl Iterator<Item = ((usize, usize), T)> + '_ {
+        let (grid_width, grid_height) = self.size();
+        let rows = (grid_height + 1).saturating_sub(height);
+        let cols = (grid_width + 1).saturating_sub(width);
+        (0..rows)
+            .flat_map(move |row| (0..cols).map(move |col| (row, col)))
+            .map(move |(row, col)| ((row, col), self.sum(row..row + height, col..col + width)))
+    }
+}
+
+impl<T, P: GridIndex> Index<P> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, pos: P) -> &Self::Output {
+        match self.index_of(pos) {
+            Some(i) => &self.cells[i],
+            None => panic!(
+                "Position {:?} is outside the {}x{} grid",
+                pos.row_col(),
+                self.width,
+                self.height
+            ),
+        }
+    }
+}
+
+impl<T, P: GridIndex> IndexMut<P> for Grid<T> {
+    fn index_mut(&mut self, pos: P) -> &mut Self::Output {
+        match self.index_of(pos) {
+            Some(i) => &mut self.cells[i],
+            None => panic!(
+                "Position {:?} is outside the {}x{} grid",
+                pos.row_col(),
+                self.width,
+                self.height
+            ),
+        }
+    }
+}
+
+/// One line per row, with no separator between cells.
+impl<T: Display> Display for Grid<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        for row in self.rows() {
+            for cell in row {
+                write!(f, "{cell}")?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use rstest::*;
+
+    #[fixture]
+    fn grid() -> Grid<char> {
+        Grid::parse("abc\ndef\n", Some).expect("Valid grid")
+    }
+
+    fn text(grid: &Grid<char>) -> String {
+        grid.to_string()
+    }
+
+    #[rstest]
+    fn parses(grid: Grid<char>) {
+        assert_eq!((grid.width(), grid.height(), grid.len()), (3, 2, 6));
+        assert_eq!(grid[(1, 0)], 'd');
+        assert_eq!(text(&grid), "abc\ndef\n");
+    }
+
+    #[rstest]
+    #[case("ab\nc", "Row 1 has 1 cells, but row 0 has 2")]
+    #[case("ab\nc!", "Unexpected '!' at row 1, column 1")]
+    fn rejects(#[case] input: &str, #[case] message: &str) {
+        let result = Grid::parse(input, |c| c.is_alphabetic().then_some(c));
+        assert_eq!(
+            result.expect_err("Invalid grid").to_string(),
+            format!("Invalid input: {message}")
+        );
+    }
+
+    #[rstest]
+    fn indexes_checked_and_signed(mut grid: Grid<char>) {
+        assert_eq!(grid.get((0, 3)), None);
+        assert_eq!(grid.get((-1, 0)), None);
+        assert_eq!(grid.get((1i32, 2i32)), Some(&'f'));
+        assert_eq!(grid[&(1i64, 1i64)], 'e');
+        grid[(0, 0)] = 'z';
+        assert_eq!(grid.row(0), ['z', 'b', 'c']);
+    }
+
+    #[rstest]
+    #[should_panic(expected = "outside the 3x2 grid")]
+    fn index_panics_outside(grid: Grid<char>) {
+        let _ = grid[(2, 0)];
+    }
+
+    #[rstest]
+    fn neighbours(grid: Grid<char>) {
+        let four = grid.neighbours4((0, 1)).collect::<Vec<_>>();
+        assert_eq!(four, [(0, 2), (1, 1), (0, 0)]);
+        let eight = grid.neighbours8((1, 0)).collect::<Vec<_>>();
+        assert_eq!(eight, [(0, 0), (0, 1), (1, 1)]);
+        assert_eq!(grid.offset((1, 2), (-1, -2)), Some((0, 0)));
+        assert_eq!(grid.offset((1, 2), (0, 1)), None);
+    }
+
+    #[rstest]
+    fn finds(grid: Grid<char>) {
+        assert_eq!(grid.find(|&c| c == 'e'), Some((1, 1)));
+        assert_eq!(grid.find(|&c| c == 'z'), None);
+        let vowels = grid.positions(|c| "aeiou".contains(*c)).collect::<Vec<_>>();
+        assert_eq!(vowels, [(0, 0), (1, 1)]);
+    }
+
+    #[rstest]
+    fn rows_and_columns(grid: Grid<char>) {
+        let rows = grid
+            .rows()
+            .map(|r| r.iter().collect::<String>())
+            .collect::<Vec<_>>();
+        assert_eq!(rows, ["abc", "def"]);
+        let columns = grid
+            .columns()
+            .map(|c| c.collect::<String>())
+            .collect::<Vec<_>>();
+        assert_eq!(columns, ["ad", "be", "cf"]);
+        assert_eq!(grid.coordinates().count(), 6);
+    }
+
+    #[rstest]
+    fn transforms(grid: Grid<char>) {
+        assert_eq!(text(&grid.transpose()), "ad\nbe\ncf\n");
+        assert_eq!(text(&grid.rotate_cw()), "da\neb\nfc\n");
+        assert_eq!(text(&grid.rotate_ccw()), "cf\nbe\nad\n");
+        assert_eq!(text(&grid.flip_horizontal()), "cba\nfed\n");
+        assert_eq!(text(&grid.flip_vertical()), "def\nabc\n");
+        assert_eq!(grid.rotate_cw().rotate_ccw(), grid);
+        assert_eq!(
+            grid.rotate_cw().rotate_cw(),
+            grid.flip_horizontal().flip_vertical()
+        );
+    }
+
+    #[rstest]
+    fn builds(grid: Grid<char>) {
+        let numbered = Grid::from_fn(3, 2, |(row, col)| row * 3 + col);
+        assert_eq!(numbered.cells(), [0, 1, 2, 3, 4, 5]);
+        assert_eq!(
+            grid.map(|c| c.is_ascii_uppercase()),
+            Grid::filled(3, 2, false)
+        );
+        assert!(Grid::new(2, 2, vec![0; 3]).is_err());
+    }
+
+    #[rstest]
+    fn summed_areas() {
+        let grid = Grid::from_fn(4, 3, |(row, col)| (row * 4 + col) as i64);
+        let sums = SummedArea::new(&grid);
+        assert_eq!(sums.size(), (4, 3));
+        assert_eq!(sums.sum(0..3, 0..4), 66);
+        assert_eq!(sums.sum(1..3, 1..3), 5 + 6 + 9 + 10);
+        assert_eq!(sums.sum(2..2, 0..4), 0);
+        let windows = sums.windows(3, 2).collect::<Vec<_>>();
+        assert_eq!(
+            windows,
+            [((0, 0), 18), ((0, 1), 24), ((1, 0), 42), ((1, 1), 48)]
+        );
+        assert_eq!(sums.windows(5, 1).count(), 0);
+    }
+
+    #[rstest]
+    fn largest_power_square() {
+        // 2018 day 11's first example: serial number 18 peaks at 33,45 with 29 power.
+        let power = |x: i64, y: i64| {
+            let rack = x + 10;
+            (rack * y + 18) * rack / 100 % 10 - 5
+        };
+        let cells = Grid::from_fn(300, 300, |(row, col)| power(col as i64 + 1, row as i64 + 1));
+        let sums = SummedArea::new(&cells);
+        let best = sums.windows(3, 3).max_by_key(|&(_, total)| total);
+        assert_eq!(best, Some(((44, 32), 29)));
+    }
+}
@@ -0,0 +1,287 @@
+//! MD5 searches over `key + nonce`, the decimal nonce counting up from zero.
+//!
+//! [`NonceSearch`] hashes the key once and clones that state for each nonce, writes nonces
+//! into a stack buffer rather than formatting a `String`, and spreads each batch of nonces over
+//! every core. Batches are finished before any of their matches are handed out, so matches
+//! always come back in nonce order however the threads are scheduled.
+//!
+//! Most predicates only care about the hex digits of the digest, so those are provided here
+//! too, working on nibbles without ever building the hex string.
+
+use std::collections::VecDeque;
+use std::ops::Range;
+use std::thread;
+
+pub use md5::Digest;
+
+/// Nonces each thread hashes per batch: enough to dwarf the cost of spawning it.
+const CHUNK: u64 = 8_192;
+
+#[derive(Clone)]
+pub struct NonceSearch {
+    prefix: md5::Context,
+    stretch: usize,
+    range: Range<u64>,
+    threads: usize,
+}
+
+impl NonceSearch {
+    pub fn new(key: &str) -> Self {
+        let mut prefix = md5::Context::new();
+        prefix.consume(key.as_bytes());
+        let threads = thread::available_parallelism().map_or(1, |n| n.get());
+        Self {
+            prefix,
+            stretch: 0,
+            range: 0..u64::MAX,
+            threads,
+        }
+    }
+    /// Rehashes each digest's lowercase hex `rounds` more times, as 2016 day 14 does 2016 times.
+    pub fn with_stretch(mut self, rounds: usize) -> Self {
+        self.stretch = rounds;
+        self
+    }
+    /// Only tries the nonces in `range`, instead of every `u64` from zero.
+    pub fn with_range(mut self, range: Range<u64>) -> Self {
+        self.range = range;
+        self
+    }
+    pub fn with_threads(mut self, threads: usize) -> Self {
+        self.threads = threads.max(1);
+        self
+    }
+
+    /// The (stretched) digest of the key followed by `nonce`.
+    pub fn hash(&self, nonce: u64) -> Digest {
+        let mut context = self.prefix.clone();
+        let mut buffer = [0; 20];
+        context.consume(decimal(nonce, &mut buffer));
+        let mut digest = context.finalize();
+        for _ in 0..self.stretch {
+            digest = md5::compute(hex(&digest));
+        }
+        digest
+    }
+    /// The digests of every nonce in `nonces`, in order, hashed in parallel.
+    pub fn hashes(&self, nonces: Range<u64>) -> Vec<Digest> {
+        self.batch(nonces, |_| true)
+            .into_iter()
+            .map(|(_, digest)| digest)
+            .collect()
+    }
+    /// The matching nonces in `nonces`, in order, with their digests.
+    fn batch(
+        &self,
+        nonces: Range<u64>,
+        predicate: impl Fn(&Digest) -> bool + Sync,
+    ) -> Vec<(u64, Digest)> {
+        let per_thread = (nonces.end - nonces.start)
+            .div_ceil(self.threads as u64)
+            .max(1);
+        let predicate = &predicate;
+        thread::scope(|scope| {
+            let workers: Vec<_> = (nonces.start..nonces.end)
+                .step_by(per_thread as usize)
+                .map(|start| {
+                    let end = nonces.end.min(start + per_thread);
+                    scope.spawn(move || {
+                        (start..end)
+                            .map(|nonce| (nonce, self.hash(nonce)))
+                            .filter(|(_, digest)| predicate(digest))
+                            .collect::<Vec<_>>()
+                    })
+                })
+                .collect();
+            workers
+                .into_iter()
+                .flat_map(|worker| worker.join().expect("Hashing thread panicked"))
+                .collect()
+        })
+    }
+
+    /// Every nonce whose digest satisfies `predicate`, in increasing order, found a batch at a
+    /// time as the iterator is advanced.
+    pub fn matches<P>(&self, predicate: P) -> Matches<'_, P>
+    where
+        P: Fn(&Digest) -> bool + Sync,
+    {
+        Matches {
+            search: self,
+            predicate,
+            next: self.range.start,
+            found: VecDeque::new(),
+        }
+    }
+    /// The first nonce whose digest satisfies `predicate`.
+    pub fn first(&self, predicate: impl Fn(&Digest) -> bool + Sync) -> Option<(u64, Digest)> {
+        self.matches(predicate).next()
+    }
+    /// The first `n` nonces whose digests satisfy `predicate`, fewer if the range runs out.
+    pub fn find(&self, n: usize, predicate: impl Fn(&Digest) -> bool + Sync) -> Vec<(u64, Digest)> {
+        self.matches(predicate).take(n).collect()
+    }
+}
+
+/// The iterator returned by [`NonceSearch::matches`].
+pub struct Matches<'a, P> {
+    search: &'a NonceSearch,
+    predicate: P,
+    next: u64,
+    found: VecDeque<(u64, Digest)>,
+}
+
+impl<P: Fn(&Digest) -> bool + Sync> Iterator for Matches<'_, P> {
+    type Item = (u64, Digest);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while self.found.is_empty() && self.next < self.search.range.end {
+            let size = CHUNK * self.search.threads as u64;
+            let end = self.search.range.end.min(self.next.saturating_add(size));
+            self.found = self.search.batch(self.next..end, &self.predicate).into();
+            self.next = end;
+        }
+        self.found.pop_front()
+    }
+}
+
+/// Writes `n` in decimal at the end of `buffer`, returning the digits.
+fn decimal(mut n: u64, buffer: &mut [u8; 20]) -> &[u8] {
+    let mut start = buffer.len();
+    loop {
+        start -= 1;
+        buffer[start] = b'0' + (n % 10) as u8;
+        n /= 10;
+        if n == 0 {
+            return &buffer[start..];
+        }
+    }
+}
+
+/// The digest as 32 lowercase hex digits.
+pub fn hex(digest: &Digest) -> [u8; 32] {
+    const DIGITS: &[u8; 16] = b"0123456789abcdef";
+    digits(digest).map(|nibble| DIGITS[nibble as usize])
+}
+
+/// The 32 hex digits of the digest as values, most significant first.
+pub fn nibbles(digest: &Digest) -> impl Iterator<Item = u8> + '_ {
+    digest.0.iter().flat_map(|&byte| [byte >> 4, byte & 0xf])
+}
+
+fn digits(digest: &Digest) -> [u8; 32] {
+    let mut digits = [0; 32];
+    for (digit, nibble) in digits.iter_mut().zip(nibbles(digest)) {
+        *digit = nibble;
+    }
+    digits
+}
+
+/// How many hex digits the digest starts with that are `0`.
+pub fn leading_zeros(digest: &Digest) -> usize {
+    nibbles(digest).take_while(|&nibble| nibble == 0).count()
+}
+
+/// A predicate for digests whose hex starts with at least `n` zeros, like `000001dbbfa...`.
+pub fn zeros(n: usize) -> impl Fn(&Digest) -> bool + Sync + Copy {
+    move |digest| leading_zeros(digest) >= n
+}
+
+/// The first hex digit repeated `len` times in a row, as in 2016 day 14's triples.
+pub fn first_run(digest: &Digest, len: usize) -> Option<u8> {
+    digits(digest)
+        .windows(len)
+        .find(|run| run.iter().all(|&d| d == run[0]))
+        .map(|run| run[0])
+}
+
+/// Whether `nibble` appears `len` times in a row anywhere in the digest.
+pub fn has_run(digest: &Digest, nibble: u8, len: usize) -> bool {
+    digits(digest)
+        .windows(len)
+        .any(|run| run.iter().all(|&d| d == nibble))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use rstest::*;
+
+    #[rstest]
+    #[case(0, "0")]
+    #[case(7, "7")]
+    #[case(609043, "609043")]
+    #[case(u64::MAX, "18446744073709551615")]
+    fn decimals(#[case] n: u64, #[case] expected: &str) {
+        let mut buffer = [0; 20];
+        assert_eq!(decimal(n, &mut buffer), expected.as_bytes());
+    }
+
+    #[rstest]
+    fn prefix_is_reused() {
+        let search = NonceSearch::new("abcdef");
+        assert_eq!(search.hash(609043), md5::compute("abcdef609043"));
+        assert_eq!(
+            &hex(&search.hash(609043)),
+            b"000001dbbfa3a5c83a2d506429c7b00e"
+        );
+        assert_eq!(leading_zeros(&search.hash(609043)), 5);
+    }
+
+    #[rstest]
+    #[case("abcdef", 609043)]
+    #[case("pqrstuv", 1048970)]
+    fn first_five_zeros(#[case] key: &str, #[case] expected: u64) {
+        let search = NonceSearch::new(key);
+        assert_eq!(
+            search.first(zeros(5)).map(|(nonce, _)| nonce),
+            Some(expected)
+        );
+    }
+
+    #[rstest]
+    fn ordered_across_threads() {
+        // 2016 day 5's example: the door's password comes from hashes in nonce order.
+        let search = NonceSearch::new("abc").with_range(3_000_000..5_300_000);
+        let found = search.find(3, zeros(5));
+        let nonces = found.iter().map(|&(nonce, _)| nonce).collect::<Vec<_>>();
+        assert_eq!(nonces, [3231929, 5017308, 5278568]);
+        let password = found
+            .iter()
+            .map(|(_, d)| hex(d)[5] as char)
+            .collect::<String>();
+        assert_eq!(password, "18f");
+        let single = NonceSearch::new("abc")
+            .with_threads(1)
+            .with_range(3_000_000..5_300_000);
+        assert_eq!(single.find(3, zeros(5)), found);
+    }
+
+    #[rstest]
+    fn range_runs_out() {
+        let search = NonceSearch::new("abcdef").with_range(0..600_000);
+        assert_eq!(search.first(zeros(5)), None);
+        assert_eq!(search.hashes(10..13).len(), 3);
+        assert_eq!(search.hashes(10..13)[2], search.hash(12));
+    }
+
+    #[rstest]
+    fn stretched() {
+        // 2016 day 14's example: salt abc, index 0, hashed 2017 times in all.
+        let search = NonceSearch::new("abc").with_stretch(2016);
+        assert_eq!(&hex(&search.hash(0)), b"a107ff634856bb300138cac6568c0f24");
+        let triple = search.hash(10);
+        assert_eq!(first_run(&triple, 3), Some(0xe));
+    }
+
+    #[rstest]
+    fn runs() {
+        // 2016 day 14's example: abc18 has a triple 8 that goes nowhere, while abc39's triple
+        // e is a key thanks to the eeeee in abc816.
+        let search = NonceSearch::new("abc");
+        assert_eq!(first_run(&search.hash(18), 3), Some(8));
+        assert!(!search.hashes(19..1019).iter().any(|d| has_run(d, 8, 5)));
+        assert_eq!(first_run(&search.hash(39), 3), Some(0xe));
+        assert!(has_run(&search.hash(816), 0xe, 5));
+    }
+}
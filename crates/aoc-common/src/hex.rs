@@ -0,0 +1,323 @@
+//! Hexagonal grids in axial coordinates.
+//!
+//! An [`Axial`] `(q, r)` is a [`Cube`] `(q, r, s)` with `s = -q - r` left implied. The six
+//! neighbours are the same whichever way the hexes are drawn; only their names differ, so
+//! pointy-topped grids step with [`PointyDirection`] and flat-topped ones with [`FlatDirection`].
+
+use nom::branch::alt;
+use nom::bytes::complete::tag;
+use nom::character::complete::char;
+use nom::multi::{many0, separated_list0};
+use nom::{IResult, Parser};
+use std::collections::{HashMap, HashSet};
+use std::ops::{Add, AddAssign, Mul, Neg, Sub};
+use strum::EnumIter;
+
+/// The neighbour offsets, anticlockwise from the `+q` side.
+const DELTAS: [Axial; 6] = [
+    Axial::new(1, 0),
+    Axial::new(1, -1),
+    Axial::new(0, -1),
+    Axial::new(-1, 0),
+    Axial::new(-1, 1),
+    Axial::new(0, 1),
+];
+
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Axial {
+    pub q: i64,
+    pub r: i64,
+}
+
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct Cube {
+    pub q: i64,
+    pub r: i64,
+    pub s: i64,
+}
+
+impl Axial {
+    pub const fn new(q: i64, r: i64) -> Self {
+        Self { q, r }
+    }
+    pub fn s(self) -> i64 {
+        -self.q - self.r
+    }
+    /// Steps to the other hex.
+    pub fn distance(self, other: Self) -> i64 {
+        let d = self - other;
+        (d.q.abs() + d.r.abs() + d.s().abs()) / 2
+    }
+    pub fn neighbours(self) -> impl Iterator<Item = Axial> {
+        DELTAS.into_iter().map(move |d| self + d)
+    }
+    /// The hexes exactly `radius` steps away, going round from the `-q +r` corner.
+    pub fn ring(self, radius: usize) -> Vec<Axial> {
+        if radius == 0 {
+            return vec![self];
+        }
+        let mut hex = self + DELTAS[4] * radius as i64;
+        let mut ring = Vec::with_capacity(6 * radius);
+        for delta in DELTAS {
+            for _ in 0..radius {
+                ring.push(hex);
+                hex += delta;
+            }
+        }
+        ring
+    }
+    /// The hexes at most `radius` steps away, ring by ring from the centre.
+    pub fn spiral(self, radius: usize) -> Vec<Axial> {
+        (0..=radius).flat_map(|r| self.ring(r)).collect()
+    }
+}
+
+impl Cube {
+    /// A cube coordinate, which must satisfy `q + r + s = 0`.
+    pub fn new(q: i64, r: i64, s: i64) -> Self {
+        assert_eq!(q + r + s, 0, "Cube coordinates must sum to zero");
+        Self { q, r, s }
+    }
+    pub fn distance(self, other: Self) -> i64 {
+        Axial::from(self).distance(other.into())
+    }
+}
+
+impl From<Axial> for Cube {
+    fn from(a: Axial) -> Self {
+        Self {
+            q: a.q,
+            r: a.r,
+            s: a.s(),
+        }
+    }
+}
+
+impl From<Cube> for Axial {
+    fn from(c: Cube) -> Self {
+        Self::new(c.q, c.r)
+    }
+}
+
+impl Add for Axial {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self::Output {
+        Self::new(self.q + other.q, self.r + other.r)
+    }
+}
+
+impl AddAssign for Axial {
+    fn add_assign(&mut self, other: Self) {
+        *self = *self + other;
+    }
+}
+
+impl Sub for Axial {
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self::Output {
+        Self::new(self.q - other.q, self.r - other.r)
+    }
+}
+
+impl Mul<i64> for Axial {
+    type Output = Self;
+
+    fn mul(self, k: i64) -> Self::Output {
+        Self::new(self.q * k, self.r * k)
+    }
+}
+
+impl Neg for Axial {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        Self::new(-self.q, -self.r)
+    }
+}
+
+/// Steps on a grid of pointy-topped hexes, whose rows run east to west.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, EnumIter)]
+pub enum PointyDirection {
+    East,
+    NorthEast,
+    NorthWest,
+    West,
+    SouthWest,
+    SouthEast,
+}
+
+/// Steps on a grid of flat-topped hexes, whose columns run north to south.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, EnumIter)]
+pub enum FlatDirection {
+    SouthEast,
+    NorthEast,
+    North,
+    NorthWest,
+    SouthWest,
+    South,
+}
+
+impl PointyDirection {
+    const ALL: [Self; 6] = [
+        Self::East,
+        Self::NorthEast,
+        Self::NorthWest,
+        Self::West,
+        Self::SouthWest,
+        Self::SouthEast,
+    ];
+
+    pub fn delta(self) -> Axial {
+        DELTAS[self as usize]
+    }
+    pub fn opposite(self) -> Self {
+        Self::ALL[(self as usize + 3) % 6]
+    }
+}
+
+impl FlatDirection {
+    const ALL: [Self; 6] = [
+        Self::SouthEast,
+        Self::NorthEast,
+        Self::North,
+        Self::NorthWest,
+        Self::SouthWest,
+        Self::South,
+    ];
+
+    pub fn delta(self) -> Axial {
+        DELTAS[self as usize]
+    }
+    pub fn opposite(self) -> Self {
+        Self::ALL[(self as usize + 3) % 6]
+    }
+}
+
+/// One of `e`, `ne`, `nw`, `w`, `sw` or `se`.
+pub fn pointy_direction(i: &str) -> IResult<&str, PointyDirection> {
+    alt((
+        tag("e").map(|_| PointyDirection::East),
+        tag("ne").map(|_| PointyDirection::NorthEast),
+        tag("nw").map(|_| PointyDirection::NorthWest),
+        tag("w").map(|_| PointyDirection::West),
+        tag("sw").map(|_| PointyDirection::SouthWest),
+        tag("se").map(|_| PointyDirection::SouthEast),
+    ))
+    .parse(i)
+}
+
+/// Steps run together with no separator, e.g. `esenee`.
+pub fn pointy_path(i: &str) -> IResult<&str, Vec<PointyDirection>> {
+    many0(pointy_direction).parse(i)
+}
+
+/// One of `ne`, `n`, `nw`, `sw`, `s` or `se`.
+pub fn flat_direction(i: &str) -> IResult<&str, FlatDirection> {
+    alt((
+        tag("ne").map(|_| FlatDirection::NorthEast),
+        tag("nw").map(|_| FlatDirection::NorthWest),
+        tag("n").map(|_| FlatDirection::North),
+        tag("se").map(|_| FlatDirection::SouthEast),
+        tag("sw").map(|_| FlatDirection::SouthWest),
+        tag("s").map(|_| FlatDirection::South),
+    ))
+    .parse(i)
+}
+
+/// Comma separated steps, e.g. `ne,ne,s`.
+pub fn flat_path(i: &str) -> IResult<&str, Vec<FlatDirection>> {
+    separated_list0(char(','), flat_direction).parse(i)
+}
+
+/// One generation of a hex automaton over its live cells.
+///
+/// `rule(alive, live_neighbours)` decides each cell's next state; only live cells and their
+/// neighbours are considered, so a dead cell with no live neighbours stays dead.
+pub fn life_step(alive: &HashSet<Axial>, rule: impl Fn(bool, usize) -> bool) -> HashSet<Axial> {
+    let mut counts: HashMap<Axial, usize> = alive.iter().map(|&hex| (hex, 0)).collect();
+    for hex in alive.iter().flat_map(|hex| hex.neighbours()) {
+        *counts.entry(hex).or_default() += 1;
+    }
+    counts
+        .into_iter()
+        .filter(|(hex, count)| rule(alive.contains(hex), *count))
+        .map(|(hex, _)| hex)
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::error::{AdventErrorExt, Result};
+    use rstest::*;
+    use strum::IntoEnumIterator;
+
+    fn walk(steps: impl IntoIterator<Item = Axial>) -> Axial {
+        steps.into_iter().fold(Axial::default(), Add::add)
+    }
+
+    #[rstest]
+    #[case("ne,ne,ne", 3)]
+    #[case("ne,ne,sw,sw", 0)]
+    #[case("ne,ne,s,s", 2)]
+    #[case("se,sw,se,sw,sw", 3)]
+    #[case("", 0)]
+    fn flat_distances(#[case] input: &str, #[case] distance: i64) -> Result<()> {
+        let path = flat_path(input).map_and_finish()?;
+        let end = walk(path.into_iter().map(FlatDirection::delta));
+        assert_eq!(end.distance(Axial::default()), distance);
+        Ok(())
+    }
+
+    #[rstest]
+    #[case("esew", Axial::new(0, 1))]
+    #[case("nwwswee", Axial::new(0, 0))]
+    fn pointy_walks(#[case] input: &str, #[case] end: Axial) -> Result<()> {
+        let path = pointy_path(input).map_and_finish()?;
+        assert_eq!(walk(path.into_iter().map(PointyDirection::delta)), end);
+        Ok(())
+    }
+
+    #[rstest]
+    fn directions() {
+        for d in PointyDirection::iter() {
+            assert_eq!(d.delta() + d.opposite().delta(), Axial::default());
+        }
+        for d in FlatDirection::iter() {
+            assert_eq!(d.delta(), -d.opposite().delta());
+            assert_eq!(Cube::from(d.delta()).distance(Cube::new(0, 0, 0)), 1);
+        }
+    }
+
+    #[rstest]
+    #[case(0, 1)]
+    #[case(1, 6)]
+    #[case(3, 18)]
+    fn rings(#[case] radius: usize, #[case] len: usize) {
+        let centre = Axial::new(2, -5);
+        let ring = centre.ring(radius);
+        assert_eq!(ring.len(), len);
+        assert_eq!(ring.iter().collect::<HashSet<_>>().len(), len);
+        assert!(ring.iter().all(|h| h.distance(centre) == radius as i64));
+        assert_eq!(centre.spiral(radius).len(), 1 + 3 * radius * (radius + 1));
+    }
+
+    #[rstest]
+    fn cube_round_trip() {
+        let a = Axial::new(3, -7);
+        assert_eq!(Cube::from(a), Cube::new(3, -7, 4));
+        assert_eq!(Axial::from(Cube::from(a)), a);
+    }
+
+    #[rstest]
+    fn life() {
+        // Three in a line: the ends each see one neighbour, the middle two.
+        let line = HashSet::from([Axial::new(-1, 0), Axial::new(0, 0), Axial::new(1, 0)]);
+        let next = life_step(&line, |alive, n| n == 2 || (alive && n == 1));
+        assert!(next.is_superset(&line));
+        assert!(next.contains(&Axial::new(0, -1)) && next.contains(&Axial::new(0, 1)));
+        assert_eq!(next.len(), 7);
+    }
+}
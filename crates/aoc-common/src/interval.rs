@@ -0,0 +1,331 @@
+//! Sets of integers kept as sorted, disjoint inclusive intervals.
+//!
+//! Intervals are stored inclusive at both ends so that a set can reach the largest value of its
+//! type, e.g. the whole `u32` address space. Touching intervals such as `1..=3` and `4..=6` are
+//! merged, so each set has exactly one representation and derived equality is set equality.
+
+use std::fmt::Debug;
+use std::ops::{Add, RangeInclusive, Sub};
+
+/// Integer types an [`IntervalSet`] can hold.
+pub trait Discrete: Copy + Ord + Debug + Add<Output = Self> + Sub<Output = Self> {
+    const ZERO: Self;
+    const ONE: Self;
+}
+
+macro_rules! impl_discrete {
+    ($($t:ty),*) => {
+        $(impl Discrete for $t {
+            const ZERO: Self = 0;
+            const ONE: Self = 1;
+        })*
+    };
+}
+
+impl_discrete!(
+    u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize
+);
+
+/// Whether `start` comes straight after `end`, leaving no gap between them.
+fn adjacent<T: Discrete>(end: T, start: T) -> bool {
+    start > end && start - end == T::ONE
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct IntervalSet<T> {
+    ranges: Vec<(T, T)>,
+}
+
+impl<T> Default for IntervalSet<T> {
+    fn default() -> Self {
+        Self { ranges: Vec::new() }
+    }
+}
+
+impl<T: Discrete> IntervalSet<T> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+    /// The values `start..=end`, empty if `end < start`.
+    pub fn inclusive(start: T, end: T) -> Self {
+        let mut set = Self::new();
+        set.insert(start..=end);
+        set
+    }
+    /// The values `start..end`, empty if `end <= start`.
+    pub fn exclusive(start: T, end: T) -> Self {
+        if end <= start {
+            Self::new()
+        } else {
+            Self::inclusive(start, end - T::ONE)
+        }
+    }
+    pub fn is_empty(&self) -> bool {
+        self.ranges.is_empty()
+    }
+    /// How many values are in the set. Overflows if that doesn't fit in `T`.
+    pub fn len(&self) -> T {
+        self.ranges
+            .iter()
+            .fold(T::ZERO, |acc, &(start, end)| acc + (end - start) + T::ONE)
+    }
+    pub fn contains(&self, value: T) -> bool {
+        let i = self.ranges.partition_point(|&(_, end)| end < value);
+        self.ranges.get(i).is_some_and(|&(start, _)| start <= value)
+    }
+    pub fn first(&self) -> Option<T> {
+        self.ranges.first().map(|&(start, _)| start)
+    }
+    pub fn last(&self) -> Option<T> {
+        self.ranges.last().map(|&(_, end)| end)
+    }
+    /// The disjoint intervals, in order.
+    pub fn iter(&self) -> impl Iterator<Item = RangeInclusive<T>> + '_ {
+        self.ranges.iter().map(|&(start, end)| start..=end)
+    }
+    pub fn insert(&mut self, range: RangeInclusive<T>) {
+        let (mut start, mut end) = range.into_inner();
+        if end < start {
+            return;
+        }
+        let first = self
+            .ranges
+            .partition_point(|&(_, e)| e < start && !adjacent(e, start));
+        let last = self
+            .ranges
+            .partition_point(|&(s, _)| s <= end || adjacent(end, s));
+        if first < last {
+            start = start.min(self.ranges[first].0);
+            end = end.max(self.ranges[last - 1].1);
+        }
+        self.ranges.splice(first..last, [(start, end)]);
+    }
+    pub fn remove(&mut self, range: RangeInclusive<T>) {
+        let (start, end) = range.into_inner();
+        if end < start {
+            return;
+        }
+        let first = self.ranges.partition_point(|&(_, e)| e < start);
+        let last = self.ranges.partition_point(|&(s, _)| s <= end);
+        if first == last {
+            return;
+        }
+        let mut pieces = Vec::with_capacity(2);
+        let (s, _) = self.ranges[first];
+        if s < start {
+            pieces.push((s, start - T::ONE));
+        }
+        let (_, e) = self.ranges[last - 1];
+        if e > end {
+            pieces.push((end + T::ONE, e));
+        }
+        self.ranges.splice(first..last, pieces);
+    }
+    pub fn union(&self, other: &Self) -> Self {
+        let mut set = self.clone();
+        for range in other.iter() {
+            set.insert(range);
+        }
+        set
+    }
+    pub fn intersection(&self, other: &Self) -> Self {
+        let mut ranges = Vec::new();
+        let (mut i, mut j) = (0, 0);
+        while let (Some(&(a0, a1)), Some(&(b0, b1))) = (self.ranges.get(i), other.ranges.get(j)) {
+            let (start, end) = (a0.max(b0), a1.min(b1));
+            if start <= end {
+                ranges.push((start, end));
+            }
+            if a1 < b1 {
+                i += 1;
+            } else {
+                j += 1;
+            }
+        }
+        Self { ranges }
+    }
+    pub fn difference(&self, other: &Self) -> Self {
+        let mut set = self.clone();
+        for range in other.iter() {
+            set.remove(range);
+        }
+        set
+    }
+    /// The stretches of `bounds` not in the set, in order.
+    pub fn gaps(&self, bounds: RangeInclusive<T>) -> impl Iterator<Item = RangeInclusive<T>> {
+        let (start, end) = bounds.into_inner();
+        Self::inclusive(start, end)
+            .difference(self)
+            .ranges
+            .into_iter()
+            .map(|(s, e)| s..=e)
+    }
+}
+
+impl<T: Discrete> FromIterator<RangeInclusive<T>> for IntervalSet<T> {
+    fn from_iter<I: IntoIterator<Item = RangeInclusive<T>>>(iter: I) -> Self {
+        let mut set = Self::new();
+        for range in iter {
+            set.insert(range);
+        }
+        set
+    }
+}
+
+impl<T: Discrete> From<RangeInclusive<T>> for IntervalSet<T> {
+    fn from(range: RangeInclusive<T>) -> Self {
+        let (start, end) = range.into_inner();
+        Self::inclusive(start, end)
+    }
+}
+
+/// Moves source intervals onto destinations, leaving unmapped values where they are.
+///
+/// Sources are expected not to overlap.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RangeMap<T> {
+    entries: Vec<(T, T, T)>,
+}
+
+impl<T: Discrete> RangeMap<T> {
+    pub fn new() -> Self {
+        Self {
+            entries: Vec::new(),
+        }
+    }
+    /// Maps `source` onto the same number of values starting at `destination`.
+    pub fn with_range(mut self, source: RangeInclusive<T>, destination: T) -> Self {
+        let (start, end) = source.into_inner();
+        if start <= end {
+            self.entries.push((start, end, destination));
+        }
+        self
+    }
+    pub fn map(&self, value: T) -> T {
+        self.entries
+            .iter()
+            .find(|&&(start, end, _)| start <= value && value <= end)
+            .map_or(value, |&(start, _, destination)| {
+                value - start + destination
+            })
+    }
+    /// Every value of `set` mapped, splitting its intervals wherever the sources divide them.
+    pub fn translate(&self, set: &IntervalSet<T>) -> IntervalSet<T> {
+        let mut unmapped = set.clone();
+        let mut mapped = IntervalSet::new();
+        for &(start, end, destination) in &self.entries {
+            let source = IntervalSet::inclusive(start, end);
+            for range in set.intersection(&source).iter() {
+                let (s, e) = range.into_inner();
+                mapped.insert(s - start + destination..=e - start + destination);
+            }
+            unmapped = unmapped.difference(&source);
+        }
+        mapped.union(&unmapped)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use proptest::prelude::*;
+    use rstest::*;
+    use std::collections::BTreeSet;
+
+    #[rstest]
+    fn merges_touching_ranges() {
+        let set: IntervalSet<u64> = [3..=5, 10..=14, 16..=20, 12..=18].into_iter().collect();
+        assert_eq!(set.iter().collect::<Vec<_>>(), vec![3..=5, 10..=20]);
+        assert_eq!(set.len(), 14);
+        let joined = set.union(&IntervalSet::inclusive(6, 9));
+        assert_eq!(joined.iter().collect::<Vec<_>>(), vec![3..=20]);
+    }
+
+    #[rstest]
+    fn reaches_the_end_of_the_type() {
+        let mut set: IntervalSet<u32> = [0..=2, 5..=8, 4..=7].into_iter().collect();
+        set.insert(u32::MAX - 1..=u32::MAX);
+        assert!(set.contains(u32::MAX));
+        let gaps = set.gaps(0..=u32::MAX).collect::<Vec<_>>();
+        assert_eq!(gaps, vec![3..=3, 9..=u32::MAX - 2]);
+    }
+
+    #[rstest]
+    fn exclusive_constructor() {
+        assert!(IntervalSet::exclusive(4, 4).is_empty());
+        assert_eq!(
+            IntervalSet::exclusive(-2i32, 3),
+            IntervalSet::inclusive(-2, 2)
+        );
+    }
+
+    #[rstest]
+    fn translates_seed_ranges() {
+        let map = RangeMap::new()
+            .with_range(98..=99, 50)
+            .with_range(50..=97, 52);
+        assert_eq!(map.map(79), 81);
+        assert_eq!(map.map(10), 10);
+        let seeds = IntervalSet::inclusive(90u64, 105);
+        let soil = map.translate(&seeds);
+        assert_eq!(soil.iter().collect::<Vec<_>>(), vec![50..=51, 92..=105]);
+        assert_eq!(soil.len(), seeds.len());
+    }
+
+    fn model(set: &IntervalSet<u16>) -> BTreeSet<u16> {
+        set.iter().flatten().collect()
+    }
+
+    fn ranges() -> impl Strategy<Value = Vec<(u16, u16)>> {
+        prop::collection::vec((0u16..300, 0u16..40), 0..8)
+    }
+
+    fn build(ranges: &[(u16, u16)]) -> IntervalSet<u16> {
+        ranges.iter().map(|&(s, n)| s..=s + n).collect()
+    }
+
+    fn is_normalised(set: &IntervalSet<u16>) -> bool {
+        set.ranges.iter().all(|&(s, e)| s <= e)
+            && set.ranges.windows(2).all(|w| w[0].1 + 1 < w[1].0)
+    }
+
+    proptest! {
+        #[test]
+        fn set_operations_match_a_model(a in ranges(), b in ranges()) {
+            let (x, y) = (build(&a), build(&b));
+            let (mx, my) = (model(&x), model(&y));
+            for set in [x.union(&y), x.intersection(&y), x.difference(&y)] {
+                prop_assert!(is_normalised(&set));
+            }
+            prop_assert_eq!(model(&x.union(&y)), &mx | &my);
+            prop_assert_eq!(model(&x.intersection(&y)), &mx & &my);
+            prop_assert_eq!(model(&x.difference(&y)), &mx - &my);
+            prop_assert_eq!(x.len() as usize, mx.len());
+            for v in 0..345 {
+                prop_assert_eq!(x.contains(v), mx.contains(&v));
+            }
+        }
+
+        #[test]
+        fn removal_and_gaps(a in ranges(), (s, n) in (0u16..300, 0u16..40)) {
+            let mut x = build(&a);
+            let expected: BTreeSet<u16> = model(&x).into_iter().filter(|v| !(s..=s + n).contains(v)).collect();
+            x.remove(s..=s + n);
+            prop_assert!(is_normalised(&x));
+            prop_assert_eq!(model(&x), expected.clone());
+            let gaps: BTreeSet<u16> = x.gaps(0..=350).flatten().collect();
+            prop_assert_eq!(gaps, (0..=350).filter(|v| !expected.contains(v)).collect::<BTreeSet<_>>());
+        }
+
+        #[test]
+        fn translation_matches_pointwise_mapping(a in ranges(), offsets in prop::collection::vec(0u16..100, 3)) {
+            let map = RangeMap::new()
+                .with_range(0..=99, offsets[0] + 400)
+                .with_range(100..=179, offsets[1] + 600)
+                .with_range(200..=259, offsets[2] + 800);
+            let x = build(&a);
+            let expected: BTreeSet<u16> = model(&x).into_iter().map(|v| map.map(v)).collect();
+            prop_assert_eq!(model(&map.translate(&x)), expected);
+        }
+    }
+}
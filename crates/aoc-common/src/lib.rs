@@ -1,4 +1,23 @@
+pub mod automaton;
+pub mod bitgrid;
+pub mod cycle;
+pub mod dsu;
 mod enums;
 mod error;
+pub mod geom;
+pub mod geom2d;
+pub mod graph;
+pub mod grid;
+pub mod hashing;
+pub mod hex;
+pub mod interval;
+pub mod linear;
+pub mod math;
+pub mod memo;
+pub mod parse;
 pub mod prelude;
+pub mod render;
+pub mod search;
+pub mod simulation;
 mod task;
+pub mod trace;
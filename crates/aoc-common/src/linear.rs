@@ -0,0 +1,376 @@
+//! Exact solutions of small integer linear systems.
+//!
+//! Elimination is fraction-free (Bareiss), so every intermediate value is an integer minor of the
+//! input and nothing is rounded; only the final back-substitution produces [`Rational`]s.
+//! Entries are widened to `i128`, which leaves room for the products of puzzle-sized inputs.
+
+use crate::error::{AdventError, Result};
+use crate::math::extended_gcd;
+use std::cmp::Ordering;
+use std::fmt::{Display, Formatter};
+use std::ops::{Add, Div, Mul, Neg, Sub};
+
+fn gcd(a: i128, b: i128) -> i128 {
+    let (mut a, mut b) = (a.abs(), b.abs());
+    while b != 0 {
+        (a, b) = (b, a % b);
+    }
+    a
+}
+
+/// A fraction in lowest terms with a positive denominator.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct Rational {
+    num: i128,
+    den: i128,
+}
+
+impl Rational {
+    pub const ZERO: Self = Self { num: 0, den: 1 };
+    pub const ONE: Self = Self { num: 1, den: 1 };
+
+    /// `num / den`, which panics if `den` is zero.
+    pub fn new(num: i128, den: i128) -> Self {
+        assert_ne!(den, 0, "Rational with a zero denominator");
+        let g = gcd(num, den) * den.signum();
+        Self {
+            num: num / g,
+            den: den / g,
+        }
+    }
+    pub fn numerator(self) -> i128 {
+        self.num
+    }
+    pub fn denominator(self) -> i128 {
+        self.den
+    }
+    pub fn is_integer(self) -> bool {
+        self.den == 1
+    }
+    pub fn to_integer(self) -> Option<i128> {
+        self.is_integer().then_some(self.num)
+    }
+}
+
+impl From<i64> for Rational {
+    fn from(n: i64) -> Self {
+        Self {
+            num: n as i128,
+            den: 1,
+        }
+    }
+}
+
+impl Add for Rational {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self::Output {
+        Self::new(
+            self.num * other.den + other.num * self.den,
+            self.den * other.den,
+        )
+    }
+}
+
+impl Sub for Rational {
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self::Output {
+        self + -other
+    }
+}
+
+impl Mul for Rational {
+    type Output = Self;
+
+    fn mul(self, other: Self) -> Self::Output {
+        Self::new(self.num * other.num, self.den * other.den)
+    }
+}
+
+impl Div for Rational {
+    type Output = Self;
+
+    fn div(self, other: Self) -> Self::Output {
+        Self::new(self.num * other.den, self.den * other.num)
+    }
+}
+
+impl Neg for Rational {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        Self {
+            num: -self.num,
+            den: self.den,
+        }
+    }
+}
+
+impl PartialOrd for Rational {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Rational {
+    fn cmp(&self, other: &Self) -> Ordering {
+        (self.num * other.den).cmp(&(other.num * self.den))
+    }
+}
+
+impl Display for Rational {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        if self.den == 1 {
+            write!(f, "{}", self.num)
+        } else {
+            write!(f, "{}/{}", self.num, self.den)
+        }
+    }
+}
+
+/// The outcome of solving `A x = b`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Solution {
+    Unique(Vec<Rational>),
+    /// Consistent, but some unknowns are free.
+    Infinite,
+    None,
+}
+
+/// Bareiss elimination to row echelon form, returning the pivot columns.
+///
+/// Only the first `columns` columns are used as pivots; any further ones ride along, which is
+/// how the right-hand side of an augmented matrix is carried.
+fn eliminate(m: &mut [Vec<i128>], columns: usize) -> Vec<usize> {
+    let mut pivots = Vec::new();
+    let mut previous = 1;
+    for col in 0..columns {
+        let row = pivots.len();
+        let Some(r) = (row..m.len()).find(|&r| m[r][col] != 0) else {
+            continue;
+        };
+        m.swap(row, r);
+        let pivot = m[row][col];
+        for i in row + 1..m.len() {
+            for j in col + 1..m[i].len() {
+                m[i][j] = (pivot * m[i][j] - m[i][col] * m[row][j]) / previous;
+            }
+            m[i][col] = 0;
+        }
+        previous = pivot;
+        pivots.push(col);
+    }
+    pivots
+}
+
+fn widen(a: &[Vec<i64>]) -> Vec<Vec<i128>> {
+    a.iter()
+        .map(|row| row.iter().map(|&v| v as i128).collect())
+        .collect()
+}
+
+/// The determinant of a square matrix.
+pub fn determinant(a: &[Vec<i64>]) -> i128 {
+    let mut m = widen(a);
+    let n = m.len();
+    // Bareiss leaves the determinant in the last pivot, up to the sign of the row swaps.
+    let mut sign = 1;
+    let mut previous = 1;
+    for col in 0..n {
+        let Some(r) = (col..n).find(|&r| m[r][col] != 0) else {
+            return 0;
+        };
+        if r != col {
+            m.swap(col, r);
+            sign = -sign;
+        }
+        for i in col + 1..n {
+            for j in col + 1..n {
+                m[i][j] = (m[col][col] * m[i][j] - m[i][col] * m[col][j]) / previous;
+            }
+        }
+        previous = m[col][col];
+    }
+    sign * previous
+}
+
+/// Solves `A x = b` exactly, where `A` has a row per equation.
+///
+/// Fails if `b` doesn't have an entry per row or the rows differ in length.
+pub fn solve(a: &[Vec<i64>], b: &[i64]) -> Result<Solution> {
+    let unknowns = a.first().map_or(0, Vec::len);
+    if a.len() != b.len() || a.iter().any(|row| row.len() != unknowns) {
+        return Err(AdventError::InvalidInput(format!(
+            "{} equations with {} right-hand sides and {unknowns} unknowns",
+            a.len(),
+            b.len()
+        )));
+    }
+    let mut m = widen(a);
+    for (row, &rhs) in m.iter_mut().zip(b) {
+        row.push(rhs as i128);
+    }
+    let pivots = eliminate(&mut m, unknowns);
+    if m[pivots.len()..].iter().any(|row| row[unknowns] != 0) {
+        return Ok(Solution::None);
+    }
+    if pivots.len() < unknowns {
+        return Ok(Solution::Infinite);
+    }
+    let mut x = vec![Rational::ZERO; unknowns];
+    for (row, &col) in pivots.iter().enumerate().rev() {
+        let known = (col + 1..unknowns)
+            .map(|j| Rational::new(m[row][j], 1) * x[j])
+            .fold(Rational::ZERO, Add::add);
+        x[col] = (Rational::new(m[row][unknowns], 1) - known) / Rational::new(m[row][col], 1);
+    }
+    Ok(Solution::Unique(x))
+}
+
+/// Every integer solution of `a x + b y = c`: `(x0 + k dx, y0 - k dy)` for integer `k`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Diophantine {
+    x0: i64,
+    y0: i64,
+    dx: i64,
+    dy: i64,
+}
+
+impl Diophantine {
+    /// The solutions, or `None` if `gcd(a, b)` doesn't divide `c`.
+    pub fn solve(a: i64, b: i64, c: i64) -> Option<Self> {
+        let (g, x, y) = extended_gcd(a, b);
+        if g == 0 {
+            return (c == 0).then_some(Self {
+                x0: 0,
+                y0: 0,
+                dx: 1,
+                dy: 0,
+            });
+        }
+        if c % g != 0 {
+            return None;
+        }
+        let scale = (c / g) as i128;
+        Some(Self {
+            x0: (x as i128 * scale) as i64,
+            y0: (y as i128 * scale) as i64,
+            dx: b / g,
+            dy: a / g,
+        })
+    }
+    /// The `k`th solution.
+    pub fn at(&self, k: i64) -> (i64, i64) {
+        (self.x0 + k * self.dx, self.y0 - k * self.dy)
+    }
+    /// The range of `k` for which both `x` and `y` are non-negative, if any.
+    ///
+    /// Needs `a` and `b` to have the same sign, or the range would be unbounded.
+    pub fn non_negative(&self) -> Option<(i64, i64)> {
+        let (mut low, mut high) = (i64::MIN, i64::MAX);
+        // Each of x and y is `base + k step >= 0`, bounding k on one side.
+        for (base, step) in [(self.x0, self.dx), (self.y0, -self.dy)] {
+            match step.cmp(&0) {
+                Ordering::Greater => low = low.max(-base.div_euclid(step)),
+                Ordering::Less => high = high.min(base.div_euclid(-step)),
+                Ordering::Equal if base < 0 => return None,
+                Ordering::Equal => {}
+            }
+        }
+        (low <= high && low > i64::MIN && high < i64::MAX).then_some((low, high))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use rstest::*;
+
+    #[rstest]
+    fn rationals() {
+        let half = Rational::new(2, 4);
+        assert_eq!(half, Rational::new(-1, -2));
+        assert_eq!(half + Rational::new(1, 3), Rational::new(5, 6));
+        assert_eq!((half * Rational::from(4)).to_integer(), Some(2));
+        assert_eq!(Rational::new(3, -6).to_string(), "-1/2");
+        assert!(Rational::new(-1, 2) < Rational::ZERO);
+    }
+
+    #[rstest]
+    #[case(vec![vec![2, 0], vec![0, 3]], 6)]
+    #[case(vec![vec![0, 1], vec![1, 0]], -1)]
+    #[case(vec![vec![2, -3, 1], vec![2, 0, -1], vec![1, 4, 5]], 49)]
+    #[case(vec![vec![1, 2], vec![2, 4]], 0)]
+    fn determinants(#[case] a: Vec<Vec<i64>>, #[case] det: i128) {
+        assert_eq!(determinant(&a), det);
+    }
+
+    #[rstest]
+    fn systems() -> Result<()> {
+        let x = |v: &[(i128, i128)]| {
+            Solution::Unique(v.iter().map(|&(n, d)| Rational::new(n, d)).collect())
+        };
+        let a = vec![vec![94, 22], vec![34, 67]];
+        assert_eq!(solve(&a, &[8400, 5400])?, x(&[(80, 1), (40, 1)]));
+        assert_eq!(
+            solve(&[vec![2, 1], vec![1, 3]], &[1, 1])?,
+            x(&[(2, 5), (1, 5)])
+        );
+        assert_eq!(
+            solve(&[vec![1, 2], vec![2, 4]], &[3, 6])?,
+            Solution::Infinite
+        );
+        assert_eq!(solve(&[vec![1, 2], vec![2, 4]], &[3, 7])?, Solution::None);
+        // Over-determined but consistent, with a zero leading column entry.
+        let a = vec![vec![0, 1], vec![1, 1], vec![1, 2]];
+        assert_eq!(solve(&a, &[2, 3, 5])?, x(&[(1, 1), (2, 1)]));
+        Ok(())
+    }
+
+    #[rstest]
+    #[case(vec![vec![1, 2], vec![3, 4]], vec![5])]
+    #[case(vec![vec![1, 2]], vec![5, 6])]
+    #[case(vec![vec![1, 2], vec![3]], vec![5, 6])]
+    fn mismatched_systems(#[case] a: Vec<Vec<i64>>, #[case] b: Vec<i64>) {
+        assert!(matches!(solve(&a, &b), Err(AdventError::InvalidInput(_))));
+    }
+
+    #[rstest]
+    fn large_offsets_are_exact() -> Result<()> {
+        let offset = 10_000_000_000_000;
+        let a = vec![vec![26, 67], vec![66, 21]];
+        let Solution::Unique(x) = solve(&a, &[12748 + offset, 12176 + offset])? else {
+            panic!("Expected a unique solution");
+        };
+        assert_eq!(x[0].to_integer(), Some(118679050709));
+        assert_eq!(x[1].to_integer(), Some(103199174542));
+        Ok(())
+    }
+
+    #[rstest]
+    #[case(3, 5, 22, Some(1))]
+    #[case(2, 4, 20, Some(6))]
+    #[case(4, 6, 7, None)]
+    fn diophantine(#[case] a: i64, #[case] b: i64, #[case] c: i64, #[case] count: Option<i64>) {
+        let solutions = Diophantine::solve(a, b, c);
+        assert_eq!(solutions.is_some(), count.is_some());
+        let Some(solutions) = solutions else {
+            return;
+        };
+        for k in -3..3 {
+            let (x, y) = solutions.at(k);
+            assert_eq!(a * x + b * y, c);
+        }
+        let (low, high) = solutions.non_negative().unwrap();
+        assert_eq!(Some(high - low + 1), count);
+        for k in low..=high {
+            let (x, y) = solutions.at(k);
+            assert!(x >= 0 && y >= 0);
+        }
+        for k in [low - 1, high + 1] {
+            let (x, y) = solutions.at(k);
+            assert!(x < 0 || y < 0);
+        }
+    }
+}
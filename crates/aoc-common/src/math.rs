@@ -0,0 +1,272 @@
+//! Number theory over machine integers.
+//!
+//! Modular helpers take `u64` moduli and widen to `u128` for products and `i128` for inverses,
+//! so any non-zero modulus up to `u64::MAX` is safe. [`mod_inverse`], [`crt`] and
+//! [`discrete_log`] give `None` for a modulus of 0. Congruences are `(residue, modulus)` pairs.
+
+use std::collections::HashMap;
+
+pub fn gcd(mut a: u64, mut b: u64) -> u64 {
+    while b != 0 {
+        (a, b) = (b, a % b);
+    }
+    a
+}
+
+/// The least common multiple, or 0 if either argument is 0.
+pub fn lcm(a: u64, b: u64) -> u64 {
+    if a == 0 || b == 0 {
+        0
+    } else {
+        a / gcd(a, b) * b
+    }
+}
+
+/// `(g, x, y)` with `g = gcd(a, b) >= 0` and `a * x + b * y = g`.
+pub fn extended_gcd(a: i64, b: i64) -> (i64, i64, i64) {
+    let (mut r0, mut r1) = (a, b);
+    let (mut x0, mut x1) = (1, 0);
+    let (mut y0, mut y1) = (0, 1);
+    while r1 != 0 {
+        let q = r0 / r1;
+        (r0, r1) = (r1, r0 - q * r1);
+        (x0, x1) = (x1, x0 - q * x1);
+        (y0, y1) = (y1, y0 - q * y1);
+    }
+    if r0 < 0 {
+        (-r0, -x0, -y0)
+    } else {
+        (r0, x0, y0)
+    }
+}
+
+/// `x` in `0..m` with `a * x ≡ 1 (mod m)`, if `m` is positive and `a` and `m` are coprime.
+pub fn mod_inverse(a: i64, m: i64) -> Option<i64> {
+    wide_inverse(a.into(), m.into()).map(|x| x as i64)
+}
+
+/// [`mod_inverse`] in `i128`, which holds every `u64` modulus as well.
+fn wide_inverse(a: i128, m: i128) -> Option<i128> {
+    if m <= 0 {
+        return None;
+    }
+    let (mut r0, mut r1) = (m, a.rem_euclid(m));
+    let (mut x0, mut x1) = (0, 1);
+    while r1 != 0 {
+        let q = r0 / r1;
+        (r0, r1) = (r1, r0 - q * r1);
+        (x0, x1) = (x1, x0 - q * x1);
+    }
+    (r0 == 1).then(|| x0.rem_euclid(m))
+}
+
+/// `a * b mod m` without overflowing.
+pub fn mulmod(a: u64, b: u64, m: u64) -> u64 {
+    (a as u128 * b as u128 % m as u128) as u64
+}
+
+/// `base ^ exp mod m` by repeated squaring.
+pub fn modpow(base: u64, mut exp: u64, m: u64) -> u64 {
+    let mut result = 1 % m;
+    let mut base = base % m;
+    while exp > 0 {
+        if exp & 1 == 1 {
+            result = mulmod(result, base, m);
+        }
+        base = mulmod(base, base, m);
+        exp >>= 1;
+    }
+    result
+}
+
+/// Combines congruences `x ≡ r (mod m)` into one `(r, lcm of the moduli)`.
+///
+/// The moduli need not be coprime; returns `None` when the congruences contradict each other
+/// or a modulus is 0.
+/// Residues may be negative and are normalised into `0..m`. The combined modulus must fit in a
+/// `u64`.
+pub fn crt(congruences: &[(i64, u64)]) -> Option<(u64, u64)> {
+    let (mut r, mut m) = (0i128, 1i128);
+    for &(r2, m2) in congruences {
+        if m2 == 0 {
+            return None;
+        }
+        let m2 = m2 as i128;
+        let r2 = (r2 as i128).rem_euclid(m2);
+        let g = gcd(m as u64, m2 as u64) as i128;
+        if (r2 - r) % g != 0 {
+            return None;
+        }
+        // Solve m * k ≡ r2 - r (mod m2) for k, then x = r + m * k.
+        let step = m2 / g;
+        let inverse = wide_inverse(m / g % step, step).unwrap_or(0) as u64;
+        let k = mulmod(((r2 - r) / g).rem_euclid(step) as u64, inverse, step as u64);
+        r += m * k as i128;
+        m *= step;
+        r = r.rem_euclid(m);
+    }
+    Some((r as u64, m as u64))
+}
+
+/// The smallest `x` with `base ^ x ≡ target (mod m)`, by baby-step giant-step.
+///
+/// Needs `base` coprime to `m`, which always holds for a prime modulus and a non-zero base.
+/// The baby-step table has `√m` entries, so this is only practical for moduli up to about
+/// 2^40.
+pub fn discrete_log(base: u64, target: u64, m: u64) -> Option<u64> {
+    if m == 0 {
+        return None;
+    }
+    let target = target % m;
+    let n = isqrt(m) + 1;
+    let mut baby = HashMap::with_capacity(n as usize);
+    let mut value = 1 % m;
+    for j in 0..n {
+        baby.entry(value).or_insert(j);
+        value = mulmod(value, base, m);
+    }
+    let factor = wide_inverse(modpow(base, n, m).into(), m.into())? as u64;
+    let mut gamma = target;
+    for i in 0..n {
+        if let Some(&j) = baby.get(&gamma) {
+            return Some(i * n + j);
+        }
+        gamma = mulmod(gamma, factor, m);
+    }
+    None
+}
+
+/// The largest `r` with `r * r <= n`.
+pub fn isqrt(n: u64) -> u64 {
+    let mut r = (n as f64).sqrt() as u64;
+    while r.checked_mul(r).is_none_or(|sq| sq > n) {
+        r -= 1;
+    }
+    while (r + 1).checked_mul(r + 1).is_some_and(|sq| sq <= n) {
+        r += 1;
+    }
+    r
+}
+
+/// Every divisor of `n`, in ascending order.
+pub fn divisors(n: u64) -> Vec<u64> {
+    let mut small = Vec::new();
+    let mut large = Vec::new();
+    for d in (1..=isqrt(n)).filter(|&d| n.is_multiple_of(d)) {
+        small.push(d);
+        if d != n / d {
+            large.push(n / d);
+        }
+    }
+    small.extend(large.into_iter().rev());
+    small
+}
+
+/// The sum of every divisor of `n`, from its prime factorisation.
+pub fn divisor_sum(mut n: u64) -> u64 {
+    let mut sum = 1;
+    let mut p = 2;
+    while p <= n / p {
+        let mut term = 1;
+        let mut power = 1;
+        while n.is_multiple_of(p) {
+            n /= p;
+            power *= p;
+            term += power;
+        }
+        sum *= term;
+        p += 1;
+    }
+    if n > 1 { sum * (n + 1) } else { sum }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use rstest::*;
+
+    #[rstest]
+    #[case(12, 18, 6, 36)]
+    #[case(7, 13, 1, 91)]
+    #[case(0, 5, 5, 0)]
+    fn gcd_and_lcm(#[case] a: u64, #[case] b: u64, #[case] g: u64, #[case] l: u64) {
+        assert_eq!(gcd(a, b), g);
+        assert_eq!(lcm(a, b), l);
+    }
+
+    #[rstest]
+    #[case(240, 46)]
+    #[case(-35, 15)]
+    #[case(17, -5)]
+    fn bezout(#[case] a: i64, #[case] b: i64) {
+        let (g, x, y) = extended_gcd(a, b);
+        assert_eq!(g as u64, gcd(a.unsigned_abs(), b.unsigned_abs()));
+        assert_eq!(a * x + b * y, g);
+    }
+
+    #[rstest]
+    fn inverses_and_powers() {
+        assert_eq!(mod_inverse(3, 11), Some(4));
+        assert_eq!(mod_inverse(-3, 11), Some(7));
+        assert_eq!(mod_inverse(6, 9), None);
+        assert_eq!(modpow(2, 10, 1000), 24);
+        assert_eq!(modpow(5, 0, 1), 0);
+        let p = 18_446_744_073_709_551_557; // the largest 64-bit prime
+        assert_eq!(modpow(123_456_789, p - 1, p), 1);
+        assert_eq!(mulmod(p - 1, p - 1, p), 1);
+        let wide = |a: u64, m: u64| wide_inverse(a.into(), m.into()).map(|x| x as u64);
+        assert_eq!(wide(2, p), Some(p / 2 + 1));
+        assert_eq!(wide(p - 1, p), Some(p - 1));
+        assert_eq!(wide(6, 9), None);
+        assert_eq!(mod_inverse(3, 0), None);
+        assert_eq!(mod_inverse(3, 1), Some(0));
+    }
+
+    #[rstest]
+    #[case(&[(0, 7), (-1, 13), (-4, 59), (-6, 31), (-7, 19)], Some((1068781, 3162341)))]
+    #[case(&[(2, 6), (8, 10)], Some((8, 30)))]
+    #[case(&[(1, 4), (2, 6)], None)]
+    #[case(&[], Some((0, 1)))]
+    #[case(&[(1, 3), (2, 0)], None)]
+    #[case(&[(-1, 18_446_744_073_709_551_557)], Some((18_446_744_073_709_551_556, 18_446_744_073_709_551_557)))]
+    #[case(&[(3, 1 << 32), (5, (1 << 32) - 1)], Some((8_589_934_595, 18_446_744_069_414_584_320)))]
+    fn chinese_remainders(
+        #[case] congruences: &[(i64, u64)],
+        #[case] expected: Option<(u64, u64)>,
+    ) {
+        assert_eq!(crt(congruences), expected);
+    }
+
+    #[rstest]
+    #[case(7, 5764801, 20201227, Some(8))]
+    #[case(7, 17807724, 20201227, Some(11))]
+    #[case(2, 3, 7, None)]
+    #[case(2, 3, 0, None)]
+    fn discrete_logs(
+        #[case] base: u64,
+        #[case] target: u64,
+        #[case] m: u64,
+        #[case] x: Option<u64>,
+    ) {
+        assert_eq!(discrete_log(base, target, m), x);
+    }
+
+    #[rstest]
+    #[case(0, 0)]
+    #[case(15, 3)]
+    #[case(16, 4)]
+    #[case(u64::MAX, 4_294_967_295)]
+    fn integer_sqrt(#[case] n: u64, #[case] r: u64) {
+        assert_eq!(isqrt(n), r);
+    }
+
+    #[rstest]
+    #[case(1, vec![1])]
+    #[case(36, vec![1, 2, 3, 4, 6, 9, 12, 18, 36])]
+    #[case(97, vec![1, 97])]
+    #[case(4_294_967_311, vec![1, 4_294_967_311])]
+    fn divisor_lists(#[case] n: u64, #[case] expected: Vec<u64>) {
+        assert_eq!(divisors(n), expected);
+        assert_eq!(divisor_sum(n), expected.iter().sum::<u64>());
+    }
+}
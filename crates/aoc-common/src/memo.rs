@@ -0,0 +1,134 @@
+//! Memoised recursion without `RefCell`.
+//!
+//! A [`Memo`] is a plain cache that the computation borrows mutably. Rather than a closure
+//! calling itself by name, it's handed a `recurse` function for its sub-problems, which looks
+//! each one up and only computes it on a miss. Nothing is borrowed across the recursive calls,
+//! so there's no `BorrowMutError` waiting at the first cache hit inside a miss.
+
+use std::collections::HashMap;
+use std::hash::Hash;
+
+#[derive(Debug, Clone)]
+pub struct Memo<K, V> {
+    cache: HashMap<K, V>,
+}
+
+impl<K, V> Default for Memo<K, V> {
+    fn default() -> Self {
+        Self {
+            cache: HashMap::new(),
+        }
+    }
+}
+
+impl<K: Clone + Hash + Eq, V: Clone> Memo<K, V> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+    pub fn len(&self) -> usize {
+        self.cache.len()
+    }
+    pub fn is_empty(&self) -> bool {
+        self.cache.is_empty()
+    }
+    pub fn get(&self, key: &K) -> Option<&V> {
+        self.cache.get(key)
+    }
+    /// Records a value, e.g. a base case, so it's never computed.
+    pub fn insert(&mut self, key: K, value: V) {
+        self.cache.insert(key, value);
+    }
+    /// Forgets everything, for when whatever the values were computed from changes.
+    pub fn clear(&mut self) {
+        self.cache.clear();
+    }
+
+    /// The value for `key`, computed by `f(recurse, &key)` and remembered if it isn't known yet.
+    ///
+    /// `recurse` gives the value for any other key the same way, so `f` describes one step of
+    /// the recursion and the memo takes care of the rest. A key that depends on itself recurses
+    /// until the stack overflows, just as the unmemoised function would.
+    pub fn get_or_compute<F>(&mut self, key: K, f: &F) -> V
+    where
+        F: Fn(&mut dyn FnMut(K) -> V, &K) -> V,
+    {
+        if let Some(value) = self.cache.get(&key) {
+            return value.clone();
+        }
+        let value = f(&mut |k| self.get_or_compute(k, f), &key);
+        self.cache.insert(key, value.clone());
+        value
+    }
+}
+
+/// A function remembering every answer `f` gives, for one-off recursions that don't need to
+/// keep or reset the [`Memo`] themselves.
+pub fn memoize<K, V, F>(f: F) -> impl FnMut(K) -> V
+where
+    K: Clone + Hash + Eq,
+    V: Clone,
+    F: Fn(&mut dyn FnMut(K) -> V, &K) -> V,
+{
+    let mut memo = Memo::new();
+    move |key| memo.get_or_compute(key, &f)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use rstest::*;
+    use std::cell::Cell;
+
+    #[rstest]
+    fn recursion_is_cached() {
+        let calls = Cell::new(0);
+        let mut memo = Memo::new();
+        let f = |recurse: &mut dyn FnMut(u64) -> u64, &n: &u64| {
+            calls.set(calls.get() + 1);
+            if n < 2 {
+                n
+            } else {
+                recurse(n - 1) + recurse(n - 2)
+            }
+        };
+        assert_eq!(memo.get_or_compute(50, &f), 12_586_269_025);
+        assert_eq!(calls.get(), 51);
+        assert_eq!(memo.get_or_compute(40, &f), 102_334_155);
+        assert_eq!(calls.get(), 51);
+        memo.clear();
+        assert!(memo.is_empty());
+        memo.get_or_compute(10, &f);
+        assert_eq!(calls.get(), 62);
+        assert_eq!(memo.len(), 11);
+    }
+
+    #[rstest]
+    fn seeded_base_cases() {
+        // Ways to make 10 from towels of 1, 2 and 5, counting the order they're laid in.
+        let mut ways = Memo::new();
+        ways.insert(0, 1u64);
+        let f = |recurse: &mut dyn FnMut(u32) -> u64, &n: &u32| {
+            [1, 2, 5]
+                .iter()
+                .filter(|&&towel| towel <= n)
+                .map(|&towel| recurse(n - towel))
+                .sum()
+        };
+        assert_eq!(ways.get_or_compute(10, &f), 128);
+        assert_eq!(ways.get(&5), Some(&9));
+    }
+
+    #[rstest]
+    fn tuple_keys() {
+        // Lattice paths through a grid, keyed by the corner still to reach.
+        let mut paths = memoize(|recurse: &mut dyn FnMut((u32, u32)) -> u64, &(r, c)| {
+            if r == 0 || c == 0 {
+                1
+            } else {
+                recurse((r - 1, c)) + recurse((r, c - 1))
+            }
+        });
+        assert_eq!(paths((16, 16)), 601_080_390);
+        assert_eq!(paths((2, 2)), 6);
+    }
+}
@@ -0,0 +1,182 @@
+//! nom combinators for the shapes puzzle inputs keep coming in.
+//!
+//! Each combinator consumes at most one trailing line ending, so they compose with
+//! [`sections`] and still accept a file that ends in a newline.
+
+use crate::grid::Grid;
+use nom::bytes::complete::tag;
+use nom::character::complete::{char, digit1, line_ending, none_of, one_of, space0, space1};
+use nom::combinator::{map_opt, map_res, opt, recognize};
+use nom::error::{Error, ErrorKind};
+use nom::multi::{many1, separated_list1};
+use nom::sequence::{delimited, separated_pair, terminated};
+use nom::{IResult, Parser};
+use std::str::FromStr;
+
+/// One record per line, e.g. `lines(number::<u32>)` for a column of numbers.
+pub fn lines<'a, O>(
+    line: impl Parser<&'a str, Output = O, Error = Error<&'a str>>,
+) -> impl Parser<&'a str, Output = Vec<O>, Error = Error<&'a str>> {
+    terminated(separated_list1(line_ending, line), opt(line_ending))
+}
+
+/// A rectangle of characters, each turned into a cell by `cell`.
+///
+/// The grid ends at a blank line, the end of input, or the first character `cell` rejects;
+/// rows of different lengths are a failure.
+pub fn grid<'a, T>(
+    mut cell: impl FnMut(char) -> Option<T>,
+) -> impl FnMut(&'a str) -> IResult<&'a str, Grid<T>> {
+    move |i: &'a str| {
+        let row = many1(map_opt(none_of("\r\n"), &mut cell));
+        let (rest, rows) =
+            terminated(separated_list1(line_ending, row), opt(line_ending)).parse(i)?;
+        let grid = Grid::from_rows(rows)
+            .map_err(|_| nom::Err::Failure(Error::new(i, ErrorKind::Verify)))?;
+        Ok((rest, grid))
+    }
+}
+
+/// Two blocks separated by one or more blank lines, e.g. rules then updates.
+pub fn sections<'a, A, B>(
+    first: impl Parser<&'a str, Output = A, Error = Error<&'a str>>,
+    second: impl Parser<&'a str, Output = B, Error = Error<&'a str>>,
+) -> impl Parser<&'a str, Output = (A, B), Error = Error<&'a str>> {
+    separated_pair(first, many1(line_ending), second)
+}
+
+/// An integer with an optional sign, in any type that parses from a string.
+pub fn number<T: FromStr>(i: &str) -> IResult<&str, T> {
+    map_res(recognize((opt(one_of("+-")), digit1)), str::parse).parse(i)
+}
+
+/// Comma separated numbers, with or without spaces after the commas.
+pub fn csv_nums<T: FromStr>(i: &str) -> IResult<&str, Vec<T>> {
+    separated_list1((char(','), space0), number).parse(i)
+}
+
+/// Numbers on one line separated by spaces or tabs, e.g. `0 -3 12`.
+pub fn signed_ints<T: FromStr>(i: &str) -> IResult<&str, Vec<T>> {
+    separated_list1(space1, number).parse(i)
+}
+
+/// `key <separator> value`, allowing spaces around the separator, e.g. `Register A: 729`.
+pub fn key_value<'a, K, V>(
+    key: impl Parser<&'a str, Output = K, Error = Error<&'a str>>,
+    separator: &'a str,
+    value: impl Parser<&'a str, Output = V, Error = Error<&'a str>>,
+) -> impl Parser<&'a str, Output = (K, V), Error = Error<&'a str>> {
+    separated_pair(key, delimited(space0, tag(separator), space0), value)
+}
+
+/// Every integer in `text`, ignoring whatever surrounds them.
+///
+/// A `-` counts as a sign only when it isn't straight after a digit, so `3-5` is `3` and `5`.
+/// Numbers that don't fit `T` with their sign are tried without it, then skipped.
+pub fn extract_ints<T: FromStr>(text: &str) -> Vec<T> {
+    let bytes = text.as_bytes();
+    let mut ints = Vec::new();
+    let mut i = 0;
+    while i < bytes.len() {
+        let start = i;
+        let signed = bytes[i] == b'-'
+            && bytes.get(i + 1).is_some_and(u8::is_ascii_digit)
+            && (i == 0 || !bytes[i - 1].is_ascii_digit());
+        let digits = if signed { i + 1 } else { i };
+        if !bytes[digits].is_ascii_digit() {
+            i += 1;
+            continue;
+        }
+        i = digits;
+        while i < bytes.len() && bytes[i].is_ascii_digit() {
+            i += 1;
+        }
+        if let Ok(value) = text[start..i].parse().or_else(|_| text[digits..i].parse()) {
+            ints.push(value);
+        }
+    }
+    ints
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::error::{AdventErrorExt, Result};
+    use nom::character::complete::alpha1;
+    use rstest::*;
+
+    #[rstest]
+    #[case("1\n2\n3", vec![1, 2, 3])]
+    #[case("1\r\n-2\r\n", vec![1, -2])]
+    fn numbers_per_line(#[case] input: &str, #[case] expected: Vec<i32>) -> Result<()> {
+        assert_eq!(
+            lines(number::<i32>).parse(input).map_and_finish()?,
+            expected
+        );
+        Ok(())
+    }
+
+    #[rstest]
+    fn grids() {
+        let (rest, walls) = grid(|c| match c {
+            '#' => Some(true),
+            '.' => Some(false),
+            _ => None,
+        })("#.#\n..#\n\nrest")
+        .unwrap();
+        assert_eq!((walls.width(), walls.height()), (3, 2));
+        assert!(walls[(1usize, 2usize)]);
+        assert_eq!(rest, "\nrest");
+        let ragged = grid(Some)("ab\nc");
+        assert!(matches!(ragged, Err(nom::Err::Failure(_))));
+    }
+
+    #[rstest]
+    fn two_sections() -> Result<()> {
+        let input = "47|53\n97|13\n\n75,47,61\n97,61\n";
+        let rule = separated_pair(number::<u32>, char('|'), number::<u32>);
+        let (rules, updates) = sections(lines(rule), lines(csv_nums::<u32>))
+            .parse(input)
+            .map_and_finish()?;
+        assert_eq!(rules, vec![(47, 53), (97, 13)]);
+        assert_eq!(updates, vec![vec![75, 47, 61], vec![97, 61]]);
+        Ok(())
+    }
+
+    #[rstest]
+    fn comma_and_space_separated() -> Result<()> {
+        assert_eq!(
+            csv_nums::<u8>("0,3, 5,4").map_and_finish()?,
+            vec![0, 3, 5, 4]
+        );
+        assert_eq!(
+            signed_ints::<i64>("0 -3\t+12").map_and_finish()?,
+            vec![0, -3, 12]
+        );
+        assert!(csv_nums::<u8>("300").is_err());
+        Ok(())
+    }
+
+    #[rstest]
+    fn key_values() -> Result<()> {
+        let mut register = key_value(tag("Register A"), ":", number::<u64>);
+        assert_eq!(register.parse("Register A: 729").map_and_finish()?.1, 729);
+        let mut wire = key_value(alpha1, "->", alpha1);
+        assert_eq!(wire.parse("abc -> xy").map_and_finish()?, ("abc", "xy"));
+        Ok(())
+    }
+
+    #[rstest]
+    #[case("p=0,4 v=3,-3", vec![0, 4, 3, -3])]
+    #[case("#1 @ 12,3: 5x4", vec![1, 12, 3, 5, 4])]
+    #[case("range 3-5, then -7", vec![3, 5, -7])]
+    #[case("no numbers", vec![])]
+    fn extracting(#[case] text: &str, #[case] expected: Vec<i64>) {
+        assert_eq!(extract_ints::<i64>(text), expected);
+    }
+
+    #[rstest]
+    fn extracting_unsigned() {
+        assert_eq!(extract_ints::<u8>("x=-3, y=300, z=7"), vec![3, 7]);
+    }
+}
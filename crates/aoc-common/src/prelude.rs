@@ -1,6 +1,9 @@
-pub use crate::enums::{Day, Event, Input};
+pub use crate::enums::{Day, Event, Input, Part, Status};
 pub use crate::error::{AdventError, AdventErrorExt, Result};
+pub use crate::render::{Cell, Color, Frame, Frames, Render};
+pub use crate::simulation::Simulation;
 pub use crate::task::Task;
+pub use crate::trace::{debug, debug_span, info, info_span, trace, trace_span, warn};
 pub use itertools::Itertools;
 pub use nom::{IResult, Parser};
 pub use strum::{EnumIter, IntoEnumIterator};
@@ -0,0 +1,460 @@
+//! Drawing grid-shaped state as text, terminal colour, images and animations.
+//!
+//! A type implements [`Render`] by describing each cell as a glyph and a colour. Snapshots are
+//! kept as [`Frame`]s, collected by [`Frames`] while a simulation runs, and encoded with
+//! [`RenderFormat::encode`].
+use crate::error::{AdventError, Result};
+use itertools::Itertools;
+use std::collections::HashMap;
+use std::path::Path;
+use std::str::FromStr;
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct Color {
+    pub r: u8,
+    pub g: u8,
+    pub b: u8,
+}
+
+impl Color {
+    pub const BLACK: Color = Color::rgb(0, 0, 0);
+    pub const DARK_GREY: Color = Color::rgb(48, 48, 48);
+    pub const GREY: Color = Color::rgb(128, 128, 128);
+    pub const WHITE: Color = Color::rgb(255, 255, 255);
+    pub const RED: Color = Color::rgb(220, 50, 47);
+    pub const GREEN: Color = Color::rgb(133, 153, 0);
+    pub const BLUE: Color = Color::rgb(38, 139, 210);
+    pub const YELLOW: Color = Color::rgb(181, 137, 0);
+    pub const ORANGE: Color = Color::rgb(203, 75, 22);
+    pub const MAGENTA: Color = Color::rgb(211, 54, 130);
+    pub const CYAN: Color = Color::rgb(42, 161, 152);
+    pub const VIOLET: Color = Color::rgb(108, 113, 196);
+
+    pub const fn rgb(r: u8, g: u8, b: u8) -> Color {
+        Color { r, g, b }
+    }
+    /// A colour from a small fixed palette, for telling many things apart (e.g. by id).
+    pub fn cycle(index: usize) -> Color {
+        const PALETTE: [Color; 8] = [
+            Color::RED,
+            Color::GREEN,
+            Color::BLUE,
+            Color::YELLOW,
+            Color::ORANGE,
+            Color::MAGENTA,
+            Color::CYAN,
+            Color::VIOLET,
+        ];
+        PALETTE[index % PALETTE.len()]
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Cell {
+    pub glyph: char,
+    pub color: Color,
+}
+
+impl Cell {
+    pub const fn new(glyph: char, color: Color) -> Cell {
+        Cell { glyph, color }
+    }
+}
+
+pub trait Render {
+    /// Width and height in cells.
+    fn size(&self) -> (usize, usize);
+    /// The cell in column `x` of row `y`.
+    fn cell(&self, x: usize, y: usize) -> Cell;
+}
+
+impl<R: Render + ?Sized> Render for &R {
+    fn size(&self) -> (usize, usize) {
+        (**self).size()
+    }
+    fn cell(&self, x: usize, y: usize) -> Cell {
+        (**self).cell(x, y)
+    }
+}
+
+/// Plain glyphs, one line per row.
+pub fn to_text(r: &impl Render) -> String {
+    let (width, height) = r.size();
+    let mut text = String::with_capacity((width + 1) * height);
+    for y in 0..height {
+        text.extend((0..width).map(|x| r.cell(x, y).glyph));
+        text.push('\n');
+    }
+    text
+}
+
+/// Glyphs coloured with 24-bit ANSI escapes, one line per row.
+pub fn to_ansi(r: &impl Render) -> String {
+    let (width, height) = r.size();
+    let mut text = String::new();
+    for y in 0..height {
+        let mut current = None;
+        for x in 0..width {
+            let Cell { glyph, color } = r.cell(x, y);
+            if current != Some(color) {
+                text += &format!("\x1b[38;2;{};{};{}m", color.r, color.g, color.b);
+                current = Some(color);
+            }
+            text.push(glyph);
+        }
+        text += "\x1b[0m\n";
+    }
+    text
+}
+
+/// A captured copy of something renderable.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Frame {
+    width: usize,
+    height: usize,
+    cells: Vec<Cell>,
+}
+
+impl Frame {
+    pub fn capture(r: &impl Render) -> Frame {
+        let (width, height) = r.size();
+        let cells = (0..height)
+            .flat_map(|y| (0..width).map(move |x| (x, y)))
+            .map(|(x, y)| r.cell(x, y))
+            .collect();
+        Frame {
+            width,
+            height,
+            cells,
+        }
+    }
+}
+
+impl Render for Frame {
+    fn size(&self) -> (usize, usize) {
+        (self.width, self.height)
+    }
+    fn cell(&self, x: usize, y: usize) -> Cell {
+        self.cells[y * self.width + x]
+    }
+}
+
+/// Frames recorded while a simulation runs.
+///
+/// A disabled recorder ignores everything, so solvers can record unconditionally at no cost.
+pub struct Frames {
+    enabled: bool,
+    last_only: bool,
+    every: usize,
+    offered: usize,
+    frames: Vec<Frame>,
+}
+
+impl Frames {
+    pub fn new() -> Self {
+        Self {
+            enabled: true,
+            last_only: false,
+            every: 1,
+            offered: 0,
+            frames: vec![],
+        }
+    }
+    pub fn disabled() -> Self {
+        Self {
+            enabled: false,
+            ..Self::new()
+        }
+    }
+    /// A recorder holding only the latest frame, for formats that draw a single image.
+    pub fn last_only() -> Self {
+        Self {
+            last_only: true,
+            ..Self::new()
+        }
+    }
+    /// Only keep every `every`th frame offered, starting with the first.
+    pub fn with_every(mut self, every: usize) -> Self {
+        self.every = every.max(1);
+        self
+    }
+    pub fn record(&mut self, r: &impl Render) {
+        self.record_with(|| r);
+    }
+    /// Record a view that is only built when the frame is kept.
+    pub fn record_with<R: Render>(&mut self, view: impl FnOnce() -> R) {
+        if self.enabled && self.offered.is_multiple_of(self.every) {
+            if self.last_only {
+                self.frames.clear();
+            }
+            self.frames.push(Frame::capture(&view()));
+        }
+        self.offered += 1;
+    }
+    pub fn frames(&self) -> &[Frame] {
+        &self.frames
+    }
+}
+
+impl Default for Frames {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// An RGB image with each cell drawn as a `scale` × `scale` square.
+struct Image {
+    width: usize,
+    height: usize,
+    pixels: Vec<Color>,
+}
+
+impl Image {
+    fn new(frame: &Frame, scale: usize) -> Image {
+        let (width, height) = (frame.width * scale, frame.height * scale);
+        let pixels = (0..height)
+            .flat_map(|y| (0..width).map(move |x| (x, y)))
+            .map(|(x, y)| frame.cell(x / scale, y / scale).color)
+            .collect();
+        Image {
+            width,
+            height,
+            pixels,
+        }
+    }
+    fn rgb(&self) -> Vec<u8> {
+        self.pixels.iter().flat_map(|c| [c.r, c.g, c.b]).collect()
+    }
+    fn to_ppm(&self) -> Vec<u8> {
+        let mut bytes = format!("P6\n{} {}\n255\n", self.width, self.height).into_bytes();
+        bytes.extend(self.rgb());
+        bytes
+    }
+    fn to_png(&self) -> Result<Vec<u8>> {
+        let to_error = |e: png::EncodingError| AdventError::Other(e.to_string());
+        let mut bytes = vec![];
+        let mut encoder = png::Encoder::new(&mut bytes, self.width as u32, self.height as u32);
+        encoder.set_color(png::ColorType::Rgb);
+        encoder.set_depth(png::BitDepth::Eight);
+        let mut writer = encoder.write_header().map_err(to_error)?;
+        writer.write_image_data(&self.rgb()).map_err(to_error)?;
+        writer.finish().map_err(to_error)?;
+        Ok(bytes)
+    }
+}
+
+/// An animated GIF sharing one palette across all frames.
+fn to_gif(frames: &[Frame], scale: usize, delay: u16) -> Result<Vec<u8>> {
+    let to_error = |e: gif::EncodingError| AdventError::Other(e.to_string());
+    let (width, height) = frames
+        .first()
+        .map_or((0, 0), |f| (f.width * scale, f.height * scale));
+    let (Ok(gif_width), Ok(gif_height)) = (u16::try_from(width), u16::try_from(height)) else {
+        return Err(AdventError::InvalidInput(format!(
+            "{width}x{height} is too large for a GIF"
+        )));
+    };
+    if frames
+        .iter()
+        .any(|f| (f.width * scale, f.height * scale) != (width, height))
+    {
+        return Err(AdventError::InvalidInput(
+            "Frames differ in size".to_string(),
+        ));
+    }
+
+    let mut palette: HashMap<Color, u8> = HashMap::new();
+    for cell in frames.iter().flat_map(|f| f.cells.iter()) {
+        if !palette.contains_key(&cell.color) {
+            let index = u8::try_from(palette.len()).map_err(|_| {
+                AdventError::InvalidInput("More than 256 colours in a GIF".to_string())
+            })?;
+            palette.insert(cell.color, index);
+        }
+    }
+    let mut global_palette = vec![0; 3 * palette.len()];
+    for (color, &index) in &palette {
+        let i = 3 * index as usize;
+        global_palette[i..i + 3].copy_from_slice(&[color.r, color.g, color.b]);
+    }
+
+    let mut bytes = vec![];
+    let mut encoder =
+        gif::Encoder::new(&mut bytes, gif_width, gif_height, &global_palette).map_err(to_error)?;
+    encoder
+        .set_repeat(gif::Repeat::Infinite)
+        .map_err(to_error)?;
+    for frame in frames {
+        // Scale one frame at a time; long animations would not fit in memory otherwise.
+        let image = Image::new(frame, scale);
+        let indices = image.pixels.iter().map(|c| palette[c]).collect_vec();
+        let mut frame = gif::Frame::from_indexed_pixels(gif_width, gif_height, indices, None);
+        frame.delay = delay;
+        encoder.write_frame(&frame).map_err(to_error)?;
+    }
+    encoder.into_inner().map_err(to_error)?;
+    Ok(bytes)
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum RenderFormat {
+    /// Every frame as plain text, separated by blank lines.
+    Text,
+    /// Every frame as ANSI-coloured text, separated by blank lines.
+    Ansi,
+    /// The last frame as a binary PPM image.
+    Ppm,
+    /// The last frame as a PNG image.
+    Png,
+    /// Every frame as an animated GIF.
+    Gif,
+}
+
+impl RenderFormat {
+    /// The format matching a file extension.
+    pub fn from_path(path: &Path) -> Result<Self> {
+        path.extension()
+            .and_then(|e| e.to_str())
+            .ok_or_else(|| AdventError::InvalidInput(format!("No extension: {}", path.display())))?
+            .parse()
+    }
+    /// Whether every frame is encoded, rather than just the last.
+    pub fn is_animated(&self) -> bool {
+        !matches!(self, RenderFormat::Ppm | RenderFormat::Png)
+    }
+    /// Encode frames, scaling each cell to `scale` pixels in the image formats.
+    pub fn encode(&self, frames: &[Frame], scale: usize) -> Result<Vec<u8>> {
+        let Some(last) = frames.last() else {
+            return Err(AdventError::InvalidInput("No frames to render".to_string()));
+        };
+        let scale = scale.max(1);
+        match self {
+            RenderFormat::Text => Ok(frames.iter().map(to_text).join("\n").into_bytes()),
+            RenderFormat::Ansi => Ok(frames.iter().map(to_ansi).join("\n").into_bytes()),
+            RenderFormat::Ppm => Ok(Image::new(last, scale).to_ppm()),
+            RenderFormat::Png => Image::new(last, scale).to_png(),
+            RenderFormat::Gif => to_gif(frames, scale, 5),
+        }
+    }
+}
+
+impl FromStr for RenderFormat {
+    type Err = AdventError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "txt" | "text" => Ok(RenderFormat::Text),
+            "ans" | "ansi" => Ok(RenderFormat::Ansi),
+            "ppm" => Ok(RenderFormat::Ppm),
+            "png" => Ok(RenderFormat::Png),
+            "gif" => Ok(RenderFormat::Gif),
+            _ => Err(AdventError::InvalidInput(format!("Unknown format: {s}"))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use rstest::*;
+
+    /// A 3x2 board with a single lit cell that can be moved.
+    struct Board {
+        lit: (usize, usize),
+    }
+
+    impl Render for Board {
+        fn size(&self) -> (usize, usize) {
+            (3, 2)
+        }
+        fn cell(&self, x: usize, y: usize) -> Cell {
+            match (x, y) == self.lit {
+                true => Cell::new('#', Color::RED),
+                false => Cell::new('.', Color::DARK_GREY),
+            }
+        }
+    }
+
+    #[fixture]
+    fn frames() -> Frames {
+        let mut frames = Frames::new();
+        for x in 0..3 {
+            frames.record(&Board { lit: (x, 1) });
+        }
+        frames
+    }
+
+    #[rstest]
+    fn text() {
+        assert_eq!(to_text(&Board { lit: (1, 0) }), ".#.\n...\n");
+    }
+
+    #[rstest]
+    fn ansi() {
+        let ansi = to_ansi(&Board { lit: (2, 0) });
+        assert_eq!(ansi.lines().count(), 2);
+        assert!(ansi.starts_with("\x1b[38;2;48;48;48m..\x1b[38;2;220;50;47m#\x1b[0m\n"));
+    }
+
+    #[rstest]
+    fn recording(frames: Frames) {
+        assert_eq!(frames.frames().len(), 3);
+        assert_eq!(frames.frames()[2].cell(2, 1).glyph, '#');
+
+        let mut sparse = Frames::new().with_every(2);
+        let mut disabled = Frames::disabled();
+        for x in 0..3 {
+            sparse.record(&Board { lit: (x, 0) });
+            disabled.record_with(|| -> Board { panic!("Built a view while disabled") });
+        }
+        assert_eq!(sparse.frames().len(), 2);
+        assert!(disabled.frames().is_empty());
+
+        let mut last = Frames::last_only();
+        for x in 0..3 {
+            last.record(&Board { lit: (x, 0) });
+        }
+        assert_eq!(last.frames().len(), 1);
+        assert_eq!(last.frames()[0].cell(2, 0).glyph, '#');
+    }
+
+    #[rstest]
+    fn ppm(frames: Frames) -> Result<()> {
+        let ppm = RenderFormat::Ppm.encode(frames.frames(), 2)?;
+        let header = b"P6\n6 4\n255\n";
+        assert!(ppm.starts_with(header));
+        assert_eq!(ppm.len(), header.len() + 6 * 4 * 3);
+        Ok(())
+    }
+
+    #[rstest]
+    fn png(frames: Frames) -> Result<()> {
+        let png = RenderFormat::Png.encode(frames.frames(), 2)?;
+        assert!(png.starts_with(b"\x89PNG\r\n\x1a\n"));
+        Ok(())
+    }
+
+    #[rstest]
+    fn gif(frames: Frames) -> Result<()> {
+        let gif = RenderFormat::Gif.encode(frames.frames(), 2)?;
+        assert!(gif.starts_with(b"GIF89a"));
+        assert_eq!(u16::from_le_bytes([gif[6], gif[7]]), 6);
+        Ok(())
+    }
+
+    #[rstest]
+    fn empty_frames_are_rejected() {
+        assert!(RenderFormat::Text.encode(&[], 1).is_err());
+    }
+
+    #[rstest]
+    #[case("out.gif", RenderFormat::Gif)]
+    #[case("out.png", RenderFormat::Png)]
+    #[case("out.ppm", RenderFormat::Ppm)]
+    #[case("out.txt", RenderFormat::Text)]
+    #[case("out.ans", RenderFormat::Ansi)]
+    fn format_from_path(#[case] path: &str, #[case] expected: RenderFormat) -> Result<()> {
+        assert_eq!(RenderFormat::from_path(Path::new(path))?, expected);
+        Ok(())
+    }
+}
@@ -0,0 +1,401 @@
+//! Shortest paths over implicit graphs.
+//!
+//! States are anything hashable and edges come from a `neighbours` closure, so a search never
+//! needs the whole graph built up front. Each search stops once it has settled every state as
+//! cheap as the first goal, which means [`Search::goals`] holds every goal at the optimal cost and
+//! [`Search::on_best_paths`] covers every optimal route to them. With no goal, everything
+//! reachable is settled.
+
+use std::cmp::Reverse;
+use std::collections::hash_map::Entry;
+use std::collections::{BinaryHeap, HashMap, HashSet, VecDeque};
+use std::hash::Hash;
+use std::ops::Add;
+
+/// What a search found: the cost to every settled state, and how it was reached.
+#[derive(Debug, Clone)]
+pub struct Search<S, C> {
+    distances: HashMap<S, C>,
+    /// Every state one step before each state on some optimal path to it.
+    predecessors: HashMap<S, Vec<S>>,
+    goals: Vec<S>,
+}
+
+impl<S: Clone + Eq + Hash, C: Copy> Search<S, C> {
+    fn new() -> Self {
+        Self {
+            distances: HashMap::new(),
+            predecessors: HashMap::new(),
+            goals: vec![],
+        }
+    }
+    pub fn distance(&self, state: &S) -> Option<C> {
+        self.distances.get(state).copied()
+    }
+    pub fn distances(&self) -> &HashMap<S, C> {
+        &self.distances
+    }
+    /// The goals reached at the optimal cost, in the order they were settled.
+    pub fn goals(&self) -> &[S] {
+        &self.goals
+    }
+    /// The cost of reaching a goal.
+    pub fn cost(&self) -> Option<C> {
+        self.goals.first().and_then(|goal| self.distance(goal))
+    }
+    /// The state before this one on the first optimal path found to it.
+    pub fn parent(&self, state: &S) -> Option<&S> {
+        self.predecessors.get(state)?.first()
+    }
+    /// Every state before this one on some optimal path to it.
+    pub fn predecessors(&self, state: &S) -> &[S] {
+        self.predecessors.get(state).map_or(&[], Vec::as_slice)
+    }
+    /// An optimal path from a start to the state, following parents back.
+    pub fn path_to(&self, state: &S) -> Option<Vec<S>> {
+        self.distances.get(state)?;
+        let mut path = vec![state.clone()];
+        while let Some(parent) = self.parent(path.last().expect("Path is never empty")) {
+            path.push(parent.clone());
+        }
+        path.reverse();
+        Some(path)
+    }
+    /// An optimal path to the first goal.
+    pub fn path(&self) -> Option<Vec<S>> {
+        self.path_to(self.goals.first()?)
+    }
+    /// Every state on any optimal path to any goal.
+    pub fn on_best_paths(&self) -> HashSet<S> {
+        let mut seen: HashSet<S> = self.goals.iter().cloned().collect();
+        let mut stack = self.goals.clone();
+        while let Some(state) = stack.pop() {
+            for previous in self.predecessors(&state) {
+                if seen.insert(previous.clone()) {
+                    stack.push(previous.clone());
+                }
+            }
+        }
+        seen
+    }
+}
+
+/// Breadth-first search, where every step costs one.
+pub fn bfs<S, I>(
+    starts: impl IntoIterator<Item = S>,
+    mut neighbours: impl FnMut(&S) -> I,
+    mut is_goal: impl FnMut(&S) -> bool,
+) -> Search<S, usize>
+where
+    S: Clone + Eq + Hash,
+    I: IntoIterator<Item = S>,
+{
+    let mut search = Search::new();
+    let mut queue = VecDeque::new();
+    for start in starts {
+        if search.distances.insert(start.clone(), 0).is_none() {
+            queue.push_back((start, 0));
+        }
+    }
+    let mut best = None;
+    while let Some((state, distance)) = queue.pop_front() {
+        if best.is_some_and(|best| distance > best) {
+            break;
+        }
+        if is_goal(&state) {
+            best = Some(distance);
+            search.goals.push(state);
+            continue;
+        }
+        for next in neighbours(&state) {
+            match search.distances.entry(next.clone()) {
+                Entry::Vacant(entry) => {
+                    entry.insert(distance + 1);
+                    search
+                        .predecessors
+                        .insert(next.clone(), vec![state.clone()]);
+                    queue.push_back((next, distance + 1));
+                }
+                Entry::Occupied(entry) if *entry.get() == distance + 1 => {
+                    search
+                        .predecessors
+                        .entry(next)
+                        .or_default()
+                        .push(state.clone());
+                }
+                Entry::Occupied(_) => {}
+            }
+        }
+    }
+    search
+}
+
+/// Dijkstra's algorithm over non-negative edge costs.
+///
+/// Zero-cost edges are fine for distances, but may leave [`Search::on_best_paths`] short of
+/// routes through states settled after the goal at the same cost.
+pub fn dijkstra<S, C, I>(
+    starts: impl IntoIterator<Item = S>,
+    neighbours: impl FnMut(&S) -> I,
+    is_goal: impl FnMut(&S) -> bool,
+) -> Search<S, C>
+where
+    S: Clone + Eq + Hash,
+    C: Copy + Ord + Default + Add<Output = C>,
+    I: IntoIterator<Item = (S, C)>,
+{
+    astar(starts, neighbours, |_| C::default(), is_goal)
+}
+
+/// A* search, guided by a heuristic that never overestimates the cost to a goal.
+///
+/// The heuristic must also be consistent, never dropping by more than an edge costs, for the
+/// states settled to have their optimal costs.
+pub fn astar<S, C, I>(
+    starts: impl IntoIterator<Item = S>,
+    mut neighbours: impl FnMut(&S) -> I,
+    mut heuristic: impl FnMut(&S) -> C,
+    mut is_goal: impl FnMut(&S) -> bool,
+) -> Search<S, C>
+where
+    S: Clone + Eq + Hash,
+    C: Copy + Ord + Default + Add<Output = C>,
+    I: IntoIterator<Item = (S, C)>,
+{
+    let mut search = Search::new();
+    // The heap orders by estimated total cost; states live alongside, pushed once per improvement.
+    let mut heap = BinaryHeap::new();
+    let mut queued = vec![];
+    let mut settled = HashSet::new();
+    for start in starts {
+        if search
+            .distances
+            .insert(start.clone(), C::default())
+            .is_none()
+        {
+            heap.push(Reverse((heuristic(&start), C::default(), queued.len())));
+            queued.push(start);
+        }
+    }
+    let mut best = None;
+    while let Some(Reverse((estimate, cost, index))) = heap.pop() {
+        if best.is_some_and(|best| estimate > best) {
+            break;
+        }
+        let state = queued[index].clone();
+        if search.distances[&state] < cost || !settled.insert(state.clone()) {
+            continue;
+        }
+        if is_goal(&state) {
+            best = Some(cost);
+            search.goals.push(state);
+            continue;
+        }
+        for (next, step) in neighbours(&state) {
+            let next_cost = cost + step;
+            match search.distances.entry(next.clone()) {
+                Entry::Occupied(entry) if *entry.get() < next_cost => {}
+                Entry::Occupied(entry) if *entry.get() == next_cost => {
+                    search
+                        .predecessors
+                        .entry(next)
+                        .or_default()
+                        .push(state.clone());
+                }
+                entry => {
+                    entry.insert_entry(next_cost);
+                    search
+                        .predecessors
+                        .insert(next.clone(), vec![state.clone()]);
+                    heap.push(Reverse((
+                        next_cost + heuristic(&next),
+                        next_cost,
+                        queued.len(),
+                    )));
+                    queued.push(next);
+                }
+            }
+        }
+    }
+    search
+}
+
+/// A shortest path between two states, searching from both ends until they meet.
+///
+/// Steps must be reversible: `neighbours` has to be symmetric.
+pub fn bidirectional_bfs<S, I>(
+    start: S,
+    goal: S,
+    mut neighbours: impl FnMut(&S) -> I,
+) -> Option<Vec<S>>
+where
+    S: Clone + Eq + Hash,
+    I: IntoIterator<Item = S>,
+{
+    // Each side maps the states it has reached to the state it reached them from.
+    let mut forward: HashMap<S, Option<S>> = HashMap::from([(start.clone(), None)]);
+    let mut backward: HashMap<S, Option<S>> = HashMap::from([(goal.clone(), None)]);
+    let mut forward_layer = vec![start.clone()];
+    let mut backward_layer = vec![goal.clone()];
+
+    let meeting = loop {
+        if let Some(meeting) = forward_layer.iter().find(|s| backward.contains_key(s)) {
+            break meeting.clone();
+        }
+        if forward_layer.is_empty() || backward_layer.is_empty() {
+            return None;
+        }
+        // Grow the smaller frontier by a layer.
+        let (seen, layer) = match forward_layer.len() <= backward_layer.len() {
+            true => (&mut forward, &mut forward_layer),
+            false => (&mut backward, &mut backward_layer),
+        };
+        let mut next_layer = vec![];
+        for state in layer.iter() {
+            for next in neighbours(state) {
+                if let Entry::Vacant(entry) = seen.entry(next.clone()) {
+                    entry.insert(Some(state.clone()));
+                    next_layer.push(next);
+                }
+            }
+        }
+        *layer = next_layer;
+        if let Some(meeting) = layer
+            .iter()
+            .find(|s| forward.contains_key(s) && backward.contains_key(s))
+        {
+            break meeting.clone();
+        }
+    };
+
+    let walk = |side: &HashMap<S, Option<S>>| {
+        let mut states = vec![meeting.clone()];
+        while let Some(Some(previous)) = side.get(states.last().expect("Never empty")) {
+            states.push(previous.clone());
+        }
+        states
+    };
+    let mut path = walk(&forward);
+    path.reverse();
+    path.extend(walk(&backward).into_iter().skip(1));
+    Some(path)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::grid::Grid;
+    use itertools::Itertools;
+    use rstest::*;
+
+    type Pos = (usize, usize);
+
+    #[fixture]
+    fn maze() -> Grid<bool> {
+        let text = "\
+..#....
+.##.##.
+.....#.
+.#.#...";
+        Grid::parse(text, |c| Some(c == '#')).expect("Valid maze")
+    }
+
+    fn open(maze: &Grid<bool>, pos: &Pos) -> Vec<Pos> {
+        maze.neighbours4(*pos).filter(|&n| !maze[n]).collect()
+    }
+
+    #[rstest]
+    fn bfs_finds_distances_and_paths(maze: Grid<bool>) {
+        let search = bfs([(0, 0)], |p| open(&maze, p), |&p| p == (0, 6));
+        assert_eq!(search.cost(), Some(10));
+        let path = search.path().expect("Path");
+        assert_eq!(path.len(), 11);
+        assert_eq!((path[0], path[10]), ((0, 0), (0, 6)));
+        assert!(path.windows(2).all(|w| open(&maze, &w[0]).contains(&w[1])));
+    }
+
+    #[rstest]
+    fn bfs_without_goal_reaches_everything(maze: Grid<bool>) {
+        let search = bfs([(0, 0)], |p| open(&maze, p), |_| false);
+        assert_eq!(search.distances().len(), 20);
+        assert_eq!(search.distance(&(3, 6)), Some(9));
+        assert!(search.goals().is_empty());
+        assert_eq!(search.path(), None);
+    }
+
+    #[rstest]
+    fn all_best_paths(maze: Grid<bool>) {
+        // The wall at (3, 3) leaves a single way round.
+        let search = bfs([(2, 2)], |p| open(&maze, p), |&p| p == (3, 4));
+        assert_eq!(search.cost(), Some(3));
+        assert_eq!(search.predecessors(&(2, 4)), [(2, 3)]);
+        let mut tiles = search.on_best_paths().into_iter().collect::<Vec<_>>();
+        tiles.sort();
+        assert_eq!(tiles, [(2, 2), (2, 3), (2, 4), (3, 4)]);
+
+        let search = dijkstra(
+            [(0, 0)],
+            |p| open(&maze, p).into_iter().map(|n| (n, 1)),
+            |&p| p == (2, 1),
+        );
+        assert_eq!(search.on_best_paths().len(), 4);
+        assert_eq!(search.predecessors(&(2, 1)), [(2, 0)]);
+    }
+
+    #[rstest]
+    fn weighted_costs_and_every_goal() {
+        // A diamond: 0 -> 1 -> 3 and 0 -> 2 -> 3 both cost 5; 0 -> 3 directly costs 7.
+        let edges = HashMap::from([
+            (0, vec![(1, 2), (2, 4), (3, 7)]),
+            (1, vec![(3, 3)]),
+            (2, vec![(3, 1), (4, 1)]),
+            (3, vec![]),
+            (4, vec![]),
+        ]);
+        let search = dijkstra([0], |n| edges[n].clone(), |&n| n == 3 || n == 4);
+        assert_eq!(search.cost(), Some(5));
+        let mut goals = search.goals().to_vec();
+        goals.sort();
+        assert_eq!(goals, [3, 4]);
+        let mut predecessors = search.predecessors(&3).to_vec();
+        predecessors.sort();
+        assert_eq!(predecessors, [1, 2]);
+        assert_eq!(search.on_best_paths().len(), 5);
+    }
+
+    #[rstest]
+    fn astar_agrees_with_dijkstra(maze: Grid<bool>) {
+        let goal: Pos = (3, 6);
+        let steps = |p: &Pos| open(&maze, p).into_iter().map(|n| (n, 1usize));
+        let guided = astar(
+            [(0, 0)],
+            steps,
+            |p| p.0.abs_diff(goal.0) + p.1.abs_diff(goal.1),
+            |&p| p == goal,
+        );
+        let plain = dijkstra([(0, 0)], steps, |&p| p == goal);
+        assert_eq!(guided.cost(), Some(9));
+        assert_eq!(guided.cost(), plain.cost());
+        assert!(guided.distances().len() <= plain.distances().len());
+    }
+
+    #[rstest]
+    fn bidirectional(maze: Grid<bool>) {
+        let path = bidirectional_bfs((0, 0), (0, 6), |p| open(&maze, p)).expect("Path");
+        assert_eq!(path.len(), 11);
+        assert_eq!((path[0], path[10]), ((0, 0), (0, 6)));
+        assert!(path.windows(2).all(|w| open(&maze, &w[0]).contains(&w[1])));
+        assert_eq!(
+            bidirectional_bfs((0, 0), (0, 0), |p| open(&maze, p)),
+            Some(vec![(0, 0)])
+        );
+        // Closing column 3 cuts the maze in two.
+        let cut = |p: &Pos| {
+            open(&maze, p)
+                .into_iter()
+                .filter(|n| n.1 != 3)
+                .collect_vec()
+        };
+        assert_eq!(bidirectional_bfs((0, 0), (0, 6), cut), None);
+    }
+}
@@ -0,0 +1,72 @@
+use crate::render::{Frame, Frames};
+
+/// A puzzle that advances one step at a time, so that it can be played back.
+pub trait Simulation {
+    /// Advance by one step; does nothing once done.
+    fn step(&mut self);
+    /// A picture of the current state.
+    fn render(&self) -> Frame;
+    fn is_done(&self) -> bool;
+    /// A short description of the current state, e.g. the step count.
+    fn status(&self) -> String {
+        String::new()
+    }
+    /// Step until done, recording the starting state and every step after it.
+    fn run(&mut self, frames: &mut Frames) {
+        frames.record_with(|| self.render());
+        while !self.is_done() {
+            self.step();
+            frames.record_with(|| self.render());
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::render::{Cell, Color, Render, to_text};
+    use rstest::*;
+
+    /// Fills a row of cells one per step.
+    struct Fill {
+        filled: usize,
+        width: usize,
+    }
+
+    impl Render for Fill {
+        fn size(&self) -> (usize, usize) {
+            (self.width, 1)
+        }
+        fn cell(&self, x: usize, _y: usize) -> Cell {
+            match x < self.filled {
+                true => Cell::new('#', Color::GREEN),
+                false => Cell::new('.', Color::DARK_GREY),
+            }
+        }
+    }
+
+    impl Simulation for Fill {
+        fn step(&mut self) {
+            self.filled = (self.filled + 1).min(self.width);
+        }
+        fn render(&self) -> Frame {
+            Frame::capture(self)
+        }
+        fn is_done(&self) -> bool {
+            self.filled == self.width
+        }
+    }
+
+    #[rstest]
+    fn runs_to_completion() {
+        let mut fill = Fill {
+            filled: 0,
+            width: 3,
+        };
+        let mut frames = Frames::new();
+        fill.run(&mut frames);
+        assert!(fill.is_done());
+        let texts = frames.frames().iter().map(to_text).collect::<Vec<_>>();
+        assert_eq!(texts, ["...\n", "#..\n", "##.\n", "###\n"]);
+    }
+}
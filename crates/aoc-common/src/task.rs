@@ -1,9 +1,35 @@
-use crate::enums::{Day, Event};
+use crate::enums::{Day, Event, Part, Status};
 use crate::error::Result;
+use crate::render::Frames;
+use crate::simulation::Simulation;
+use crate::trace::info_span;
 
 pub trait Task {
     fn event(&self) -> Event;
     fn day(&self) -> Day;
+    fn status(&self) -> Status;
     fn solve_part1(&self, input: &str) -> Result<String>;
     fn solve_part2(&self, input: &str) -> Result<String>;
+
+    /// Name of the approach used, for solvers that offer more than one.
+    fn strategy(&self) -> &str {
+        "default"
+    }
+    fn solve(&self, part: Part, input: &str) -> Result<String> {
+        let (event, day) = (self.event().year(), self.day().number());
+        let puzzle = format_args!("{event}/{day}");
+        let _span = info_span!("solve", %puzzle, part = part.number()).entered();
+        match part {
+            Part::Part1 => self.solve_part1(input),
+            Part::Part2 => self.solve_part2(input),
+        }
+    }
+    /// Solve a part while recording frames, for solvers that can draw their state.
+    fn solve_with_frames(&self, part: Part, input: &str, _frames: &mut Frames) -> Result<String> {
+        self.solve(part, input)
+    }
+    /// A part as a step-by-step simulation, for solvers that can be played back.
+    fn simulation(&self, _part: Part, _input: &str) -> Result<Option<Box<dyn Simulation>>> {
+        Ok(None)
+    }
 }
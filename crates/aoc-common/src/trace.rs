@@ -0,0 +1,14 @@
+//! Diagnostics for solvers, built on `tracing`.
+//!
+//! Nothing is printed unless a subscriber is installed, which the `aoc` binary does for
+//! `-v`, `-vv` and `--trace-day`. Solvers follow a few conventions so that output stays
+//! comparable from one day to the next:
+//!
+//! - [`Task::solve`](crate::prelude::Task::solve) opens an `info` span named `solve`
+//!   carrying `puzzle` (e.g. `2024/15`) and `part`.
+//! - Input parsing runs inside a `debug` span named `parse`.
+//! - Each simulation step (a move, a tick, a search expansion) is a `debug` event.
+//! - Anything bulkier, such as grid dumps, is a `trace` event.
+pub use tracing::{
+    Level, Span, debug, debug_span, error, info, info_span, trace, trace_span, warn,
+};
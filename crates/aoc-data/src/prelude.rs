@@ -1 +1 @@
-pub use crate::resource::ResourceReader;
+pub use crate::resource::{ResourceReader, read_answer, read_resource, resource_dir};
@@ -15,16 +15,37 @@ where
 
 const MANIFEST_DIR: &str = env!("CARGO_MANIFEST_DIR");
 
-fn read_resource(event: Event, day: Day, input: Input) -> Result<String> {
-    let file_path: PathBuf = [
+const ANSWERS: &str = "answers.txt";
+
+pub fn resource_dir(event: Event, day: Day) -> PathBuf {
+    [
         Path::new(&MANIFEST_DIR),
         Path::new("resources"),
         event.folder_name(),
         day.folder_name(),
-        input.file_name(),
     ]
     .iter()
-    .collect();
+    .collect()
+}
 
+pub fn read_resource(event: Event, day: Day, input: Input) -> Result<String> {
+    let file_path = resource_dir(event, day).join(input.file_name());
     fs::read_to_string(file_path).map_err(AdventError::from)
 }
+
+/// Look up the known answer for an input, if one has been recorded.
+///
+/// Answers live next to the inputs in `answers.txt`, one `input: answer` per line.
+pub fn read_answer(event: Event, day: Day, input: Input) -> Result<Option<String>> {
+    let file_path = resource_dir(event, day).join(ANSWERS);
+    if !file_path.exists() {
+        return Ok(None);
+    }
+    let answers = fs::read_to_string(file_path)?;
+    let answer = answers
+        .lines()
+        .filter_map(|line| line.split_once(':'))
+        .find(|(name, _)| name.trim() == input.name())
+        .map(|(_, answer)| answer.trim().to_string());
+    Ok(answer)
+}
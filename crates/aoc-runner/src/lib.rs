@@ -0,0 +1,6 @@
+mod alloc;
+pub mod prelude;
+mod progress;
+mod registry;
+mod report;
+mod run;
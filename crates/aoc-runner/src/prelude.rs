@@ -0,0 +1,5 @@
+pub use crate::alloc::{Allocations, CountingAllocator};
+pub use crate::progress::{EventProgress, Progress};
+pub use crate::registry::Registry;
+pub use crate::report::{ReportFormat, RunRecord, RunReport, RunStatus};
+pub use crate::run::Runner;
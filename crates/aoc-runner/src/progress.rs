@@ -0,0 +1,111 @@
+use crate::registry::Registry;
+use aoc_common::prelude::*;
+
+/// Completion counts for a single event.
+#[derive(Debug, Clone, PartialEq)]
+pub struct EventProgress {
+    pub event: Event,
+    pub days: usize,
+    pub complete: usize,
+    pub part1: usize,
+    pub unimplemented: usize,
+}
+
+impl EventProgress {
+    fn new(event: Event) -> Self {
+        Self {
+            event,
+            days: 0,
+            complete: 0,
+            part1: 0,
+            unimplemented: 0,
+        }
+    }
+    fn record(&mut self, status: Status) {
+        self.days += 1;
+        match status {
+            Status::Unimplemented => self.unimplemented += 1,
+            Status::Part1 => self.part1 += 1,
+            Status::Complete => self.complete += 1,
+        }
+    }
+    pub fn stars(&self) -> usize {
+        self.complete * Status::Complete.stars() + self.part1 * Status::Part1.stars()
+    }
+    pub fn max_stars(&self) -> usize {
+        self.days * Status::Complete.stars()
+    }
+}
+
+/// Completion counts for every registered event.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Progress {
+    pub events: Vec<EventProgress>,
+}
+
+impl Progress {
+    pub fn new(registry: &Registry) -> Self {
+        let events = registry
+            .events()
+            .into_iter()
+            .map(|event| {
+                registry
+                    .days(event)
+                    .fold(EventProgress::new(event), |mut progress, solver| {
+                        progress.record(solver.status());
+                        progress
+                    })
+            })
+            .collect();
+        Self { events }
+    }
+    pub fn days(&self) -> usize {
+        self.events.iter().map(|e| e.days).sum()
+    }
+    pub fn complete(&self) -> usize {
+        self.events.iter().map(|e| e.complete).sum()
+    }
+    pub fn part1(&self) -> usize {
+        self.events.iter().map(|e| e.part1).sum()
+    }
+    pub fn unimplemented(&self) -> usize {
+        self.events.iter().map(|e| e.unimplemented).sum()
+    }
+    pub fn stars(&self) -> usize {
+        self.events.iter().map(|e| e.stars()).sum()
+    }
+    pub fn max_stars(&self) -> usize {
+        self.events.iter().map(|e| e.max_stars()).sum()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use rstest::*;
+
+    #[fixture]
+    fn progress() -> Progress {
+        Progress::new(&Registry::new())
+    }
+
+    #[rstest]
+    fn totals_add_up(progress: Progress) {
+        assert_eq!(
+            progress.days(),
+            progress.complete() + progress.part1() + progress.unimplemented()
+        );
+        assert_eq!(progress.max_stars(), 2 * progress.days());
+    }
+
+    #[rstest]
+    fn stars_count_parts() {
+        let mut progress = EventProgress::new(Event::Event2015);
+        progress.record(Status::Complete);
+        progress.record(Status::Part1);
+        progress.record(Status::Unimplemented);
+        assert_eq!(progress.days, 3);
+        assert_eq!(progress.stars(), 3);
+        assert_eq!(progress.max_stars(), 6);
+    }
+}
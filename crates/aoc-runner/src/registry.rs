@@ -0,0 +1,90 @@
+use aoc_common::prelude::*;
+
+/// Every solver in the workspace, ordered by event and day.
+pub struct Registry {
+    solvers: Vec<Box<dyn Task>>,
+}
+
+impl Registry {
+    pub fn new() -> Self {
+        let mut solvers = [
+            aoc_2015::solvers(),
+            aoc_2016::solvers(),
+            aoc_2017::solvers(),
+            aoc_2018::solvers(),
+            aoc_2019::solvers(),
+            aoc_2020::solvers(),
+            aoc_2021::solvers(),
+            aoc_2022::solvers(),
+            aoc_2023::solvers(),
+            aoc_2024::solvers(),
+            aoc_2025::solvers(),
+        ]
+        .into_iter()
+        .flatten()
+        .collect_vec();
+        solvers.sort_by_key(|s| (s.event(), s.day()));
+        Self { solvers }
+    }
+    pub fn solvers(&self) -> impl Iterator<Item = &dyn Task> {
+        self.solvers.iter().map(|s| s.as_ref())
+    }
+    pub fn events(&self) -> Vec<Event> {
+        self.solvers().map(|s| s.event()).dedup().collect()
+    }
+    pub fn days(&self, event: Event) -> impl Iterator<Item = &dyn Task> {
+        self.solvers().filter(move |s| s.event() == event)
+    }
+    pub fn get(&self, event: Event, day: Day) -> Option<&dyn Task> {
+        self.solvers()
+            .find(|s| s.event() == event && s.day() == day)
+    }
+}
+
+impl Default for Registry {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use rstest::*;
+
+    #[fixture]
+    fn registry() -> Registry {
+        Registry::new()
+    }
+
+    #[rstest]
+    fn every_event_is_registered(registry: Registry) {
+        assert_eq!(registry.events(), Event::iter().collect_vec());
+    }
+
+    #[rstest]
+    #[case(Event::Event2015, 25)]
+    #[case(Event::Event2016, 25)]
+    #[case(Event::Event2025, 12)]
+    fn days_per_event(registry: Registry, #[case] event: Event, #[case] expected: usize) {
+        assert_eq!(registry.days(event).count(), expected);
+    }
+
+    #[rstest]
+    fn solvers_are_unique(registry: Registry) {
+        let keys = registry
+            .solvers()
+            .map(|s| (s.event(), s.day()))
+            .collect_vec();
+        assert_eq!(keys.iter().unique().count(), keys.len());
+    }
+
+    #[rstest]
+    fn get_finds_solver(registry: Registry) {
+        let solver = registry
+            .get(Event::Event2016, Day::Day1)
+            .expect("Missing solver");
+        assert_eq!(solver.status(), Status::Complete);
+        assert!(registry.get(Event::Event2025, Day::Day25).is_none());
+    }
+}
@@ -0,0 +1,229 @@
+use aoc_common::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum RunStatus {
+    /// The answer matches the recorded one.
+    Correct,
+    /// The answer differs from the recorded one.
+    Incorrect,
+    /// No answer is recorded for this input.
+    Unverified,
+    /// The solver returned an error.
+    Failed,
+    /// The solver does not implement this part.
+    Unimplemented,
+}
+
+impl Display for RunStatus {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            RunStatus::Correct => "correct",
+            RunStatus::Incorrect => "incorrect",
+            RunStatus::Unverified => "unverified",
+            RunStatus::Failed => "failed",
+            RunStatus::Unimplemented => "unimplemented",
+        };
+        write!(f, "{name}")
+    }
+}
+
+/// A single solver run, flat so that it serialises to one CSV row.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RunRecord {
+    pub event: u16,
+    pub day: u8,
+    pub part: u8,
+    pub input: String,
+    pub strategy: String,
+    pub answer: Option<String>,
+    pub expected: Option<String>,
+    pub status: RunStatus,
+    pub error: Option<String>,
+    pub runs: u32,
+    pub min_ns: u64,
+    pub mean_ns: u64,
+    pub max_ns: u64,
+    pub allocations: Option<u64>,
+    pub allocated_bytes: Option<u64>,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum ReportFormat {
+    Json,
+    Csv,
+    Markdown,
+}
+
+impl FromStr for ReportFormat {
+    type Err = AdventError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "json" => Ok(ReportFormat::Json),
+            "csv" => Ok(ReportFormat::Csv),
+            "markdown" | "md" => Ok(ReportFormat::Markdown),
+            _ => Err(AdventError::InvalidInput(format!("Unknown format: {s}"))),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+pub struct RunReport {
+    pub records: Vec<RunRecord>,
+}
+
+impl RunReport {
+    pub fn new(records: Vec<RunRecord>) -> Self {
+        Self { records }
+    }
+    pub fn render(&self, format: ReportFormat) -> Result<String> {
+        match format {
+            ReportFormat::Json => self.to_json(),
+            ReportFormat::Csv => self.to_csv(),
+            ReportFormat::Markdown => Ok(self.to_markdown()),
+        }
+    }
+    pub fn to_json(&self) -> Result<String> {
+        serde_json::to_string_pretty(self).map_err(|e| AdventError::Other(e.to_string()))
+    }
+    pub fn from_json(json: &str) -> Result<Self> {
+        serde_json::from_str(json).map_err(|e| AdventError::InvalidInput(e.to_string()))
+    }
+    pub fn to_csv(&self) -> Result<String> {
+        let to_error = |e: csv::Error| AdventError::Other(e.to_string());
+        let mut writer = csv::Writer::from_writer(vec![]);
+        for record in &self.records {
+            writer.serialize(record).map_err(to_error)?;
+        }
+        let bytes = writer
+            .into_inner()
+            .map_err(|e| AdventError::Other(e.to_string()))?;
+        String::from_utf8(bytes).map_err(|e| AdventError::Other(e.to_string()))
+    }
+    pub fn from_csv(csv: &str) -> Result<Self> {
+        let records = csv::Reader::from_reader(csv.as_bytes())
+            .deserialize()
+            .collect::<std::result::Result<Vec<RunRecord>, _>>()
+            .map_err(|e| AdventError::InvalidInput(e.to_string()))?;
+        Ok(Self::new(records))
+    }
+    pub fn to_markdown(&self) -> String {
+        let mut table = String::from(
+            "\
+| Event | Day | Part | Input | Strategy | Answer | Expected | Status | Mean | Allocations |
+| ----- | --: | ---: | ----- | -------- | ------ | -------- | ------ | ---: | ----------: |
+",
+        );
+        for r in &self.records {
+            table += &format!(
+                "| {} | {} | {} | {} | {} | {} | {} | {} | {} | {} |\n",
+                r.event,
+                r.day,
+                r.part,
+                r.input,
+                r.strategy,
+                r.answer.as_deref().unwrap_or("-"),
+                r.expected.as_deref().unwrap_or("-"),
+                r.status,
+                format_duration(r.mean_ns),
+                r.allocations.map_or("-".to_string(), |a| a.to_string()),
+            );
+        }
+        table
+    }
+}
+
+fn format_duration(ns: u64) -> String {
+    match ns {
+        0..1_000 => format!("{ns} ns"),
+        1_000..1_000_000 => format!("{:.1} µs", ns as f64 / 1e3),
+        1_000_000..1_000_000_000 => format!("{:.1} ms", ns as f64 / 1e6),
+        _ => format!("{:.2} s", ns as f64 / 1e9),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use rstest::*;
+
+    #[fixture]
+    fn report() -> RunReport {
+        RunReport::new(vec![
+            RunRecord {
+                event: 2024,
+                day: 16,
+                part: 1,
+                input: "part1".to_string(),
+                strategy: "default".to_string(),
+                answer: Some("130536".to_string()),
+                expected: Some("130536".to_string()),
+                status: RunStatus::Correct,
+                error: None,
+                runs: 3,
+                min_ns: 900,
+                mean_ns: 1_500,
+                max_ns: 2_500,
+                allocations: Some(42),
+                allocated_bytes: Some(4096),
+            },
+            RunRecord {
+                event: 2023,
+                day: 1,
+                part: 2,
+                input: "part2".to_string(),
+                strategy: "default".to_string(),
+                answer: None,
+                expected: None,
+                status: RunStatus::Unimplemented,
+                error: None,
+                runs: 0,
+                min_ns: 0,
+                mean_ns: 0,
+                max_ns: 0,
+                allocations: None,
+                allocated_bytes: None,
+            },
+        ])
+    }
+
+    #[rstest]
+    fn json_round_trip(report: RunReport) -> Result<()> {
+        assert_eq!(RunReport::from_json(&report.to_json()?)?, report);
+        Ok(())
+    }
+
+    #[rstest]
+    fn csv_round_trip(report: RunReport) -> Result<()> {
+        let csv = report.to_csv()?;
+        assert!(csv.starts_with("event,day,part,input,strategy,answer,expected,status,"));
+        assert_eq!(RunReport::from_csv(&csv)?, report);
+        Ok(())
+    }
+
+    #[rstest]
+    fn markdown_table(report: RunReport) {
+        let markdown = report.to_markdown();
+        assert_eq!(markdown.lines().count(), 4);
+        assert!(markdown.contains(
+            "| 2024 | 16 | 1 | part1 | default | 130536 | 130536 | correct | 1.5 µs | 42 |"
+        ));
+        assert!(
+            markdown
+                .contains("| 2023 | 1 | 2 | part2 | default | - | - | unimplemented | 0 ns | - |")
+        );
+    }
+
+    #[rstest]
+    #[case("json", ReportFormat::Json)]
+    #[case("csv", ReportFormat::Csv)]
+    #[case("md", ReportFormat::Markdown)]
+    fn parse_format(#[case] name: &str, #[case] expected: ReportFormat) -> Result<()> {
+        assert_eq!(name.parse::<ReportFormat>()?, expected);
+        Ok(())
+    }
+}
@@ -0,0 +1,234 @@
+use crate::alloc::Allocations;
+use crate::report::{RunRecord, RunStatus};
+use aoc_common::prelude::*;
+use aoc_data::prelude::*;
+use std::time::{Duration, Instant};
+
+/// Runs solvers against their inputs, timing every repetition.
+pub struct Runner {
+    repeat: u32,
+}
+
+impl Runner {
+    pub fn new() -> Self {
+        Self { repeat: 1 }
+    }
+    pub fn with_repeat(mut self, repeat: u32) -> Self {
+        self.repeat = repeat.max(1);
+        self
+    }
+    /// Run one part against a bundled input, checking it against the recorded answer.
+    ///
+    /// An input's recorded answer is for the part it belongs to, so running the other part on
+    /// it leaves the answer unverified. A missing or empty input fails without being solved.
+    pub fn run(&self, solver: &dyn Task, part: Part, input: Input) -> RunRecord {
+        let (event, day) = (solver.event(), solver.day());
+        let text = read_resource(event, day, input).and_then(|text| {
+            if text.trim().is_empty() {
+                Err(AdventError::InvalidInput(format!(
+                    "No {} input committed",
+                    input.name()
+                )))
+            } else {
+                Ok(text)
+            }
+        });
+        let expected = if input.part() == part {
+            read_answer(event, day, input).ok().flatten()
+        } else {
+            None
+        };
+        match text {
+            Ok(text) => self.run_text(solver, part, input.name(), &text, expected),
+            Err(_) if !solver.status().solves(part) => {
+                let mut record = self.record(solver, part, input.name(), expected);
+                record.status = RunStatus::Unimplemented;
+                record
+            }
+            Err(e) => {
+                let mut record = self.record(solver, part, input.name(), expected);
+                record.status = RunStatus::Failed;
+                record.error = Some(e.to_string());
+                record
+            }
+        }
+    }
+    /// Run one part against arbitrary input text.
+    pub fn run_text(
+        &self,
+        solver: &dyn Task,
+        part: Part,
+        input: &str,
+        text: &str,
+        expected: Option<String>,
+    ) -> RunRecord {
+        let mut record = self.record(solver, part, input, expected);
+        if !solver.status().solves(part) {
+            record.status = RunStatus::Unimplemented;
+            return record;
+        }
+
+        let mut timings = vec![];
+        let before = Allocations::snapshot();
+        for _ in 0..self.repeat {
+            let start = Instant::now();
+            let result = solver.solve(part, text);
+            timings.push(start.elapsed());
+            match result {
+                Ok(answer) => record.answer = Some(answer),
+                Err(e) => {
+                    record.error = Some(e.to_string());
+                    break;
+                }
+            }
+        }
+        let after = Allocations::snapshot();
+
+        // Allocations are reported per run.
+        if let (Some(before), Some(after)) = (before, after) {
+            let total = after.since(&before);
+            let runs = timings.len() as u64;
+            record.allocations = Some(total.count / runs);
+            record.allocated_bytes = Some(total.bytes / runs);
+        }
+
+        record.runs = timings.len() as u32;
+        record.min_ns = as_nanos(timings.iter().min());
+        record.max_ns = as_nanos(timings.iter().max());
+        record.mean_ns = as_nanos(Some(&(timings.iter().sum::<Duration>() / record.runs)));
+        record.status = match (&record.error, &record.answer, &record.expected) {
+            (Some(_), _, _) => RunStatus::Failed,
+            (None, _, None) => RunStatus::Unverified,
+            (None, Some(answer), Some(expected)) if answer == expected => RunStatus::Correct,
+            (None, _, Some(_)) => RunStatus::Incorrect,
+        };
+        record
+    }
+    fn record(
+        &self,
+        solver: &dyn Task,
+        part: Part,
+        input: &str,
+        expected: Option<String>,
+    ) -> RunRecord {
+        RunRecord {
+            event: solver.event().year(),
+            day: solver.day().number(),
+            part: part.number(),
+            input: input.to_string(),
+            strategy: solver.strategy().to_string(),
+            answer: None,
+            expected,
+            status: RunStatus::Unverified,
+            error: None,
+            runs: 0,
+            min_ns: 0,
+            mean_ns: 0,
+            max_ns: 0,
+            allocations: None,
+            allocated_bytes: None,
+        }
+    }
+}
+
+impl Default for Runner {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn as_nanos(duration: Option<&Duration>) -> u64 {
+    duration.map_or(0, |d| d.as_nanos() as u64)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::registry::Registry;
+    use rstest::*;
+
+    #[fixture]
+    fn registry() -> Registry {
+        Registry::new()
+    }
+
+    #[rstest]
+    fn correct_answer(registry: Registry) {
+        let solver = registry
+            .get(Event::Event2016, Day::Day1)
+            .expect("Missing solver");
+        let record = Runner::new()
+            .with_repeat(3)
+            .run(solver, Part::Part1, Input::Part1);
+        assert_eq!(record.answer.as_deref(), Some("301"));
+        assert_eq!(record.status, RunStatus::Correct);
+        assert_eq!(record.runs, 3);
+        assert!(record.min_ns <= record.mean_ns && record.mean_ns <= record.max_ns);
+    }
+
+    #[rstest]
+    fn other_parts_input(registry: Registry) {
+        let solver = registry
+            .get(Event::Event2016, Day::Day1)
+            .expect("Missing solver");
+        let record = Runner::new().run(solver, Part::Part2, Input::Part1);
+        assert!(record.answer.is_some());
+        assert_eq!(record.expected, None);
+        assert_eq!(record.status, RunStatus::Unverified);
+    }
+
+    #[rstest]
+    fn empty_input(registry: Registry) {
+        let solver = registry
+            .get(Event::Event2017, Day::Day11)
+            .expect("Missing solver");
+        let record = Runner::new().run(solver, Part::Part1, Input::Part1);
+        assert_eq!(record.status, RunStatus::Failed);
+        assert_eq!(record.answer, None);
+        assert_eq!(record.runs, 0);
+        assert_eq!(
+            record.error.as_deref(),
+            Some("Invalid input: No part1 input committed")
+        );
+    }
+
+    #[rstest]
+    fn unverified_answer(registry: Registry) {
+        let solver = registry
+            .get(Event::Event2016, Day::Day1)
+            .expect("Missing solver");
+        let record = Runner::new().run_text(solver, Part::Part1, "custom", "R2, L3", None);
+        assert_eq!(record.answer.as_deref(), Some("5"));
+        assert_eq!(record.status, RunStatus::Unverified);
+    }
+
+    #[rstest]
+    fn incorrect_answer(registry: Registry) {
+        let solver = registry
+            .get(Event::Event2016, Day::Day1)
+            .expect("Missing solver");
+        let expected = Some("6".to_string());
+        let record = Runner::new().run_text(solver, Part::Part1, "custom", "R2, L3", expected);
+        assert_eq!(record.status, RunStatus::Incorrect);
+    }
+
+    #[rstest]
+    fn failed_run(registry: Registry) {
+        let solver = registry
+            .get(Event::Event2016, Day::Day1)
+            .expect("Missing solver");
+        let record = Runner::new().run_text(solver, Part::Part1, "custom", "X", None);
+        assert_eq!(record.status, RunStatus::Failed);
+        assert!(record.error.is_some());
+    }
+
+    #[rstest]
+    fn unimplemented_part(registry: Registry) {
+        let solver = registry
+            .get(Event::Event2023, Day::Day1)
+            .expect("Missing solver");
+        let record = Runner::new().run(solver, Part::Part2, Input::Part2);
+        assert_eq!(record.status, RunStatus::Unimplemented);
+        assert_eq!(record.runs, 0);
+    }
+}